@@ -0,0 +1,101 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use fluent_syntax::ast::Entry;
+use fluent_syntax::parser;
+use regex::Regex;
+
+// Locale every other locale is checked against
+const REFERENCE_LOCALE: &str = "en";
+
+// Fail the build when a locale file does not parse, lacks messages of the reference locale,
+// or when the app asks for a message the reference locale does not have
+fn main() {
+    println!("cargo:rerun-if-changed=locales");
+    println!("cargo:rerun-if-changed=src");
+
+    let mut problems = vec![];
+    let mut locales = vec![];
+    for entry in fs::read_dir("locales").expect("Error reading locales directory") {
+        let dir = entry.expect("Error reading locales directory").path();
+        let tag = dir.file_name().and_then(|name| name.to_str()).unwrap_or_default().to_string();
+        locales.push((tag, message_ids(&dir.join("main.ftl"), &mut problems)));
+    }
+
+    let reference = locales
+        .iter()
+        .find(|(tag, _)| tag == REFERENCE_LOCALE)
+        .map(|(_, ids)| ids.clone())
+        .expect("Reference locale is missing");
+    for (tag, ids) in &locales {
+        for id in reference.difference(ids) {
+            problems.push(format!("locales/{}/main.ftl: missing message {}", tag, id));
+        }
+        for id in ids.difference(&reference) {
+            problems.push(format!(
+                "locales/{}/main.ftl: message {} is not in {}",
+                tag, id, REFERENCE_LOCALE
+            ));
+        }
+    }
+
+    // Messages are looked up with literal IDs, so every lookup can be checked here
+    let lookup = Regex::new(r#"\.t(?:_args)?\(\s*"([^"]+)""#).expect("Invalid lookup regex");
+    for path in source_files(Path::new("src")) {
+        let source = fs::read_to_string(&path).expect("Error reading source file");
+        for id in lookup.captures_iter(&source).map(|captures| captures[1].to_string()) {
+            if !reference.contains(&id) {
+                problems.push(format!(
+                    "{}: message {} is not in {}",
+                    path.display(),
+                    id,
+                    REFERENCE_LOCALE
+                ));
+            }
+        }
+    }
+
+    if !problems.is_empty() {
+        panic!("Locale check failed:\n{}", problems.join("\n"));
+    }
+}
+
+// IDs of messages in locale file, recording parse errors and duplicates as problems
+fn message_ids(path: &Path, problems: &mut Vec<String>) -> BTreeSet<String> {
+    let source = fs::read_to_string(path).unwrap_or_else(|error| {
+        problems.push(format!("{}: {}", path.display(), error));
+        String::new()
+    });
+    let resource = parser::parse(source.as_str()).unwrap_or_else(|(resource, errors)| {
+        for error in errors {
+            problems.push(format!("{}: {:?}", path.display(), error));
+        }
+        resource
+    });
+
+    let mut ids = BTreeSet::new();
+    for entry in resource.body {
+        if let Entry::Message(message) = entry {
+            if !ids.insert(message.id.name.to_string()) {
+                problems.push(format!("{}: duplicate message {}", path.display(), message.id.name));
+            }
+        }
+    }
+    ids
+}
+
+fn source_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir).expect("Error reading source directory") {
+        let path = entry.expect("Error reading source directory").path();
+        match path.is_dir() {
+            true => files.extend(source_files(&path)),
+            false if path.extension().is_some_and(|extension| extension == "rs") => {
+                files.push(path)
+            }
+            false => {}
+        }
+    }
+    files
+}
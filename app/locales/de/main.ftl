@@ -0,0 +1,202 @@
+## Common
+
+loading = Wird geladen...
+saving = Wird gespeichert...
+cancel = Abbrechen
+actions = Aktionen
+language = Sprache
+footer = rustenv { $version } · Entwickelt von Spectrum Studios
+not-found = Seite nicht gefunden :(
+forbidden = Du hast keinen Zugriff auf diese Seite
+
+## Navigation
+
+nav-menu = Menü
+nav-main = Hauptmenü
+nav-home = Start
+nav-settings = Einstellungen
+nav-admin = Verwaltung
+nav-login = Anmelden
+nav-register = Registrieren
+nav-logout = Abmelden
+nav-settings-profile = Profil
+nav-settings-password = Passwort
+nav-settings-email = E-Mail
+nav-settings-two-factor = Zwei-Faktor
+nav-settings-sessions = Sitzungen
+nav-settings-account = Konto
+nav-admin-users = Benutzer
+nav-admin-audit = Audit-Protokoll
+
+pager-label = Seitennavigation
+pager-range = { $first }–{ $last } von { $total }
+pager-previous = Zurück
+pager-next = Weiter
+
+theme = Design
+theme-light = Hell
+theme-dark = Dunkel
+theme-system = System
+
+toast-dismiss = Schließen
+toast-logged-out = Du wurdest abgemeldet
+
+## Errors
+
+error-account-disabled = Dein Konto ist deaktiviert
+error-bad-request = Die Anfrage konnte nicht verarbeitet werden
+error-conflict = Das existiert bereits
+error-forbidden = Dazu fehlt dir die Berechtigung
+error-invalid-input = Bitte korrigiere die markierten Felder
+error-invalid-token = Deine Sitzung ist abgelaufen, bitte melde dich erneut an
+error-invalid-two-factor-code = Der Authentifizierungscode ist falsch
+error-not-found = Nicht gefunden
+error-server = Etwas ist schiefgelaufen, bitte versuche es erneut
+error-service-unavailable = Der Server ist nicht erreichbar, bitte versuche es später erneut
+error-two-factor-required = Gib den Code aus deiner Authenticator-App ein
+error-user-exists = Dieser Benutzername oder diese E-Mail ist bereits vergeben
+error-user-not-exists = Kein Konto mit diesem Benutzernamen oder dieser E-Mail gefunden
+error-wrong-credentials = Falsches Passwort
+
+field-error-username-length = Der Benutzername muss zwischen 3 und 32 Zeichen lang sein
+field-error-username-characters = Der Benutzername darf nur Buchstaben, Ziffern, '.', '_' und '-' enthalten
+field-error-login-required = Benutzername oder E-Mail ist erforderlich
+field-error-email-length = Die E-Mail darf höchstens 254 Zeichen lang sein
+field-error-email-invalid = Die E-Mail ist keine gültige E-Mail-Adresse
+field-error-email-taken = Die E-Mail wird bereits verwendet
+field-error-password-required = Passwort ist erforderlich
+field-error-password-length = Das Passwort muss mindestens 8 Zeichen und höchstens 72 Bytes lang sein
+field-error-password-common = Das Passwort ist zu verbreitet
+field-error-password-incorrect = Das Passwort ist falsch
+field-error-display-name-length = Der Anzeigename muss zwischen 1 und 64 Zeichen lang sein
+field-error-avatar-url = Die Avatar-URL muss eine HTTPS-URL sein
+field-error-locale = Die Sprache muss ein Sprach-Tag wie "de-DE" sein
+field-error-timezone = Die Zeitzone muss ein IANA-Zeitzonenname wie "Europe/Berlin" sein
+field-error-code-format = Der Code muss aus 6 Ziffern bestehen
+field-error-code-incorrect = Der Code ist falsch
+
+## Forms
+
+field-login = Benutzername oder E-Mail
+field-username = Benutzername
+field-email = E-Mail
+field-password = Passwort
+field-current-password = Aktuelles Passwort
+field-new-password = Neues Passwort
+field-two-factor-code = Authentifizierungscode
+field-display-name = Anzeigename
+field-locale = Sprache
+field-timezone = Zeitzone
+
+## Home
+
+home-tagline = Entwicklungsumgebung für Rust-Anwendungen
+home-made-by = Entwickelt von Spectrum Studios
+home-logged-in-as = Angemeldet als { $name }
+
+## Login and registration
+
+login-title = Anmelden
+login-submit = Anmelden
+login-submitting = Anmeldung läuft...
+login-no-account = Noch kein Konto?
+register-title = Registrieren
+register-submit = Konto erstellen
+register-submitting = Konto wird erstellt...
+register-have-account = Bereits ein Konto?
+
+## Settings
+
+profile-submit = Profil speichern
+profile-saved = Profil gespeichert
+avatar = Avatar
+avatar-upload = Avatar hochladen
+avatar-uploading = Wird hochgeladen...
+avatar-remove = Entfernen
+avatar-wrong-type = Wähle ein JPEG-, PNG- oder WebP-Bild
+avatar-unreadable = Die ausgewählte Datei konnte nicht gelesen werden
+
+password-submit = Passwort ändern
+password-changed = Passwort geändert, andere Sitzungen wurden abgemeldet
+
+email-submit = E-Mail ändern
+email-changed = E-Mail geändert
+
+two-factor-title = Zwei-Faktor-Authentifizierung
+two-factor-intro = Schütze dein Konto bei jeder Anmeldung mit einem Code aus einer Authenticator-App.
+two-factor-start = Zwei-Faktor-Authentifizierung einrichten
+two-factor-starting = Wird eingerichtet...
+two-factor-scan = Scanne den QR-Code mit deiner Authenticator-App und gib dann den angezeigten Code ein.
+two-factor-manual-key = Oder gib diesen Schlüssel manuell ein:
+two-factor-enable = Einschalten
+two-factor-enabling = Wird bestätigt...
+two-factor-enabled-since = Die Zwei-Faktor-Authentifizierung ist seit { $date } eingeschaltet.
+two-factor-disable-hint = Gib einen Code aus deiner Authenticator-App ein, um sie auszuschalten.
+two-factor-disable = Ausschalten
+two-factor-disabling = Wird ausgeschaltet...
+
+sessions-count =
+    { $count ->
+        [one] Du bist auf { $count } Gerät angemeldet.
+       *[other] Du bist auf { $count } Geräten angemeldet.
+    }
+sessions-device = Gerät
+sessions-ip = IP-Adresse
+sessions-created = Angemeldet
+sessions-last-used = Zuletzt aktiv
+sessions-unknown-device = Unbekanntes Gerät
+sessions-this-device = Dieses Gerät
+sessions-revoke = Widerrufen
+
+account-delete-title = Konto löschen
+account-delete-intro = Beim Löschen deines Kontos wirst du überall abgemeldet. Deine Daten werden nach Ablauf der Aufbewahrungsfrist endgültig entfernt.
+account-delete = Konto löschen
+account-delete-confirm-title = Konto löschen?
+account-delete-confirm = Du wirst abgemeldet und kannst dich nicht mehr bei diesem Konto anmelden.
+account-deleted = Dein Konto wurde gelöscht
+
+## Admin
+
+users-search = Suchen
+users-search-label = Benutzer suchen
+users-search-placeholder = Nach Benutzername, E-Mail oder Name suchen
+users-empty = Keine Benutzer gefunden
+
+user-title = Benutzer
+user-uuid = UUID
+user-role = Rolle
+user-role-admin = Administrator
+user-role-user = Benutzer
+user-status = Status
+user-status-active = Aktiv
+user-status-disabled = Deaktiviert
+user-disabled-since = Deaktiviert seit { $date }
+user-created = Erstellt
+user-updated = Aktualisiert
+user-last-login = Letzte Anmeldung
+user-grant-admin = Zum Administrator machen
+user-revoke-admin = Administratorrolle entfernen
+user-enable = Konto aktivieren
+user-disable = Konto deaktivieren
+user-audit-events = Audit-Ereignisse anzeigen
+user-self-note = Du kannst hier weder deine eigene Administratorrolle entfernen noch dein eigenes Konto deaktivieren oder löschen.
+user-disable-confirm-title = Konto deaktivieren?
+user-disable-confirm = { $username } kann sich nicht mehr anmelden, bis das Konto wieder aktiviert wird.
+user-disable-confirm-label = Deaktivieren
+user-delete-confirm = { $username } wird gelöscht und die Daten werden nach Ablauf der Aufbewahrungsfrist entfernt. Dies kann nicht rückgängig gemacht werden.
+user-delete-confirm-label = Löschen
+
+audit-filter = Filtern
+audit-event-type = Ereignistyp
+audit-all-events = Alle Ereignisse
+audit-outcome = Ergebnis
+audit-all-outcomes = Alle Ergebnisse
+audit-outcome-success = Erfolg
+audit-outcome-failure = Fehlschlag
+audit-actor-uuid = Akteur-UUID
+audit-target = Ziel
+audit-time = Zeit
+audit-event = Ereignis
+audit-actor = Akteur
+audit-detail = Details
+audit-empty = Keine Audit-Ereignisse gefunden
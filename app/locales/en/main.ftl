@@ -0,0 +1,202 @@
+## Common
+
+loading = Loading...
+saving = Saving...
+cancel = Cancel
+actions = Actions
+language = Language
+footer = rustenv { $version } · Made by Spectrum Studios
+not-found = Page not found :(
+forbidden = You do not have access to this page
+
+## Navigation
+
+nav-menu = Menu
+nav-main = Main
+nav-home = Home
+nav-settings = Settings
+nav-admin = Admin
+nav-login = Log in
+nav-register = Register
+nav-logout = Log out
+nav-settings-profile = Profile
+nav-settings-password = Password
+nav-settings-email = Email
+nav-settings-two-factor = Two-factor
+nav-settings-sessions = Sessions
+nav-settings-account = Account
+nav-admin-users = Users
+nav-admin-audit = Audit log
+
+pager-label = Pagination
+pager-range = { $first }–{ $last } of { $total }
+pager-previous = Previous
+pager-next = Next
+
+theme = Theme
+theme-light = Light
+theme-dark = Dark
+theme-system = System
+
+toast-dismiss = Dismiss
+toast-logged-out = You have been logged out
+
+## Errors
+
+error-account-disabled = Your account is disabled
+error-bad-request = The request could not be processed
+error-conflict = This already exists
+error-forbidden = You do not have permission to do this
+error-invalid-input = Please correct the highlighted fields
+error-invalid-token = Your session has expired, please log in again
+error-invalid-two-factor-code = The authentication code is incorrect
+error-not-found = Not found
+error-server = Something went wrong, please try again
+error-service-unavailable = Could not reach the server, please try again later
+error-two-factor-required = Enter the code from your authenticator app
+error-user-exists = This username or email is already taken
+error-user-not-exists = No account found with this username or email
+error-wrong-credentials = Incorrect password
+
+field-error-username-length = Username must be between 3 and 32 characters
+field-error-username-characters = Username may only contain letters, numbers, '.', '_' and '-'
+field-error-login-required = Username or email is required
+field-error-email-length = Email must be at most 254 characters
+field-error-email-invalid = Email is not a valid email address
+field-error-email-taken = Email is already in use
+field-error-password-required = Password is required
+field-error-password-length = Password must be at least 8 characters and at most 72 bytes
+field-error-password-common = Password is too common
+field-error-password-incorrect = Password is incorrect
+field-error-display-name-length = Display name must be between 1 and 64 characters
+field-error-avatar-url = Avatar URL must be an HTTPS URL
+field-error-locale = Locale must be a language tag such as "en-US"
+field-error-timezone = Timezone must be an IANA time zone name such as "Europe/Berlin"
+field-error-code-format = Code must be 6 digits
+field-error-code-incorrect = Code is incorrect
+
+## Forms
+
+field-login = Username or email
+field-username = Username
+field-email = Email
+field-password = Password
+field-current-password = Current password
+field-new-password = New password
+field-two-factor-code = Authentication code
+field-display-name = Display name
+field-locale = Locale
+field-timezone = Timezone
+
+## Home
+
+home-tagline = Rust application development environment
+home-made-by = Made by Spectrum Studios
+home-logged-in-as = Logged in as { $name }
+
+## Login and registration
+
+login-title = Log in
+login-submit = Log in
+login-submitting = Logging in...
+login-no-account = No account yet?
+register-title = Register
+register-submit = Create account
+register-submitting = Creating account...
+register-have-account = Already have an account?
+
+## Settings
+
+profile-submit = Save profile
+profile-saved = Profile saved
+avatar = Avatar
+avatar-upload = Upload avatar
+avatar-uploading = Uploading...
+avatar-remove = Remove
+avatar-wrong-type = Choose a JPEG, PNG or WebP image
+avatar-unreadable = The selected file could not be read
+
+password-submit = Change password
+password-changed = Password changed, other sessions have been logged out
+
+email-submit = Change email
+email-changed = Email changed
+
+two-factor-title = Two-factor authentication
+two-factor-intro = Protect your account with a code from an authenticator app each time you log in.
+two-factor-start = Set up two-factor authentication
+two-factor-starting = Setting up...
+two-factor-scan = Scan the QR code with your authenticator app, then enter the code it shows.
+two-factor-manual-key = Or enter this key manually:
+two-factor-enable = Turn on
+two-factor-enabling = Confirming...
+two-factor-enabled-since = Two-factor authentication is on since { $date }.
+two-factor-disable-hint = Enter a code from your authenticator app to turn it off.
+two-factor-disable = Turn off
+two-factor-disabling = Turning off...
+
+sessions-count =
+    { $count ->
+        [one] You are logged in on { $count } device.
+       *[other] You are logged in on { $count } devices.
+    }
+sessions-device = Device
+sessions-ip = IP address
+sessions-created = Logged in
+sessions-last-used = Last active
+sessions-unknown-device = Unknown device
+sessions-this-device = This device
+sessions-revoke = Revoke
+
+account-delete-title = Delete account
+account-delete-intro = Deleting your account logs you out everywhere. Your data is removed permanently after the retention period.
+account-delete = Delete account
+account-delete-confirm-title = Delete account?
+account-delete-confirm = You will be logged out and will not be able to log in to this account again.
+account-deleted = Your account has been deleted
+
+## Admin
+
+users-search = Search
+users-search-label = Search users
+users-search-placeholder = Search by username, email or name
+users-empty = No users found
+
+user-title = User
+user-uuid = UUID
+user-role = Role
+user-role-admin = Admin
+user-role-user = User
+user-status = Status
+user-status-active = Active
+user-status-disabled = Disabled
+user-disabled-since = Disabled since { $date }
+user-created = Created
+user-updated = Updated
+user-last-login = Last login
+user-grant-admin = Make admin
+user-revoke-admin = Remove admin role
+user-enable = Enable account
+user-disable = Disable account
+user-audit-events = View audit events
+user-self-note = You cannot remove your own admin role, or disable or delete your own account here.
+user-disable-confirm-title = Disable account?
+user-disable-confirm = { $username } will be unable to log in until the account is enabled again.
+user-disable-confirm-label = Disable
+user-delete-confirm = { $username } will be deleted and their data purged after the retention period. This cannot be undone.
+user-delete-confirm-label = Delete
+
+audit-filter = Filter
+audit-event-type = Event type
+audit-all-events = All events
+audit-outcome = Outcome
+audit-all-outcomes = All outcomes
+audit-outcome-success = Success
+audit-outcome-failure = Failure
+audit-actor-uuid = Actor UUID
+audit-target = Target
+audit-time = Time
+audit-event = Event
+audit-actor = Actor
+audit-detail = Detail
+audit-empty = No audit events found
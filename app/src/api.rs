@@ -0,0 +1,59 @@
+use rustenv_client::{ApiClient, ClientError};
+use rustenv_shared::auth::AuthErrorType;
+use rustenv_shared::validation::FieldError;
+
+use crate::i18n::I18n;
+
+// Server the app talks to, overridden with API_BASE_URL at build time
+const API_BASE_URL: &str = match option_env!("API_BASE_URL") {
+    Some(url) => url,
+    None => "http://localhost:3001",
+};
+
+// Create API client for the configured server
+pub fn api_client() -> ApiClient {
+    ApiClient::new(API_BASE_URL)
+}
+
+// User-facing message for a failed API call
+pub fn error_message(i18n: &I18n, error: &ClientError) -> String {
+    match error.error_type() {
+        AuthErrorType::AccountDisabled => i18n.t("error-account-disabled"),
+        AuthErrorType::BadRequest => i18n.t("error-bad-request"),
+        AuthErrorType::Conflict => i18n.t("error-conflict"),
+        AuthErrorType::Forbidden => i18n.t("error-forbidden"),
+        AuthErrorType::InvalidInput => i18n.t("error-invalid-input"),
+        AuthErrorType::InvalidToken => i18n.t("error-invalid-token"),
+        AuthErrorType::InvalidTwoFactorCode => i18n.t("error-invalid-two-factor-code"),
+        AuthErrorType::NotFound => i18n.t("error-not-found"),
+        AuthErrorType::ServerError | AuthErrorType::TokenGeneration => i18n.t("error-server"),
+        AuthErrorType::ServiceUnavailable => i18n.t("error-service-unavailable"),
+        AuthErrorType::TwoFactorRequired => i18n.t("error-two-factor-required"),
+        AuthErrorType::UserExists => i18n.t("error-user-exists"),
+        AuthErrorType::UserNotExists => i18n.t("error-user-not-exists"),
+        AuthErrorType::WrongCredentials => i18n.t("error-wrong-credentials"),
+    }
+}
+
+// User-facing message for a field error, keeping the server's message for errors the app does not know
+pub fn field_error_message(i18n: &I18n, field: &str, error: &FieldError) -> String {
+    match (field, error.code.as_str()) {
+        ("username", "length") => i18n.t("field-error-username-length"),
+        (_, "username_charset") => i18n.t("field-error-username-characters"),
+        (_, "login_required") => i18n.t("field-error-login-required"),
+        ("email", "length") => i18n.t("field-error-email-length"),
+        ("email", "email") => i18n.t("field-error-email-invalid"),
+        ("email", "email_taken") => i18n.t("field-error-email-taken"),
+        (_, "password_required") => i18n.t("field-error-password-required"),
+        (_, "password_length") => i18n.t("field-error-password-length"),
+        (_, "password_common") => i18n.t("field-error-password-common"),
+        (_, "password_incorrect") => i18n.t("field-error-password-incorrect"),
+        (_, "display_name_length") => i18n.t("field-error-display-name-length"),
+        (_, "avatar_url") => i18n.t("field-error-avatar-url"),
+        (_, "locale") => i18n.t("field-error-locale"),
+        (_, "timezone") => i18n.t("field-error-timezone"),
+        ("code" | "two_factor_code", "regex") => i18n.t("field-error-code-format"),
+        (_, "code_incorrect") => i18n.t("field-error-code-incorrect"),
+        _ => error.message.clone(),
+    }
+}
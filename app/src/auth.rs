@@ -0,0 +1,218 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use futures::StreamExt;
+use futures::channel::mpsc;
+use gloo_events::EventListener;
+use rustenv_client::{ApiClient, ClientError, Tokens};
+use rustenv_shared::auth::AuthErrorType;
+use rustenv_shared::user::UserInformation;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::StorageEvent;
+use yew::prelude::*;
+
+use crate::api::{api_client, error_message};
+use crate::i18n::use_i18n;
+use crate::storage::{self, TOKENS_KEY, USER_KEY};
+use crate::toast::use_toast;
+
+// Authentication state shared through the app
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AuthState {
+    pub user: Option<UserInformation>,
+    pub tokens: Tokens,
+    // Whether a stored session is still being checked with the server
+    pub restoring: bool,
+}
+
+impl AuthState {
+    pub fn is_authenticated(&self) -> bool {
+        self.user.is_some()
+    }
+
+    pub fn is_admin(&self) -> bool {
+        self.user.as_ref().is_some_and(|user| user.is_admin)
+    }
+}
+
+pub enum AuthAction {
+    Restored(Option<UserInformation>),
+    LoggedIn(UserInformation),
+    // Own profile changed, such as from the settings page
+    UserUpdated(UserInformation),
+    // Logging out clears the client's tokens, which ends the session here
+    TokensChanged(Tokens),
+}
+
+impl Reducible for AuthState {
+    type Action = AuthAction;
+
+    fn reduce(self: Rc<Self>, action: AuthAction) -> Rc<Self> {
+        let state = match action {
+            AuthAction::Restored(user) => AuthState { user, restoring: false, ..(*self).clone() },
+            AuthAction::LoggedIn(user) => {
+                AuthState { user: Some(user), restoring: false, ..(*self).clone() }
+            }
+            AuthAction::UserUpdated(user) => AuthState { user: Some(user), ..(*self).clone() },
+            // Losing the request token ends the session, wherever it happened
+            AuthAction::TokensChanged(tokens) if tokens.request_token.is_none() => {
+                AuthState::default()
+            }
+            AuthAction::TokensChanged(tokens) => AuthState { tokens, ..(*self).clone() },
+        };
+        Rc::new(state)
+    }
+}
+
+pub type AuthContext = UseReducerHandle<AuthState>;
+
+// Authentication state from the surrounding AuthProvider
+#[hook]
+pub fn use_auth() -> AuthContext {
+    use_context::<AuthContext>().expect("AuthProvider context")
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AuthProviderProps {
+    pub children: Html,
+}
+
+// Provide API client and authentication state, persisted to local storage and synced across tabs
+#[function_component(AuthProvider)]
+pub fn auth_provider(props: &AuthProviderProps) -> Html {
+    let state = use_reducer(|| {
+        let tokens = storage::load::<Tokens>(TOKENS_KEY).unwrap_or_default();
+        let user = storage::load::<UserInformation>(USER_KEY);
+        let restoring = tokens.request_token.is_some();
+        AuthState { user: user.filter(|_| restoring), tokens, restoring }
+    });
+
+    let toaster = use_toast();
+
+    // Errors arrive long after this render, so they are translated for the locale current by then
+    let i18n = use_i18n();
+    let current_i18n = use_mut_ref(|| i18n.clone());
+    *current_i18n.borrow_mut() = i18n;
+
+    // Client persists its tokens itself and reports changes, including refreshes, over a channel
+    let session = use_memo((), {
+        let tokens = state.tokens.clone();
+        move |_| {
+            let (sender, receiver) = mpsc::unbounded::<Tokens>();
+            let (error_sender, error_receiver) = mpsc::unbounded::<ClientError>();
+            let client = api_client()
+                .with_tokens(tokens)
+                .on_tokens_changed(move |tokens| {
+                    match tokens.is_empty() {
+                        true => storage::remove(TOKENS_KEY),
+                        false => storage::save(TOKENS_KEY, tokens),
+                    }
+                    let _ = sender.unbounded_send(tokens.clone());
+                })
+                .on_error(move |error| {
+                    // Views show their own errors, only problems with the server itself are global
+                    if matches!(
+                        error.error_type(),
+                        AuthErrorType::ServiceUnavailable | AuthErrorType::ServerError
+                    ) {
+                        let _ = error_sender.unbounded_send(error.clone());
+                    }
+                });
+            (client, RefCell::new(Some((receiver, error_receiver))))
+        }
+    });
+    let client = session.0.clone();
+
+    use_effect_with((), {
+        let session = session.clone();
+        let dispatcher = state.dispatcher();
+        move |_| {
+            if let Some((mut receiver, mut error_receiver)) = session.1.borrow_mut().take() {
+                spawn_local(async move {
+                    while let Some(tokens) = receiver.next().await {
+                        dispatcher.dispatch(AuthAction::TokensChanged(tokens));
+                    }
+                });
+                spawn_local(async move {
+                    while let Some(error) = error_receiver.next().await {
+                        toaster.error(error_message(&current_i18n.borrow(), &error));
+                    }
+                });
+            }
+        }
+    });
+
+    // Check a restored session is still valid, keeping it while the server is unreachable
+    use_effect_with((), {
+        let client = client.clone();
+        let state = state.clone();
+        move |_| {
+            if state.restoring {
+                let stored_user = state.user.clone();
+                spawn_local(async move {
+                    match client.me().await {
+                        Ok(user) => state.dispatch(AuthAction::Restored(Some(user))),
+                        Err(error) => match error.error_type() {
+                            AuthErrorType::InvalidToken
+                            | AuthErrorType::AccountDisabled
+                            | AuthErrorType::UserNotExists => {
+                                client.clear_tokens();
+                                state.dispatch(AuthAction::Restored(None));
+                            }
+                            _ => state.dispatch(AuthAction::Restored(stored_user)),
+                        },
+                    }
+                });
+            }
+        }
+    });
+
+    use_effect_with(state.user.clone(), |user| match user {
+        Some(user) => storage::save(USER_KEY, user),
+        None => storage::remove(USER_KEY),
+    });
+
+    // Follow logins and logouts in other tabs, which only see each other through storage events
+    use_effect_with((), {
+        let client = client.clone();
+        let dispatcher = state.dispatcher();
+        move |_| {
+            let listener = web_sys::window().map(|window| {
+                EventListener::new(&window, "storage", move |event| {
+                    let Some(event) = event.dyn_ref::<StorageEvent>() else {
+                        return;
+                    };
+                    match event.key().as_deref() {
+                        Some(TOKENS_KEY) => client.set_tokens(
+                            event
+                                .new_value()
+                                .and_then(|value| storage::parse::<Tokens>(&value))
+                                .unwrap_or_default(),
+                        ),
+                        Some(USER_KEY) => {
+                            if let Some(user) = event
+                                .new_value()
+                                .and_then(|value| storage::parse::<UserInformation>(&value))
+                            {
+                                dispatcher.dispatch(AuthAction::LoggedIn(user));
+                            }
+                        }
+                        // Storage was cleared
+                        None => client.clear_tokens(),
+                        Some(_) => {}
+                    }
+                })
+            });
+            move || drop(listener)
+        }
+    });
+
+    html! {
+        <ContextProvider<ApiClient> context={client}>
+            <ContextProvider<AuthContext> context={state}>
+                {props.children.clone()}
+            </ContextProvider<AuthContext>>
+        </ContextProvider<ApiClient>>
+    }
+}
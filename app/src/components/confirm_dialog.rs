@@ -0,0 +1,65 @@
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+
+#[derive(Properties, PartialEq)]
+pub struct ConfirmDialogProps {
+    pub title: AttrValue,
+    pub message: AttrValue,
+    pub confirm_label: AttrValue,
+    #[prop_or_default]
+    pub busy: bool,
+    pub onconfirm: Callback<()>,
+    pub oncancel: Callback<()>,
+}
+
+// Modal dialog asking to confirm a destructive action
+#[function_component(ConfirmDialog)]
+pub fn confirm_dialog(props: &ConfirmDialogProps) -> Html {
+    let i18n = use_i18n();
+    let onconfirm = props.onconfirm.reform(|_: MouseEvent| ());
+    let oncancel = props.oncancel.reform(|_: MouseEvent| ());
+    let onkeydown = {
+        let oncancel = props.oncancel.clone();
+        Callback::from(move |event: KeyboardEvent| {
+            if event.key() == "Escape" {
+                oncancel.emit(());
+            }
+        })
+    };
+
+    html! {
+        <div class="fixed inset-0 z-50 flex justify-center items-center bg-black/50" {onkeydown}>
+            <div
+                role="alertdialog"
+                aria-modal="true"
+                aria-labelledby="confirm-dialog-title"
+                aria-describedby="confirm-dialog-message"
+                class="flex flex-col w-full max-w-md rounded-md space-y-4 border border-slate-300 dark:border-slate-700 text-slate-800 dark:text-slate-100 shadow-lg mx-10 p-6 bg-white dark:bg-slate-900"
+            >
+                <h2 id="confirm-dialog-title" class="text-xl font-medium">{&props.title}</h2>
+                <p id="confirm-dialog-message">{&props.message}</p>
+                <div class="flex justify-end space-x-2">
+                    <button
+                        type="button"
+                        autofocus=true
+                        disabled={props.busy}
+                        onclick={oncancel}
+                        class="rounded-md border border-slate-300 dark:border-slate-600 px-4 py-2 hover:bg-slate-100 dark:hover:bg-slate-800"
+                    >
+                        {i18n.t("cancel")}
+                    </button>
+                    <button
+                        type="button"
+                        disabled={props.busy}
+                        aria-busy={props.busy.to_string()}
+                        onclick={onconfirm}
+                        class="rounded-md bg-red-600 hover:bg-red-700 disabled:opacity-60 text-white font-medium px-4 py-2"
+                    >
+                        {&props.confirm_label}
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,118 @@
+use rustenv_shared::validation::FieldError;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::api::field_error_message;
+use crate::i18n::use_i18n;
+
+#[derive(Properties, PartialEq)]
+pub struct TextFieldProps {
+    pub label: AttrValue,
+    pub name: AttrValue,
+    #[prop_or(AttrValue::Static("text"))]
+    pub input_type: AttrValue,
+    #[prop_or_default]
+    pub autocomplete: Option<AttrValue>,
+    pub value: AttrValue,
+    #[prop_or_default]
+    pub errors: Vec<FieldError>,
+    #[prop_or_default]
+    pub disabled: bool,
+    pub oninput: Callback<String>,
+}
+
+// Labelled text input with its validation errors
+#[function_component(TextField)]
+pub fn text_field(props: &TextFieldProps) -> Html {
+    let i18n = use_i18n();
+    let oninput = props
+        .oninput
+        .reform(|event: InputEvent| event.target_unchecked_into::<HtmlInputElement>().value());
+    let invalid = !props.errors.is_empty();
+    let error_id = format!("{}-errors", props.name);
+    let border = match invalid {
+        true => "border-red-500 dark:border-red-400",
+        false => "border-slate-300 dark:border-slate-600",
+    };
+
+    html! {
+        <div class="flex flex-col space-y-1">
+            <label for={props.name.clone()} class="text-sm font-medium">{&props.label}</label>
+            <input
+                id={props.name.clone()}
+                name={props.name.clone()}
+                type={props.input_type.clone()}
+                autocomplete={props.autocomplete.clone()}
+                value={props.value.clone()}
+                disabled={props.disabled}
+                aria-invalid={invalid.to_string()}
+                aria-describedby={invalid.then(|| error_id.clone())}
+                class={classes!("rounded-md", "border", "px-3", "py-2", "bg-white", "dark:bg-slate-800", "focus:outline-none", "focus:ring-2", "focus:ring-sky-500", border)}
+                {oninput}
+            />
+            if invalid {
+                <ul id={error_id} class="text-sm text-red-600 dark:text-red-400">
+                    { for props.errors.iter().map(|error| html! { <li>{field_error_message(&i18n, &props.name, error)}</li> }) }
+                </ul>
+            }
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct FormAlertProps {
+    pub message: Option<String>,
+}
+
+// Error banner for a failed form submission
+#[function_component(FormAlert)]
+pub fn form_alert(props: &FormAlertProps) -> Html {
+    match &props.message {
+        Some(message) => html! {
+            <p role="alert" class="rounded-md border border-red-300 dark:border-red-700 bg-red-50 dark:bg-red-950 text-red-700 dark:text-red-300 px-3 py-2 text-sm">
+                {message}
+            </p>
+        },
+        None => html! {},
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct FormSuccessProps {
+    pub message: Option<AttrValue>,
+}
+
+// Confirmation banner for a successful form submission
+#[function_component(FormSuccess)]
+pub fn form_success(props: &FormSuccessProps) -> Html {
+    match &props.message {
+        Some(message) => html! {
+            <p role="status" class="rounded-md border border-emerald-300 dark:border-emerald-700 bg-emerald-50 dark:bg-emerald-950 text-emerald-700 dark:text-emerald-300 px-3 py-2 text-sm">
+                {message}
+            </p>
+        },
+        None => html! {},
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SubmitButtonProps {
+    pub label: AttrValue,
+    pub loading_label: AttrValue,
+    pub loading: bool,
+}
+
+// Form submit button, disabled while the form is submitting
+#[function_component(SubmitButton)]
+pub fn submit_button(props: &SubmitButtonProps) -> Html {
+    html! {
+        <button
+            type="submit"
+            disabled={props.loading}
+            aria-busy={props.loading.to_string()}
+            class="rounded-md bg-sky-600 hover:bg-sky-700 disabled:opacity-60 disabled:cursor-wait text-white font-medium px-4 py-2"
+        >
+            { if props.loading { &props.loading_label } else { &props.label } }
+        </button>
+    }
+}
@@ -0,0 +1,61 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::AppRoute;
+use crate::auth::use_auth;
+use crate::components::language_switcher::LanguageSwitcher;
+use crate::components::nav::{NavLink, Section};
+use crate::components::theme_toggle::ThemeToggle;
+use crate::components::user_menu::UserMenu;
+use crate::i18n::use_i18n;
+
+#[derive(Properties, PartialEq)]
+pub struct HeaderProps {
+    // Whether the sidebar menu is open on small screens
+    pub menu_open: bool,
+    pub onmenu: Callback<MouseEvent>,
+}
+
+// Application header with section links, language and theme choice and user menu
+#[function_component(Header)]
+pub fn header(props: &HeaderProps) -> Html {
+    let auth = use_auth();
+    let i18n = use_i18n();
+    let route = use_route::<AppRoute>();
+    let current = route.as_ref().and_then(Section::of);
+
+    html! {
+        <header class="flex justify-between items-center w-full border-b border-slate-200 dark:border-slate-700 bg-white dark:bg-slate-900 text-slate-800 dark:text-slate-100 px-6 py-3">
+            <div class="flex items-center space-x-6">
+                <button
+                    type="button"
+                    aria-label={i18n.t("nav-menu")}
+                    aria-expanded={props.menu_open.to_string()}
+                    onclick={props.onmenu.clone()}
+                    class="md:hidden text-2xl leading-none"
+                >
+                    {"☰"}
+                </button>
+                <Link<AppRoute> to={AppRoute::Home} classes="text-xl font-medium">{"rustenv"}</Link<AppRoute>>
+                <nav aria-label={i18n.t("nav-main")} class="hidden md:flex space-x-6">
+                    { for Section::ALL.into_iter().filter(|section| section.is_visible(&auth)).map(|section| html! {
+                        <NavLink
+                            to={section.route()}
+                            active={current == Some(section)}
+                            classes="border-b-2 pb-1"
+                            active_classes="border-sky-500"
+                            inactive_classes="border-transparent hover:border-slate-400"
+                        >
+                            {section.label(&i18n)}
+                        </NavLink>
+                    }) }
+                </nav>
+            </div>
+            <div class="flex items-center space-x-4">
+                <LanguageSwitcher />
+                <ThemeToggle />
+                <UserMenu />
+            </div>
+        </header>
+    }
+}
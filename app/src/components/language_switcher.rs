@@ -0,0 +1,33 @@
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use crate::i18n::{Locale, use_i18n};
+
+// Select choosing the interface language, each listed by its own name
+#[function_component(LanguageSwitcher)]
+pub fn language_switcher() -> Html {
+    let i18n = use_i18n();
+    let onchange = {
+        let set_locale = i18n.set_locale.clone();
+        Callback::from(move |event: Event| {
+            let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+            if let Some(locale) = Locale::from_tag(&value) {
+                set_locale.emit(locale);
+            }
+        })
+    };
+
+    html! {
+        <select
+            aria-label={i18n.t("language")}
+            class="rounded-md border border-slate-300 dark:border-slate-600 bg-white dark:bg-slate-900 text-sm px-2 py-1"
+            {onchange}
+        >
+            { for Locale::ALL.into_iter().map(|locale| html! {
+                <option value={locale.tag()} lang={locale.tag()} selected={locale == i18n.locale}>
+                    {locale.name()}
+                </option>
+            }) }
+        </select>
+    }
+}
@@ -0,0 +1,11 @@
+pub mod confirm_dialog;
+pub mod form;
+pub mod header;
+pub mod language_switcher;
+pub mod nav;
+pub mod pager;
+pub mod protected_route;
+pub mod shell;
+pub mod sidebar;
+pub mod theme_toggle;
+pub mod user_menu;
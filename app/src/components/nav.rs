@@ -0,0 +1,116 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::AppRoute;
+use crate::auth::AuthState;
+use crate::components::protected_route::Access;
+use crate::i18n::I18n;
+
+// Top-level part of the app, linked from the header
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Section {
+    Home,
+    Settings,
+    Admin,
+}
+
+impl Section {
+    pub const ALL: [Section; 3] = [Section::Home, Section::Settings, Section::Admin];
+
+    // Section route belongs to, None for pages outside the navigation such as login
+    pub fn of(route: &AppRoute) -> Option<Section> {
+        match route {
+            AppRoute::Home => Some(Section::Home),
+            AppRoute::Settings
+            | AppRoute::SettingsPassword
+            | AppRoute::SettingsEmail
+            | AppRoute::SettingsTwoFactor
+            | AppRoute::SettingsSessions
+            | AppRoute::SettingsAccount => Some(Section::Settings),
+            AppRoute::Admin | AppRoute::AdminUser { .. } | AppRoute::AdminAudit => {
+                Some(Section::Admin)
+            }
+            AppRoute::Login | AppRoute::Register | AppRoute::NotFound => None,
+        }
+    }
+
+    pub fn label(self, i18n: &I18n) -> String {
+        match self {
+            Section::Home => i18n.t("nav-home"),
+            Section::Settings => i18n.t("nav-settings"),
+            Section::Admin => i18n.t("nav-admin"),
+        }
+    }
+
+    // Landing page of section
+    pub fn route(self) -> AppRoute {
+        match self {
+            Section::Home => AppRoute::Home,
+            Section::Settings => AppRoute::Settings,
+            Section::Admin => AppRoute::Admin,
+        }
+    }
+
+    // Pages of section listed in the sidebar
+    pub fn pages(self, i18n: &I18n) -> Vec<(AppRoute, String)> {
+        match self {
+            Section::Home => vec![],
+            Section::Settings => vec![
+                (AppRoute::Settings, i18n.t("nav-settings-profile")),
+                (AppRoute::SettingsPassword, i18n.t("nav-settings-password")),
+                (AppRoute::SettingsEmail, i18n.t("nav-settings-email")),
+                (AppRoute::SettingsTwoFactor, i18n.t("nav-settings-two-factor")),
+                (AppRoute::SettingsSessions, i18n.t("nav-settings-sessions")),
+                (AppRoute::SettingsAccount, i18n.t("nav-settings-account")),
+            ],
+            Section::Admin => vec![
+                (AppRoute::Admin, i18n.t("nav-admin-users")),
+                (AppRoute::AdminAudit, i18n.t("nav-admin-audit")),
+            ],
+        }
+    }
+
+    // Whether user may see section, judged by its landing page
+    pub fn is_visible(self, auth: &AuthState) -> bool {
+        match self.route().access() {
+            Access::Public => true,
+            Access::Guest => !auth.is_authenticated(),
+            Access::User => auth.is_authenticated(),
+            Access::Admin => auth.is_admin(),
+        }
+    }
+}
+
+// Whether link points at the current page, counting a user's page as part of the user list
+pub fn is_current(route: Option<&AppRoute>, to: &AppRoute) -> bool {
+    match (route, to) {
+        (Some(AppRoute::AdminUser { .. }), AppRoute::Admin) => true,
+        (Some(route), to) => route == to,
+        (None, _) => false,
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct NavLinkProps {
+    pub to: AppRoute,
+    pub active: bool,
+    pub classes: Classes,
+    pub active_classes: Classes,
+    #[prop_or_default]
+    pub inactive_classes: Classes,
+    pub children: Html,
+}
+
+// Router link styled as the current page when active
+#[function_component(NavLink)]
+pub fn nav_link(props: &NavLinkProps) -> Html {
+    let state = match props.active {
+        true => props.active_classes.clone(),
+        false => props.inactive_classes.clone(),
+    };
+    let classes = classes!(props.classes.clone(), state);
+
+    html! {
+        <Link<AppRoute> to={props.to.clone()} {classes}>{props.children.clone()}</Link<AppRoute>>
+    }
+}
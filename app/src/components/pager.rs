@@ -0,0 +1,46 @@
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+
+#[derive(Properties, PartialEq)]
+pub struct PagerProps {
+    pub offset: u64,
+    pub limit: u32,
+    pub total: i64,
+    pub has_prev: bool,
+    pub has_next: bool,
+    pub onprev: Callback<()>,
+    pub onnext: Callback<()>,
+}
+
+// Previous and next page controls with the range of shown items
+#[function_component(Pager)]
+pub fn pager(props: &PagerProps) -> Html {
+    let i18n = use_i18n();
+    let first = match props.total {
+        0 => 0,
+        _ => props.offset + 1,
+    };
+    let last = (props.offset + u64::from(props.limit)).min(props.total.max(0) as u64);
+    let button = "rounded-md border border-slate-300 dark:border-slate-600 px-3 py-1 hover:bg-slate-100 dark:hover:bg-slate-800 disabled:opacity-50 disabled:cursor-not-allowed";
+
+    html! {
+        <nav aria-label={i18n.t("pager-label")} class="flex justify-between items-center text-sm">
+            <p>
+                {i18n.t_args("pager-range", &[
+                    ("first", i18n.number(first as f64).into()),
+                    ("last", i18n.number(last as f64).into()),
+                    ("total", i18n.number(props.total as f64).into()),
+                ])}
+            </p>
+            <div class="flex space-x-2">
+                <button type="button" class={button} disabled={!props.has_prev} onclick={props.onprev.reform(|_: MouseEvent| ())}>
+                    {i18n.t("pager-previous")}
+                </button>
+                <button type="button" class={button} disabled={!props.has_next} onclick={props.onnext.reform(|_: MouseEvent| ())}>
+                    {i18n.t("pager-next")}
+                </button>
+            </div>
+        </nav>
+    }
+}
@@ -0,0 +1,68 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::auth::use_auth;
+use crate::i18n::use_i18n;
+use crate::views::forbidden::Forbidden;
+use crate::{AppRoute, RedirectQuery};
+
+// Who may see a route
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Access {
+    Public,
+    // Only logged out users, such as the login page
+    Guest,
+    User,
+    Admin,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ProtectedRouteProps {
+    pub access: Access,
+    pub children: Html,
+}
+
+// Render route if the current user has access, otherwise redirect to login or away from it
+#[function_component(ProtectedRoute)]
+pub fn protected_route(props: &ProtectedRouteProps) -> Html {
+    let auth = use_auth();
+    let i18n = use_i18n();
+    let navigator = use_navigator().expect("ProtectedRoute must be rendered inside a router");
+    let location = use_location().expect("ProtectedRoute must be rendered inside a router");
+
+    // Wait for a restored session to be checked before deciding
+    let redirect = match (props.access, auth.restoring, auth.is_authenticated()) {
+        (Access::Public, _, _) | (_, true, _) => None,
+        (Access::Guest, false, true) => {
+            Some((location.query::<RedirectQuery>().unwrap_or_default().route(), None))
+        }
+        (Access::User | Access::Admin, false, false) => {
+            let redirect = format!("{}{}", location.path(), location.query_str());
+            Some((AppRoute::Login, Some(RedirectQuery { redirect: Some(redirect) })))
+        }
+        _ => None,
+    };
+
+    use_effect_with(redirect.clone(), move |redirect| match redirect {
+        Some((route, Some(query))) => {
+            if let Err(error) = navigator.replace_with_query(route, query) {
+                log::error!("Error redirecting to login: {}", error);
+            }
+        }
+        Some((route, None)) => navigator.replace(route),
+        None => {}
+    });
+
+    if props.access != Access::Public && auth.restoring {
+        return html! {
+            <p aria-busy="true" class="text-slate-500 dark:text-slate-400">{i18n.t("loading")}</p>
+        };
+    }
+    if redirect.is_some() {
+        return html! {};
+    }
+    if props.access == Access::Admin && !auth.is_admin() {
+        return html! { <Forbidden /> };
+    }
+    props.children.clone()
+}
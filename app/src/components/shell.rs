@@ -0,0 +1,42 @@
+use yew::prelude::*;
+
+use crate::components::header::Header;
+use crate::components::sidebar::Sidebar;
+use crate::i18n::use_i18n;
+
+#[derive(Properties, PartialEq)]
+pub struct AppShellProps {
+    pub children: Html,
+}
+
+// Application frame around the current view, which scrolls with the footer below it
+#[function_component(AppShell)]
+pub fn app_shell(props: &AppShellProps) -> Html {
+    let i18n = use_i18n();
+    let menu_open = use_state(|| false);
+    let onmenu = {
+        let menu_open = menu_open.clone();
+        Callback::from(move |_: MouseEvent| menu_open.set(!*menu_open))
+    };
+    let onclose = {
+        let menu_open = menu_open.clone();
+        Callback::from(move |()| menu_open.set(false))
+    };
+
+    html! {
+        <div class="flex flex-col w-full h-full">
+            <Header menu_open={*menu_open} {onmenu} />
+            <div class="flex flex-1 min-h-0">
+                <Sidebar open={*menu_open} {onclose} />
+                <div class="flex flex-col flex-1 min-w-0 overflow-y-auto">
+                    <main class="flex flex-1 justify-center items-center py-10">
+                        {props.children.clone()}
+                    </main>
+                    <footer class="border-t border-slate-200 dark:border-slate-700 text-sm text-center text-slate-500 dark:text-slate-400 px-6 py-4">
+                        {i18n.t_args("footer", &[("version", env!("CARGO_PKG_VERSION").into())])}
+                    </footer>
+                </div>
+            </div>
+        </div>
+    }
+}
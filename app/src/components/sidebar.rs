@@ -0,0 +1,81 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::AppRoute;
+use crate::auth::use_auth;
+use crate::components::nav::{NavLink, Section, is_current};
+use crate::i18n::use_i18n;
+
+#[derive(Properties, PartialEq)]
+pub struct SidebarProps {
+    // Whether the menu drawer is open on small screens
+    pub open: bool,
+    pub onclose: Callback<()>,
+}
+
+// Pages of the current section beside the content, and a drawer with all navigation on small screens
+#[function_component(Sidebar)]
+pub fn sidebar(props: &SidebarProps) -> Html {
+    let auth = use_auth();
+    let i18n = use_i18n();
+    let route = use_route::<AppRoute>();
+    let current = route.as_ref().and_then(Section::of);
+
+    // Close the drawer after navigating
+    use_effect_with(route.clone(), {
+        let onclose = props.onclose.clone();
+        move |_| onclose.emit(())
+    });
+
+    let links = |section: Section| {
+        html! {
+            <nav aria-label={section.label(&i18n)} class="flex flex-col space-y-1">
+                { for section.pages(&i18n).into_iter().map(|(to, label)| html! {
+                    <NavLink
+                        active={is_current(route.as_ref(), &to)}
+                        {to}
+                        classes="rounded-md px-3 py-2"
+                        active_classes="bg-sky-100 dark:bg-sky-900 text-sky-800 dark:text-sky-100"
+                        inactive_classes="hover:bg-slate-100 dark:hover:bg-slate-800"
+                    >
+                        {label}
+                    </NavLink>
+                }) }
+            </nav>
+        }
+    };
+    let panel = "flex flex-col bg-white dark:bg-slate-900 text-slate-800 dark:text-slate-100 overflow-y-auto p-4";
+
+    html! {
+        <>
+            if let Some(section) = current.filter(|section| !section.pages(&i18n).is_empty()) {
+                <aside class={classes!(panel, "hidden", "md:flex", "w-56", "shrink-0", "border-r", "border-slate-200", "dark:border-slate-700", "space-y-4")}>
+                    <h2 class="text-sm font-medium uppercase text-slate-500 dark:text-slate-400 px-3">{section.label(&i18n)}</h2>
+                    {links(section)}
+                </aside>
+            }
+            if props.open {
+                <div class="fixed inset-0 z-40 md:hidden">
+                    <div class="absolute inset-0 bg-slate-900/50" onclick={props.onclose.reform(|_: MouseEvent| ())}></div>
+                    <aside class={classes!(panel, "relative", "w-64", "h-full", "shadow-md", "space-y-6")}>
+                        { for Section::ALL.into_iter().filter(|section| section.is_visible(&auth)).map(|section| html! {
+                            <div class="flex flex-col space-y-1">
+                                <NavLink
+                                    to={section.route()}
+                                    active={current == Some(section)}
+                                    classes="px-3 py-2 font-medium"
+                                    active_classes="text-sky-700 dark:text-sky-300"
+                                >
+                                    {section.label(&i18n)}
+                                </NavLink>
+                                if current == Some(section) {
+                                    {links(section)}
+                                }
+                            </div>
+                        }) }
+                    </aside>
+                </div>
+            }
+        </>
+    }
+}
@@ -0,0 +1,29 @@
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+use crate::theme::{Theme, use_theme};
+
+// Segmented control choosing light, dark or system theme
+#[function_component(ThemeToggle)]
+pub fn theme_toggle() -> Html {
+    let context = use_theme();
+    let i18n = use_i18n();
+
+    html! {
+        <div role="group" aria-label={i18n.t("theme")} class="flex rounded-md border border-slate-300 dark:border-slate-600 text-sm overflow-hidden">
+            { for Theme::ALL.into_iter().map(|theme| {
+                let active = context.theme == theme;
+                let onclick = context.set_theme.reform(move |_: MouseEvent| theme);
+                let classes = match active {
+                    true => "bg-sky-600 text-white",
+                    false => "hover:bg-slate-100 dark:hover:bg-slate-700",
+                };
+                html! {
+                    <button type="button" aria-pressed={active.to_string()} class={classes!("px-3", "py-1", classes)} {onclick}>
+                        {theme.label(&i18n)}
+                    </button>
+                }
+            }) }
+        </div>
+    }
+}
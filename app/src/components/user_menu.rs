@@ -0,0 +1,83 @@
+use rustenv_client::ApiClient;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::AppRoute;
+use crate::auth::use_auth;
+use crate::i18n::use_i18n;
+use crate::toast::use_toast;
+
+// Logged in user's menu with account links and logging out, or login links for guests
+#[function_component(UserMenu)]
+pub fn user_menu() -> Html {
+    let auth = use_auth();
+    let i18n = use_i18n();
+    let client = use_context::<ApiClient>().expect("API client context");
+    let toaster = use_toast();
+    let open = use_state(|| false);
+
+    let link = "text-sky-600 dark:text-sky-400 hover:underline";
+    let Some(user) = &auth.user else {
+        return match auth.restoring {
+            true => html! {},
+            false => html! {
+                <div class="flex space-x-4 text-sm">
+                    <Link<AppRoute> to={AppRoute::Login} classes={link}>{i18n.t("nav-login")}</Link<AppRoute>>
+                    <Link<AppRoute> to={AppRoute::Register} classes={link}>{i18n.t("nav-register")}</Link<AppRoute>>
+                </div>
+            },
+        };
+    };
+
+    let toggle = {
+        let open = open.clone();
+        Callback::from(move |_: MouseEvent| open.set(!*open))
+    };
+    let close = {
+        let open = open.clone();
+        Callback::from(move |_: MouseEvent| open.set(false))
+    };
+    let onlogout = {
+        let message = i18n.t("toast-logged-out");
+        Callback::from(move |_: MouseEvent| {
+            let (client, toaster, message) = (client.clone(), toaster.clone(), message.clone());
+            spawn_local(async move {
+                client.logout().await;
+                toaster.info(message);
+            });
+        })
+    };
+
+    let name = user.display_name.clone().unwrap_or_else(|| user.username.clone());
+    let item = "block w-full text-left px-4 py-2 hover:bg-slate-100 dark:hover:bg-slate-700";
+
+    html! {
+        <div class="relative">
+            <button
+                type="button"
+                aria-haspopup="menu"
+                aria-expanded={open.to_string()}
+                onclick={toggle}
+                class="flex items-center space-x-2 rounded-md px-2 py-1 hover:bg-slate-100 dark:hover:bg-slate-800"
+            >
+                <span aria-hidden="true" class="flex justify-center items-center w-8 h-8 rounded-full bg-slate-200 dark:bg-slate-700">
+                    {name.chars().next().map(|initial| initial.to_uppercase().to_string()).unwrap_or_default()}
+                </span>
+                <span class="hidden sm:inline max-w-[10rem] truncate">{&name}</span>
+            </button>
+            if *open {
+                // Clicking anywhere outside closes the menu
+                <div class="fixed inset-0 z-30" onclick={close.clone()}></div>
+                <div role="menu" onclick={close} class="absolute right-0 z-40 mt-2 w-56 rounded-md border border-slate-200 dark:border-slate-700 bg-white dark:bg-slate-900 shadow-md py-1 text-sm">
+                    <p class="px-4 py-2 text-slate-500 dark:text-slate-400 truncate">{&user.email}</p>
+                    <Link<AppRoute> to={AppRoute::Settings} classes={item}>{i18n.t("nav-settings")}</Link<AppRoute>>
+                    if user.is_admin {
+                        <Link<AppRoute> to={AppRoute::Admin} classes={item}>{i18n.t("nav-admin")}</Link<AppRoute>>
+                    }
+                    <button type="button" role="menuitem" class={item} onclick={onlogout}>{i18n.t("nav-logout")}</button>
+                </div>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,66 @@
+use js_sys::{Function, JSON, Promise, Reflect};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::i18n::Locale;
+use crate::theme::Theme;
+
+// Preferences kept in the settings file of the desktop app, unset fields are left unchanged on update
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct DesktopSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<Theme>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<Locale>,
+}
+
+// Commands take named arguments, so even no arguments is an object
+#[derive(Serialize)]
+struct NoArgs {}
+
+#[derive(Serialize)]
+struct UpdateSettings<'a> {
+    settings: &'a DesktopSettings,
+}
+
+// Tauri command bridge, only present when running in the desktop app
+fn tauri_invoke() -> Option<Function> {
+    let window = web_sys::window()?;
+    let tauri = Reflect::get(&window, &"__TAURI__".into()).ok().filter(JsValue::is_object)?;
+    let core = Reflect::get(&tauri, &"core".into()).ok().filter(JsValue::is_object)?;
+    Reflect::get(&core, &"invoke".into()).ok()?.dyn_into::<Function>().ok()
+}
+
+pub fn is_desktop() -> bool {
+    tauri_invoke().is_some()
+}
+
+// Call Tauri command, passing arguments and result through JSON
+async fn invoke<A, R>(command: &str, args: &A) -> Option<R>
+where
+    A: Serialize,
+    R: DeserializeOwned,
+{
+    let invoke = tauri_invoke()?;
+    let args = JSON::parse(&serde_json::to_string(args).ok()?).ok()?;
+    let promise = invoke.call2(&JsValue::NULL, &command.into(), &args).ok()?;
+    match JsFuture::from(promise.dyn_into::<Promise>().ok()?).await {
+        Ok(value) => serde_json::from_str(&String::from(JSON::stringify(&value).ok()?)).ok(),
+        Err(error) => {
+            log::warn!("Desktop command {} failed: {:?}", command, error);
+            None
+        }
+    }
+}
+
+// Read desktop settings, None outside the desktop app
+pub async fn load_settings() -> Option<DesktopSettings> {
+    invoke("load_settings", &NoArgs {}).await
+}
+
+// Save fields set in settings to the desktop settings file
+pub async fn update_settings(settings: &DesktopSettings) {
+    let _: Option<DesktopSettings> = invoke("update_settings", &UpdateSettings { settings }).await;
+}
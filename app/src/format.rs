@@ -0,0 +1,18 @@
+use js_sys::{Date, Number};
+use wasm_bindgen::JsValue;
+
+// Local date and time of a Unix timestamp in seconds, formatted for language tag
+pub fn timestamp(locale: &str, seconds: i64) -> String {
+    let date = Date::new(&JsValue::from_f64(seconds as f64 * 1000.0));
+    date.to_locale_string(locale, &JsValue::UNDEFINED).into()
+}
+
+// Local date and time of an optional timestamp, or a dash when absent
+pub fn optional_timestamp(locale: &str, seconds: Option<i64>) -> String {
+    seconds.map(|seconds| timestamp(locale, seconds)).unwrap_or_else(|| "—".to_string())
+}
+
+// Number formatted for language tag
+pub fn number(locale: &str, value: f64) -> String {
+    Number::from(value).to_locale_string(locale).into()
+}
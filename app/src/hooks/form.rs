@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+
+use rustenv_client::ClientError;
+use rustenv_shared::validation::{FieldError, field_errors};
+use validator::Validate;
+use yew::prelude::*;
+
+use crate::api::error_message;
+use crate::i18n::{I18n, use_i18n};
+
+// Field errors keyed by field name
+pub type FieldErrors = BTreeMap<String, Vec<FieldError>>;
+
+// State of a form bound to a payload
+pub struct FormState<T> {
+    pub payload: UseStateHandle<T>,
+    pub errors: UseStateHandle<FieldErrors>,
+    pub error: UseStateHandle<Option<String>>,
+    pub loading: UseStateHandle<bool>,
+    i18n: I18n,
+}
+
+impl<T> Clone for FormState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            payload: self.payload.clone(),
+            errors: self.errors.clone(),
+            error: self.error.clone(),
+            loading: self.loading.clone(),
+            i18n: self.i18n.clone(),
+        }
+    }
+}
+
+impl<T> FormState<T>
+where
+    T: Clone + Validate + 'static,
+{
+    // Errors of a field
+    pub fn field_errors(&self, field: &str) -> Vec<FieldError> {
+        self.errors.get(field).cloned().unwrap_or_default()
+    }
+
+    // Callback updating a field with an input value, clearing the field's errors
+    pub fn oninput<F>(&self, field: &'static str, update: F) -> Callback<String>
+    where
+        F: Fn(&T, String) -> T + 'static,
+    {
+        let form = self.clone();
+        Callback::from(move |value: String| {
+            form.payload.set(update(&form.payload, value));
+            if form.errors.contains_key(field) {
+                let mut errors = (*form.errors).clone();
+                errors.remove(field);
+                form.errors.set(errors);
+            }
+        })
+    }
+
+    // Validate payload, showing any errors, and mark form as submitting if valid
+    pub fn submit(&self) -> Option<T> {
+        if *self.loading {
+            return None;
+        }
+        if let Err(errors) = self.payload.validate() {
+            self.errors.set(field_errors(&errors));
+            return None;
+        }
+        self.errors.set(FieldErrors::new());
+        self.error.set(None);
+        self.loading.set(true);
+        Some((*self.payload).clone())
+    }
+
+    // Mark submission as finished after it succeeded
+    pub fn done(&self) {
+        self.loading.set(false);
+    }
+
+    // Show failed submission, including field errors reported by the server
+    pub fn fail(&self, error: &ClientError) {
+        if let Some(problem) = error.problem() {
+            self.errors.set(problem.fields.clone());
+        }
+        self.error.set(Some(error_message(&self.i18n, error)));
+        self.loading.set(false);
+    }
+}
+
+// Form state starting from the payload's default
+#[hook]
+pub fn use_form<T>() -> FormState<T>
+where
+    T: Default + 'static,
+{
+    use_form_with(T::default)
+}
+
+// Form state starting from an initial payload, such as the values being edited
+#[hook]
+pub fn use_form_with<T, F>(init: F) -> FormState<T>
+where
+    T: 'static,
+    F: FnOnce() -> T,
+{
+    FormState {
+        payload: use_state(init),
+        errors: use_state(FieldErrors::new),
+        error: use_state(|| None),
+        loading: use_state(|| false),
+        i18n: use_i18n(),
+    }
+}
@@ -0,0 +1,205 @@
+use std::rc::Rc;
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::desktop::{self, DesktopSettings};
+use crate::format;
+use crate::storage::{self, LOCALE_KEY};
+
+// Language of the interface, with messages bundled from locales/<tag>/main.ftl
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::English, Locale::German];
+
+    // BCP 47 language tag
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::German => "de",
+        }
+    }
+
+    // Name of language in itself, as listed in the language switcher
+    pub fn name(self) -> &'static str {
+        match self {
+            Locale::English => "English",
+            Locale::German => "Deutsch",
+        }
+    }
+
+    fn messages(self) -> &'static str {
+        match self {
+            Locale::English => include_str!("../locales/en/main.ftl"),
+            Locale::German => include_str!("../locales/de/main.ftl"),
+        }
+    }
+
+    // Supported locale for a language tag such as "de-AT", matching on the language only
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.split(['-', '_']).next()?.to_lowercase();
+        Locale::ALL.into_iter().find(|locale| locale.tag() == language)
+    }
+
+    // First supported language the browser asks for
+    fn detect() -> Option<Locale> {
+        let navigator = web_sys::window()?.navigator();
+        navigator
+            .languages()
+            .iter()
+            .filter_map(|tag| tag.as_string())
+            .chain(navigator.language())
+            .find_map(|tag| Locale::from_tag(&tag))
+    }
+}
+
+fn bundle(locale: Locale) -> FluentBundle<FluentResource> {
+    let language: LanguageIdentifier = locale.tag().parse().expect("Invalid locale tag");
+    let mut bundle = FluentBundle::new(vec![language]);
+    // Isolation marks around arguments would end up in the page text
+    bundle.set_use_isolating(false);
+
+    // Locale files are checked by the build script, so they parse and hold no duplicates
+    let resource = FluentResource::try_new(locale.messages().to_string())
+        .unwrap_or_else(|(resource, _)| resource);
+    if let Err(errors) = bundle.add_resource(resource) {
+        log::error!("Error loading {} messages: {:?}", locale.tag(), errors);
+    }
+    bundle
+}
+
+// Translations of the current locale shared through the app
+#[derive(Clone)]
+pub struct I18n {
+    pub locale: Locale,
+    bundle: Rc<FluentBundle<FluentResource>>,
+    // Choose locale and persist it
+    pub set_locale: Callback<Locale>,
+}
+
+impl PartialEq for I18n {
+    fn eq(&self, other: &Self) -> bool {
+        self.locale == other.locale && self.set_locale == other.set_locale
+    }
+}
+
+impl I18n {
+    // Message by ID
+    pub fn t(&self, id: &str) -> String {
+        self.format(id, None)
+    }
+
+    // Message by ID with arguments, numbers among them selecting plural forms
+    pub fn t_args(&self, id: &str, args: &[(&str, FluentValue)]) -> String {
+        let args = args.iter().cloned().collect::<FluentArgs>();
+        self.format(id, Some(&args))
+    }
+
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        let Some(pattern) = self.bundle.get_message(id).and_then(|message| message.value()) else {
+            log::warn!("Missing {} message {}", self.locale.tag(), id);
+            return id.to_string();
+        };
+        let mut errors = vec![];
+        let value = self.bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            log::warn!("Error formatting {} message {}: {:?}", self.locale.tag(), id, errors);
+        }
+        value.into_owned()
+    }
+
+    // Local date and time of a Unix timestamp in seconds
+    pub fn timestamp(&self, seconds: i64) -> String {
+        format::timestamp(self.locale.tag(), seconds)
+    }
+
+    // Local date and time of an optional timestamp, or a dash when absent
+    pub fn optional_timestamp(&self, seconds: Option<i64>) -> String {
+        format::optional_timestamp(self.locale.tag(), seconds)
+    }
+
+    // Number with the locale's digit grouping and decimal separator
+    pub fn number(&self, value: f64) -> String {
+        format::number(self.locale.tag(), value)
+    }
+}
+
+// Translations from the surrounding LocaleProvider
+#[hook]
+pub fn use_i18n() -> I18n {
+    use_context::<I18n>().expect("LocaleProvider context")
+}
+
+// Set document language for screen readers and browser features such as translation
+fn apply(locale: Locale) {
+    if let Some(root) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.document_element())
+    {
+        let _ = root.set_attribute("lang", locale.tag());
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct LocaleProviderProps {
+    pub children: Html,
+}
+
+// Provide translations for the chosen locale, falling back to the browser's languages
+#[function_component(LocaleProvider)]
+pub fn locale_provider(props: &LocaleProviderProps) -> Html {
+    let locale = use_state(|| {
+        storage::load::<Locale>(LOCALE_KEY).or_else(Locale::detect).unwrap_or_default()
+    });
+    let bundle = use_memo(*locale, |locale| bundle(*locale));
+
+    // Desktop settings file wins over local storage and the browser's languages
+    use_effect_with((), {
+        let locale = locale.clone();
+        move |_| {
+            if desktop::is_desktop() {
+                spawn_local(async move {
+                    if let Some(saved) = desktop::load_settings().await.and_then(|s| s.locale) {
+                        storage::save(LOCALE_KEY, &saved);
+                        locale.set(saved);
+                    }
+                });
+            }
+        }
+    });
+
+    use_effect_with(*locale, |locale| apply(*locale));
+
+    let set_locale = {
+        let locale = locale.clone();
+        Callback::from(move |value: Locale| {
+            storage::save(LOCALE_KEY, &value);
+            locale.set(value);
+            spawn_local(async move {
+                desktop::update_settings(&DesktopSettings {
+                    locale: Some(value),
+                    ..Default::default()
+                })
+                .await;
+            });
+        })
+    };
+
+    let context = I18n { locale: *locale, bundle, set_locale };
+    html! {
+        <ContextProvider<I18n> {context}>
+            {props.children.clone()}
+        </ContextProvider<I18n>>
+    }
+}
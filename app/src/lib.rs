@@ -1,39 +1,147 @@
+mod api;
+mod auth;
+mod components;
+mod desktop;
+mod format;
+mod hooks;
+mod i18n;
+mod storage;
+mod theme;
+mod toast;
 mod views;
 
+use serde::{Deserialize, Serialize};
 use yew::prelude::*;
 use yew_router::prelude::*;
 
+use crate::auth::AuthProvider;
+use crate::components::protected_route::{Access, ProtectedRoute};
+use crate::components::shell::AppShell;
+use crate::i18n::LocaleProvider;
+use crate::theme::ThemeProvider;
+use crate::toast::ToastProvider;
+use crate::views::admin::audit::AdminAudit;
+use crate::views::admin::user::AdminUser;
+use crate::views::admin::users::AdminUsers;
 use crate::views::home::Home;
+use crate::views::login::Login;
 use crate::views::not_found::NotFound;
+use crate::views::register::Register;
+use crate::views::settings::account::SettingsAccount;
+use crate::views::settings::email::SettingsEmail;
+use crate::views::settings::password::SettingsPassword;
+use crate::views::settings::profile::SettingsProfile;
+use crate::views::settings::sessions::SettingsSessions;
+use crate::views::settings::two_factor::SettingsTwoFactor;
 
 // Application routes
 #[derive(Clone, PartialEq, Routable)]
 pub enum AppRoute {
     #[at("/")]
     Home,
+    #[at("/login")]
+    Login,
+    #[at("/register")]
+    Register,
+    #[at("/settings")]
+    Settings,
+    #[at("/settings/password")]
+    SettingsPassword,
+    #[at("/settings/email")]
+    SettingsEmail,
+    #[at("/settings/two-factor")]
+    SettingsTwoFactor,
+    #[at("/settings/sessions")]
+    SettingsSessions,
+    #[at("/settings/account")]
+    SettingsAccount,
+    #[at("/admin")]
+    Admin,
+    #[at("/admin/users/:uuid")]
+    AdminUser { uuid: String },
+    #[at("/admin/audit")]
+    AdminAudit,
     #[not_found]
     #[at("/404")]
     NotFound,
 }
 
+impl AppRoute {
+    // Who may see route
+    pub fn access(&self) -> Access {
+        match self {
+            AppRoute::Login | AppRoute::Register => Access::Guest,
+            AppRoute::Settings
+            | AppRoute::SettingsPassword
+            | AppRoute::SettingsEmail
+            | AppRoute::SettingsTwoFactor
+            | AppRoute::SettingsSessions
+            | AppRoute::SettingsAccount => Access::User,
+            AppRoute::Admin | AppRoute::AdminUser { .. } | AppRoute::AdminAudit => Access::Admin,
+            AppRoute::Home | AppRoute::NotFound => Access::Public,
+        }
+    }
+}
+
+// Query carrying the path to return to after logging in
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct RedirectQuery {
+    pub redirect: Option<String>,
+}
+
+impl RedirectQuery {
+    // Route to return to, ignoring anything that is not an in-app page
+    pub fn route(&self) -> AppRoute {
+        self.redirect
+            .as_deref()
+            .filter(|path| path.starts_with('/') && !path.starts_with("//"))
+            .and_then(AppRoute::recognize)
+            .filter(|route| {
+                !matches!(route, AppRoute::Login | AppRoute::Register | AppRoute::NotFound)
+            })
+            .unwrap_or(AppRoute::Home)
+    }
+}
+
 // Switch function
 pub fn switch(route: AppRoute) -> Html {
-    match route {
+    let access = route.access();
+    let view = match route {
         AppRoute::Home => html! { <Home /> },
+        AppRoute::Login => html! { <Login /> },
+        AppRoute::Register => html! { <Register /> },
+        AppRoute::Settings => html! { <SettingsProfile /> },
+        AppRoute::SettingsPassword => html! { <SettingsPassword /> },
+        AppRoute::SettingsEmail => html! { <SettingsEmail /> },
+        AppRoute::SettingsTwoFactor => html! { <SettingsTwoFactor /> },
+        AppRoute::SettingsSessions => html! { <SettingsSessions /> },
+        AppRoute::SettingsAccount => html! { <SettingsAccount /> },
+        AppRoute::Admin => html! { <AdminUsers /> },
+        AppRoute::AdminUser { uuid } => html! { <AdminUser {uuid} /> },
+        AppRoute::AdminAudit => html! { <AdminAudit /> },
         AppRoute::NotFound => html! { <NotFound /> },
-    }
+    };
+    html! { <ProtectedRoute {access}>{view}</ProtectedRoute> }
 }
 
 // Application component
 #[function_component(App)]
 pub fn app() -> Html {
     html! {
-        <BrowserRouter>
-            <body class="w-screen h-screen bg-slate-50 dark:bg-slate-800 overflow-hidden">
-                <main class="flex justify-center items-center w-full h-full">
-                    <Switch<AppRoute> render={switch} />
-                </main>
-            </body>
-        </BrowserRouter>
+        <ThemeProvider>
+            <LocaleProvider>
+                <ToastProvider>
+                    <AuthProvider>
+                        <BrowserRouter>
+                            <body class="w-screen h-screen bg-slate-50 dark:bg-slate-800 overflow-hidden">
+                                <AppShell>
+                                    <Switch<AppRoute> render={switch} />
+                                </AppShell>
+                            </body>
+                        </BrowserRouter>
+                    </AuthProvider>
+                </ToastProvider>
+            </LocaleProvider>
+        </ThemeProvider>
     }
 }
@@ -0,0 +1,52 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use web_sys::Storage;
+
+// Storage key of the API tokens
+pub const TOKENS_KEY: &str = "rustenv.tokens";
+
+// Storage key of the theme preference, also read by the script in index.html
+pub const THEME_KEY: &str = "rustenv.theme";
+
+// Storage key of the chosen locale
+pub const LOCALE_KEY: &str = "rustenv.locale";
+
+// Storage key of the logged in user
+pub const USER_KEY: &str = "rustenv.user";
+
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+// Read JSON value from local storage, ignoring missing or unreadable values
+pub fn load<T>(key: &str) -> Option<T>
+where
+    T: DeserializeOwned,
+{
+    parse(&local_storage()?.get_item(key).ok().flatten()?)
+}
+
+// Parse stored JSON value, such as the new value of a storage event
+pub fn parse<T>(value: &str) -> Option<T>
+where
+    T: DeserializeOwned,
+{
+    serde_json::from_str(value).ok()
+}
+
+// Write JSON value to local storage
+pub fn save<T>(key: &str, value: &T)
+where
+    T: Serialize,
+{
+    let (Some(storage), Ok(value)) = (local_storage(), serde_json::to_string(value)) else {
+        return;
+    };
+    let _ = storage.set_item(key, &value);
+}
+
+pub fn remove(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(key);
+    }
+}
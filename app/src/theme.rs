@@ -0,0 +1,128 @@
+use gloo_events::EventListener;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::desktop::{self, DesktopSettings};
+use crate::i18n::I18n;
+use crate::storage::{self, THEME_KEY};
+
+// Media query followed by the system theme
+const DARK_QUERY: &str = "(prefers-color-scheme: dark)";
+
+// Colour theme preference
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Light,
+    Dark,
+    #[default]
+    System,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::Light, Theme::Dark, Theme::System];
+
+    pub fn label(self, i18n: &I18n) -> String {
+        match self {
+            Theme::Light => i18n.t("theme-light"),
+            Theme::Dark => i18n.t("theme-dark"),
+            Theme::System => i18n.t("theme-system"),
+        }
+    }
+
+    // Whether dark styles apply, following the operating system in system mode
+    pub fn is_dark(self) -> bool {
+        match self {
+            Theme::Light => false,
+            Theme::Dark => true,
+            Theme::System => web_sys::window()
+                .and_then(|window| window.match_media(DARK_QUERY).ok().flatten())
+                .is_some_and(|query| query.matches()),
+        }
+    }
+}
+
+// Toggle the dark class Tailwind keys dark styles on, which index.html also sets before the app loads
+fn apply(theme: Theme) {
+    let Some(root) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.document_element())
+    else {
+        return;
+    };
+    let _ = root.class_list().toggle_with_force("dark", theme.is_dark());
+}
+
+// Theme preference shared through the app
+#[derive(Clone, PartialEq)]
+pub struct ThemeContext {
+    pub theme: Theme,
+    // Choose theme and persist it
+    pub set_theme: Callback<Theme>,
+}
+
+// Theme from the surrounding ThemeProvider
+#[hook]
+pub fn use_theme() -> ThemeContext {
+    use_context::<ThemeContext>().expect("ThemeProvider context")
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ThemeProviderProps {
+    pub children: Html,
+}
+
+// Provide theme preference, persisted to local storage and the desktop settings file
+#[function_component(ThemeProvider)]
+pub fn theme_provider(props: &ThemeProviderProps) -> Html {
+    let theme = use_state(|| storage::load::<Theme>(THEME_KEY).unwrap_or_default());
+
+    // Desktop settings file wins over local storage, which only serves to avoid a flash on load
+    use_effect_with((), {
+        let theme = theme.clone();
+        move |_| {
+            if desktop::is_desktop() {
+                spawn_local(async move {
+                    if let Some(saved) = desktop::load_settings().await.and_then(|s| s.theme) {
+                        storage::save(THEME_KEY, &saved);
+                        theme.set(saved);
+                    }
+                });
+            }
+        }
+    });
+
+    // Apply theme, and follow operating system changes in system mode
+    use_effect_with(*theme, |theme| {
+        let theme = *theme;
+        apply(theme);
+        let listener = (theme == Theme::System)
+            .then(|| web_sys::window()?.match_media(DARK_QUERY).ok().flatten())
+            .flatten()
+            .map(|query| EventListener::new(&query, "change", move |_| apply(theme)));
+        move || drop(listener)
+    });
+
+    let set_theme = {
+        let theme = theme.clone();
+        Callback::from(move |value: Theme| {
+            storage::save(THEME_KEY, &value);
+            theme.set(value);
+            spawn_local(async move {
+                desktop::update_settings(&DesktopSettings {
+                    theme: Some(value),
+                    ..Default::default()
+                })
+                .await;
+            });
+        })
+    };
+
+    let context = ThemeContext { theme: *theme, set_theme };
+    html! {
+        <ContextProvider<ThemeContext> {context}>
+            {props.children.clone()}
+        </ContextProvider<ThemeContext>>
+    }
+}
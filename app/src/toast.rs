@@ -0,0 +1,171 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use wasm_bindgen_futures::spawn_local;
+use yew::platform::time::sleep;
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+
+// Most toasts shown at once, older ones make way for new ones
+const MAX_TOASTS: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToastKind {
+    Success,
+    Info,
+    Error,
+}
+
+impl ToastKind {
+    // How long toast stays up, errors a little longer so they can be read
+    fn duration(self) -> Duration {
+        match self {
+            ToastKind::Success | ToastKind::Info => Duration::from_secs(4),
+            ToastKind::Error => Duration::from_secs(8),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toast {
+    pub id: u32,
+    pub kind: ToastKind,
+    pub message: AttrValue,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ToastState {
+    pub toasts: Vec<Toast>,
+    next_id: u32,
+}
+
+pub enum ToastAction {
+    Push(ToastKind, AttrValue),
+    Dismiss(u32),
+}
+
+impl Reducible for ToastState {
+    type Action = ToastAction;
+
+    fn reduce(self: Rc<Self>, action: ToastAction) -> Rc<Self> {
+        match action {
+            // Repeats of a toast still showing, such as from parallel failed requests, are dropped
+            ToastAction::Push(kind, message)
+                if self
+                    .toasts
+                    .iter()
+                    .any(|toast| toast.kind == kind && toast.message == message) =>
+            {
+                self
+            }
+            ToastAction::Push(kind, message) => {
+                let mut toasts = self.toasts.clone();
+                toasts.push(Toast { id: self.next_id, kind, message });
+                let overflow = toasts.len().saturating_sub(MAX_TOASTS);
+                toasts.drain(..overflow);
+                Rc::new(ToastState { toasts, next_id: self.next_id.wrapping_add(1) })
+            }
+            ToastAction::Dismiss(id) => {
+                let toasts = self.toasts.iter().filter(|toast| toast.id != id).cloned().collect();
+                Rc::new(ToastState { toasts, next_id: self.next_id })
+            }
+        }
+    }
+}
+
+// Handle for pushing toasts from anywhere below the ToastProvider
+#[derive(Clone, PartialEq)]
+pub struct Toaster(UseReducerDispatcher<ToastState>);
+
+impl Toaster {
+    pub fn push(&self, kind: ToastKind, message: impl Into<AttrValue>) {
+        self.0.dispatch(ToastAction::Push(kind, message.into()));
+    }
+
+    pub fn success(&self, message: impl Into<AttrValue>) {
+        self.push(ToastKind::Success, message);
+    }
+
+    pub fn info(&self, message: impl Into<AttrValue>) {
+        self.push(ToastKind::Info, message);
+    }
+
+    pub fn error(&self, message: impl Into<AttrValue>) {
+        self.push(ToastKind::Error, message);
+    }
+
+    pub fn dismiss(&self, id: u32) {
+        self.0.dispatch(ToastAction::Dismiss(id));
+    }
+}
+
+// Toast service from the surrounding ToastProvider
+#[hook]
+pub fn use_toast() -> Toaster {
+    use_context::<Toaster>().expect("ToastProvider context")
+}
+
+#[derive(Properties, PartialEq)]
+struct ToastItemProps {
+    toast: Toast,
+    toaster: Toaster,
+}
+
+// Single toast, dismissed when clicked or after its duration
+#[function_component(ToastItem)]
+fn toast_item(props: &ToastItemProps) -> Html {
+    let i18n = use_i18n();
+    let Toast { id, kind, message } = props.toast.clone();
+
+    use_effect_with(id, {
+        let toaster = props.toaster.clone();
+        move |id| {
+            let id = *id;
+            spawn_local(async move {
+                sleep(kind.duration()).await;
+                toaster.dismiss(id);
+            });
+        }
+    });
+
+    let (role, classes) = match kind {
+        ToastKind::Success => ("status", "border-emerald-500 bg-emerald-50 dark:bg-emerald-950"),
+        ToastKind::Info => ("status", "border-sky-500 bg-sky-50 dark:bg-sky-950"),
+        ToastKind::Error => ("alert", "border-red-500 bg-red-50 dark:bg-red-950"),
+    };
+    let onclick = {
+        let toaster = props.toaster.clone();
+        Callback::from(move |_: MouseEvent| toaster.dismiss(id))
+    };
+
+    html! {
+        <div {role} class={classes!("flex", "justify-between", "items-start", "space-x-4", "w-80", "rounded-md", "border-l-4", "shadow-md", "text-slate-800", "dark:text-slate-100", "px-4", "py-3", classes)}>
+            <p class="text-sm">{message}</p>
+            <button type="button" aria-label={i18n.t("toast-dismiss")} class="text-slate-500 hover:text-slate-800 dark:hover:text-slate-100" {onclick}>{"×"}</button>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ToastProviderProps {
+    pub children: Html,
+}
+
+// Provide toast service and show its toasts in the corner of the window
+#[function_component(ToastProvider)]
+pub fn toast_provider(props: &ToastProviderProps) -> Html {
+    let state = use_reducer(ToastState::default);
+    let toaster = Toaster(state.dispatcher());
+
+    html! {
+        <ContextProvider<Toaster> context={toaster.clone()}>
+            {props.children.clone()}
+            <div aria-live="polite" class="fixed bottom-4 right-4 z-50 flex flex-col items-end space-y-2">
+                { for state.toasts.iter().map(|toast| html! {
+                    <ToastItem key={toast.id} toast={toast.clone()} toaster={toaster.clone()} />
+                }) }
+            </div>
+        </ContextProvider<Toaster>>
+    }
+}
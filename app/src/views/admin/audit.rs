@@ -0,0 +1,190 @@
+use rustenv_client::ApiClient;
+use rustenv_shared::audit::{AuditEvent, AuditEventType, AuditOutcome, AuditQuery};
+use rustenv_shared::pagination::Page;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::api::error_message;
+use crate::components::form::FormAlert;
+use crate::components::pager::Pager;
+use crate::i18n::use_i18n;
+use crate::views::admin::AdminLayout;
+
+// Admin audit log view, filtered by the page's query such as ?target=<uuid>
+#[function_component(AdminAudit)]
+pub fn admin_audit() -> Html {
+    let client = use_context::<ApiClient>().expect("API client context");
+    let i18n = use_i18n();
+    let initial = use_location()
+        .and_then(|location| location.query::<AuditQuery>().ok())
+        .map(|query| AuditQuery { offset: Some(0), format: None, ..query })
+        .unwrap_or(AuditQuery { offset: Some(0), ..Default::default() });
+    let filters = use_state({
+        let initial = initial.clone();
+        move || initial
+    });
+    let query = use_state(move || initial);
+    let link = use_state(|| None::<String>);
+    let page = use_state(|| None::<Page<AuditEvent>>);
+    let error = use_state(|| None::<String>);
+    let loading = use_state(|| true);
+
+    // Fetch first page of query, or the page behind a followed link
+    use_effect_with(((*query).clone(), (*link).clone()), {
+        let (i18n, page, error, loading) =
+            (i18n.clone(), page.clone(), error.clone(), loading.clone());
+        move |(query, link): &(AuditQuery, Option<String>)| {
+            let (query, link) = (query.clone(), link.clone());
+            loading.set(true);
+            spawn_local(async move {
+                let result = match link {
+                    Some(link) => client.page(&link).await,
+                    None => client.audit_events(&query).await,
+                };
+                match result {
+                    Ok(result) => {
+                        page.set(Some(result));
+                        error.set(None);
+                    }
+                    Err(result) => error.set(Some(error_message(&i18n, &result))),
+                }
+                loading.set(false);
+            });
+        }
+    });
+
+    let onevent = {
+        let filters = filters.clone();
+        Callback::from(move |event: Event| {
+            let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+            let event_type =
+                AuditEventType::ALL.into_iter().find(|event_type| event_type.as_str() == value);
+            filters.set(AuditQuery { event_type, ..(*filters).clone() });
+        })
+    };
+    let onoutcome = {
+        let filters = filters.clone();
+        Callback::from(move |event: Event| {
+            let outcome = match event.target_unchecked_into::<HtmlSelectElement>().value().as_str()
+            {
+                "success" => Some(AuditOutcome::Success),
+                "failure" => Some(AuditOutcome::Failure),
+                _ => None,
+            };
+            filters.set(AuditQuery { outcome, ..(*filters).clone() });
+        })
+    };
+    let text_filter = |update: fn(AuditQuery, Option<String>) -> AuditQuery| {
+        let filters = filters.clone();
+        Callback::from(move |event: InputEvent| {
+            let value = event.target_unchecked_into::<HtmlInputElement>().value();
+            let value = Some(value.trim().to_string()).filter(|value| !value.is_empty());
+            filters.set(update((*filters).clone(), value));
+        })
+    };
+    let onfilter = {
+        let (filters, query, link) = (filters.clone(), query.clone(), link.clone());
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            query.set(AuditQuery { offset: Some(0), ..(*filters).clone() });
+            link.set(None);
+        })
+    };
+    let follow = |target: Option<String>| {
+        let link = link.clone();
+        Callback::from(move |_| {
+            if let Some(target) = target.clone() {
+                link.set(Some(target));
+            }
+        })
+    };
+
+    let input = "rounded-md border border-slate-300 dark:border-slate-600 px-3 py-2 bg-white dark:bg-slate-800";
+    let selected_event = filters.event_type.map(AuditEventType::as_str);
+    let events = page.as_ref().map(|page| page.items.clone()).unwrap_or_default();
+    html! {
+        <AdminLayout title={i18n.t("nav-admin-audit")}>
+            <form class="flex flex-wrap gap-2" onsubmit={onfilter}>
+                <select aria-label={i18n.t("audit-event-type")} class={input} onchange={onevent}>
+                    <option value="" selected={selected_event.is_none()}>{i18n.t("audit-all-events")}</option>
+                    { for AuditEventType::ALL.into_iter().map(|event_type| html! {
+                        <option value={event_type.as_str()} selected={selected_event == Some(event_type.as_str())}>
+                            {event_type.as_str()}
+                        </option>
+                    }) }
+                </select>
+                <select aria-label={i18n.t("audit-outcome")} class={input} onchange={onoutcome}>
+                    <option value="" selected={filters.outcome.is_none()}>{i18n.t("audit-all-outcomes")}</option>
+                    <option value="success" selected={filters.outcome == Some(AuditOutcome::Success)}>{i18n.t("audit-outcome-success")}</option>
+                    <option value="failure" selected={filters.outcome == Some(AuditOutcome::Failure)}>{i18n.t("audit-outcome-failure")}</option>
+                </select>
+                <input
+                    type="text"
+                    aria-label={i18n.t("audit-actor-uuid")}
+                    placeholder={i18n.t("audit-actor-uuid")}
+                    value={filters.actor_uuid.clone().unwrap_or_default()}
+                    oninput={text_filter(|query, actor_uuid| AuditQuery { actor_uuid, ..query })}
+                    class={input}
+                />
+                <input
+                    type="text"
+                    aria-label={i18n.t("audit-target")}
+                    placeholder={i18n.t("audit-target")}
+                    value={filters.target.clone().unwrap_or_default()}
+                    oninput={text_filter(|query, target| AuditQuery { target, ..query })}
+                    class={input}
+                />
+                <button type="submit" class="rounded-md bg-sky-600 hover:bg-sky-700 text-white font-medium px-4 py-2">
+                    {i18n.t("audit-filter")}
+                </button>
+            </form>
+            <FormAlert message={(*error).clone()} />
+            <table class="w-full text-left text-sm" aria-busy={loading.to_string()}>
+                <thead class="border-b border-slate-300 dark:border-slate-700">
+                    <tr>
+                        <th class="py-2">{i18n.t("audit-time")}</th>
+                        <th class="py-2">{i18n.t("audit-event")}</th>
+                        <th class="py-2">{i18n.t("audit-outcome")}</th>
+                        <th class="py-2">{i18n.t("audit-actor")}</th>
+                        <th class="py-2">{i18n.t("audit-target")}</th>
+                        <th class="py-2">{i18n.t("sessions-ip")}</th>
+                        <th class="py-2">{i18n.t("audit-detail")}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for events.into_iter().map(|event| html! {
+                        <tr key={event.id} class="border-b border-slate-200 dark:border-slate-800">
+                            <td class="py-2 whitespace-nowrap">{i18n.timestamp(event.created_at)}</td>
+                            <td class="py-2">{&event.event_type}</td>
+                            <td class="py-2">{match event.outcome.as_str() {
+                                "success" => i18n.t("audit-outcome-success"),
+                                "failure" => i18n.t("audit-outcome-failure"),
+                                outcome => outcome.to_string(),
+                            }}</td>
+                            <td class="py-2 font-mono text-xs break-all">{event.actor_uuid.clone().unwrap_or_default()}</td>
+                            <td class="py-2 font-mono text-xs break-all">{event.target.clone().unwrap_or_default()}</td>
+                            <td class="py-2">{event.ip.clone().unwrap_or_default()}</td>
+                            <td class="py-2">{event.detail.clone().unwrap_or_default()}</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+            if let Some(page) = &*page {
+                if page.items.is_empty() && !*loading {
+                    <p class="text-slate-500 dark:text-slate-400">{i18n.t("audit-empty")}</p>
+                }
+                <Pager
+                    offset={page.offset.unwrap_or_default()}
+                    limit={page.limit}
+                    total={page.total}
+                    has_prev={page.prev.is_some()}
+                    has_next={page.next.is_some()}
+                    onprev={follow(page.prev.clone())}
+                    onnext={follow(page.next.clone())}
+                />
+            }
+        </AdminLayout>
+    }
+}
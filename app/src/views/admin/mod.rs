@@ -0,0 +1,22 @@
+pub mod audit;
+pub mod user;
+pub mod users;
+
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct AdminLayoutProps {
+    pub title: AttrValue,
+    pub children: Html,
+}
+
+// Admin section frame with the title of the admin view, whose pages are listed in the sidebar
+#[function_component(AdminLayout)]
+pub fn admin_layout(props: &AdminLayoutProps) -> Html {
+    html! {
+        <div class="flex flex-col self-start w-full max-w-6xl space-y-6 text-slate-800 dark:text-slate-100 px-10">
+            <h1 class="text-3xl">{&props.title}</h1>
+            {props.children.clone()}
+        </div>
+    }
+}
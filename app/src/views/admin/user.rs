@@ -0,0 +1,213 @@
+use rustenv_client::ApiClient;
+use rustenv_shared::audit::AuditQuery;
+use rustenv_shared::user::UserInformation;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::AppRoute;
+use crate::api::error_message;
+use crate::auth::use_auth;
+use crate::components::confirm_dialog::ConfirmDialog;
+use crate::components::form::FormAlert;
+use crate::i18n::use_i18n;
+use crate::views::admin::AdminLayout;
+
+// Change made to the user
+#[derive(Clone, Copy)]
+enum Action {
+    SetAdmin(bool),
+    Disable,
+    Enable,
+    Delete,
+}
+
+// Action waiting for confirmation
+#[derive(Clone, Copy, PartialEq)]
+enum Pending {
+    Disable,
+    Delete,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AdminUserProps {
+    pub uuid: String,
+}
+
+// Admin user detail view
+#[function_component(AdminUser)]
+pub fn admin_user(props: &AdminUserProps) -> Html {
+    let client = use_context::<ApiClient>().expect("API client context");
+    let navigator = use_navigator().expect("AdminUser must be rendered inside a router");
+    let auth = use_auth();
+    let i18n = use_i18n();
+    let user = use_state(|| None::<UserInformation>);
+    let error = use_state(|| None::<String>);
+    let busy = use_state(|| false);
+    let pending = use_state(|| None::<Pending>);
+
+    use_effect_with(props.uuid.clone(), {
+        let (client, i18n, user, error) =
+            (client.clone(), i18n.clone(), user.clone(), error.clone());
+        move |uuid: &String| {
+            let uuid = uuid.clone();
+            spawn_local(async move {
+                match client.user(&uuid).await {
+                    Ok(result) => user.set(Some(result)),
+                    Err(result) => error.set(Some(error_message(&i18n, &result))),
+                }
+            });
+        }
+    });
+
+    // Run action against the user, showing the refreshed user or the error
+    let run = {
+        let handles = (
+            client.clone(),
+            navigator.clone(),
+            i18n.clone(),
+            user.clone(),
+            error.clone(),
+            busy.clone(),
+            pending.clone(),
+            props.uuid.clone(),
+        );
+        move |action: Action| {
+            let handles = handles.clone();
+            Callback::from(move |()| {
+                let (client, navigator, i18n, user, error, busy, pending, uuid) = handles.clone();
+                busy.set(true);
+                spawn_local(async move {
+                    let result = match action {
+                        Action::SetAdmin(is_admin) => client.set_user_role(&uuid, is_admin).await,
+                        Action::Disable => match client.disable_user(&uuid).await {
+                            Ok(()) => client.user(&uuid).await,
+                            Err(result) => Err(result),
+                        },
+                        Action::Enable => match client.enable_user(&uuid).await {
+                            Ok(()) => client.user(&uuid).await,
+                            Err(result) => Err(result),
+                        },
+                        Action::Delete => match client.delete_user(&uuid).await {
+                            Ok(()) => return navigator.push(&AppRoute::Admin),
+                            Err(result) => Err(result),
+                        },
+                    };
+                    match result {
+                        Ok(result) => {
+                            user.set(Some(result));
+                            error.set(None);
+                        }
+                        Err(result) => error.set(Some(error_message(&i18n, &result))),
+                    }
+                    busy.set(false);
+                    pending.set(None);
+                });
+            })
+        }
+    };
+    let confirm = |action: Option<Pending>| {
+        let pending = pending.clone();
+        Callback::from(move |()| pending.set(action))
+    };
+
+    let Some(user) = &*user else {
+        return html! {
+            <AdminLayout title={i18n.t("user-title")}>
+                <FormAlert message={(*error).clone()} />
+                if error.is_none() {
+                    <p aria-busy="true" class="text-slate-500 dark:text-slate-400">{i18n.t("loading")}</p>
+                }
+            </AdminLayout>
+        };
+    };
+
+    // Admins cannot lock themselves out
+    let is_self = auth.user.as_ref().is_some_and(|current| current.uuid == user.uuid);
+    let button = "rounded-md border border-slate-300 dark:border-slate-600 px-4 py-2 hover:bg-slate-100 dark:hover:bg-slate-800 disabled:opacity-50 disabled:cursor-not-allowed";
+    let danger = "rounded-md bg-red-600 hover:bg-red-700 text-white font-medium px-4 py-2 disabled:opacity-50 disabled:cursor-not-allowed";
+    let audit_query = AuditQuery { target: Some(user.uuid.clone()), ..Default::default() };
+    let field = |label: String, value: String| {
+        html! {
+            <>
+                <dt class="font-medium">{label}</dt>
+                <dd class="col-span-2 break-all">{value}</dd>
+            </>
+        }
+    };
+
+    html! {
+        <AdminLayout title={user.display_name.clone().unwrap_or_else(|| user.username.clone())}>
+            <FormAlert message={(*error).clone()} />
+            <dl class="grid grid-cols-3 gap-2 text-sm">
+                {field(i18n.t("user-uuid"), user.uuid.clone())}
+                {field(i18n.t("field-username"), user.username.clone())}
+                {field(i18n.t("field-email"), user.email.clone())}
+                {field(i18n.t("field-display-name"), user.display_name.clone().unwrap_or_else(|| "—".to_string()))}
+                {field(i18n.t("user-role"), if user.is_admin { i18n.t("user-role-admin") } else { i18n.t("user-role-user") })}
+                {field(i18n.t("user-status"), match user.disabled_at {
+                    Some(disabled_at) => i18n.t_args("user-disabled-since", &[("date", i18n.timestamp(disabled_at).into())]),
+                    None => i18n.t("user-status-active"),
+                })}
+                {field(i18n.t("field-locale"), user.locale.clone().unwrap_or_else(|| "—".to_string()))}
+                {field(i18n.t("field-timezone"), user.timezone.clone().unwrap_or_else(|| "—".to_string()))}
+                {field(i18n.t("user-created"), i18n.timestamp(user.created_at))}
+                {field(i18n.t("user-updated"), i18n.timestamp(user.updated_at))}
+                {field(i18n.t("user-last-login"), i18n.optional_timestamp(user.last_login_at))}
+            </dl>
+            <div class="flex flex-wrap gap-2">
+                if user.is_admin {
+                    <button type="button" class={button} disabled={*busy || is_self} onclick={run(Action::SetAdmin(false)).reform(|_| ())}>
+                        {i18n.t("user-revoke-admin")}
+                    </button>
+                } else {
+                    <button type="button" class={button} disabled={*busy} onclick={run(Action::SetAdmin(true)).reform(|_| ())}>
+                        {i18n.t("user-grant-admin")}
+                    </button>
+                }
+                if user.is_disabled() {
+                    <button type="button" class={button} disabled={*busy} onclick={run(Action::Enable).reform(|_| ())}>
+                        {i18n.t("user-enable")}
+                    </button>
+                } else {
+                    <button type="button" class={button} disabled={*busy || is_self} onclick={confirm(Some(Pending::Disable)).reform(|_| ())}>
+                        {i18n.t("user-disable")}
+                    </button>
+                }
+                <button type="button" class={danger} disabled={*busy || is_self} onclick={confirm(Some(Pending::Delete)).reform(|_| ())}>
+                    {i18n.t("account-delete")}
+                </button>
+                <Link<AppRoute, AuditQuery> to={AppRoute::AdminAudit} query={Some(audit_query)} classes={button}>
+                    {i18n.t("user-audit-events")}
+                </Link<AppRoute, AuditQuery>>
+            </div>
+            if is_self {
+                <p class="text-sm text-slate-500 dark:text-slate-400">
+                    {i18n.t("user-self-note")}
+                </p>
+            }
+            if let Some(action) = *pending {
+                <ConfirmDialog
+                    title={match action {
+                        Pending::Disable => i18n.t("user-disable-confirm-title"),
+                        Pending::Delete => i18n.t("account-delete-confirm-title"),
+                    }}
+                    message={match action {
+                        Pending::Disable => i18n.t_args("user-disable-confirm", &[("username", user.username.clone().into())]),
+                        Pending::Delete => i18n.t_args("user-delete-confirm", &[("username", user.username.clone().into())]),
+                    }}
+                    confirm_label={match action {
+                        Pending::Disable => i18n.t("user-disable-confirm-label"),
+                        Pending::Delete => i18n.t("user-delete-confirm-label"),
+                    }}
+                    busy={*busy}
+                    onconfirm={run(match action {
+                        Pending::Disable => Action::Disable,
+                        Pending::Delete => Action::Delete,
+                    })}
+                    oncancel={confirm(None)}
+                />
+            }
+        </AdminLayout>
+    }
+}
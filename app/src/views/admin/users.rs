@@ -0,0 +1,137 @@
+use rustenv_client::ApiClient;
+use rustenv_shared::pagination::Page;
+use rustenv_shared::user::{UserInformation, UserListQuery};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::AppRoute;
+use crate::api::error_message;
+use crate::components::form::FormAlert;
+use crate::components::pager::Pager;
+use crate::i18n::use_i18n;
+use crate::views::admin::AdminLayout;
+
+// Admin user table view
+#[function_component(AdminUsers)]
+pub fn admin_users() -> Html {
+    let client = use_context::<ApiClient>().expect("API client context");
+    let i18n = use_i18n();
+    let search = use_state(String::new);
+    let query = use_state(|| UserListQuery { offset: Some(0), ..Default::default() });
+    let link = use_state(|| None::<String>);
+    let page = use_state(|| None::<Page<UserInformation>>);
+    let error = use_state(|| None::<String>);
+    let loading = use_state(|| true);
+
+    // Fetch first page of query, or the page behind a followed link
+    use_effect_with(((*query).clone(), (*link).clone()), {
+        let (i18n, page, error, loading) =
+            (i18n.clone(), page.clone(), error.clone(), loading.clone());
+        move |(query, link): &(UserListQuery, Option<String>)| {
+            let (query, link) = (query.clone(), link.clone());
+            loading.set(true);
+            spawn_local(async move {
+                let result = match link {
+                    Some(link) => client.page(&link).await,
+                    None => client.list_users(&query).await,
+                };
+                match result {
+                    Ok(result) => {
+                        page.set(Some(result));
+                        error.set(None);
+                    }
+                    Err(result) => error.set(Some(error_message(&i18n, &result))),
+                }
+                loading.set(false);
+            });
+        }
+    });
+
+    let oninput = {
+        let search = search.clone();
+        Callback::from(move |event: InputEvent| {
+            search.set(event.target_unchecked_into::<HtmlInputElement>().value());
+        })
+    };
+    let onsearch = {
+        let (search, query, link) = (search.clone(), query.clone(), link.clone());
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            let search = Some(search.trim().to_string()).filter(|search| !search.is_empty());
+            query.set(UserListQuery { search, offset: Some(0), ..Default::default() });
+            link.set(None);
+        })
+    };
+    let follow = |target: Option<String>| {
+        let link = link.clone();
+        Callback::from(move |_| {
+            if let Some(target) = target.clone() {
+                link.set(Some(target));
+            }
+        })
+    };
+
+    let rows = page.as_ref().map(|page| page.items.clone()).unwrap_or_default();
+    html! {
+        <AdminLayout title={i18n.t("nav-admin-users")}>
+            <form role="search" class="flex space-x-2" onsubmit={onsearch}>
+                <input
+                    type="search"
+                    aria-label={i18n.t("users-search-label")}
+                    placeholder={i18n.t("users-search-placeholder")}
+                    value={(*search).clone()}
+                    {oninput}
+                    class="flex-grow rounded-md border border-slate-300 dark:border-slate-600 px-3 py-2 bg-white dark:bg-slate-800"
+                />
+                <button type="submit" class="rounded-md bg-sky-600 hover:bg-sky-700 text-white font-medium px-4 py-2">
+                    {i18n.t("users-search")}
+                </button>
+            </form>
+            <FormAlert message={(*error).clone()} />
+            <table class="w-full text-left text-sm" aria-busy={loading.to_string()}>
+                <thead class="border-b border-slate-300 dark:border-slate-700">
+                    <tr>
+                        <th class="py-2">{i18n.t("field-username")}</th>
+                        <th class="py-2">{i18n.t("field-email")}</th>
+                        <th class="py-2">{i18n.t("user-role")}</th>
+                        <th class="py-2">{i18n.t("user-status")}</th>
+                        <th class="py-2">{i18n.t("user-created")}</th>
+                        <th class="py-2">{i18n.t("user-last-login")}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for rows.into_iter().map(|user| html! {
+                        <tr key={user.uuid.clone()} class="border-b border-slate-200 dark:border-slate-800">
+                            <td class="py-2">
+                                <Link<AppRoute> to={AppRoute::AdminUser { uuid: user.uuid.clone() }} classes="text-sky-600 dark:text-sky-400 hover:underline">
+                                    {&user.username}
+                                </Link<AppRoute>>
+                            </td>
+                            <td class="py-2">{&user.email}</td>
+                            <td class="py-2">{if user.is_admin { i18n.t("user-role-admin") } else { i18n.t("user-role-user") }}</td>
+                            <td class="py-2">{if user.is_disabled() { i18n.t("user-status-disabled") } else { i18n.t("user-status-active") }}</td>
+                            <td class="py-2">{i18n.timestamp(user.created_at)}</td>
+                            <td class="py-2">{i18n.optional_timestamp(user.last_login_at)}</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+            if let Some(page) = &*page {
+                if page.items.is_empty() && !*loading {
+                    <p class="text-slate-500 dark:text-slate-400">{i18n.t("users-empty")}</p>
+                }
+                <Pager
+                    offset={page.offset.unwrap_or_default()}
+                    limit={page.limit}
+                    total={page.total}
+                    has_prev={page.prev.is_some()}
+                    has_next={page.next.is_some()}
+                    onprev={follow(page.prev.clone())}
+                    onnext={follow(page.next.clone())}
+                />
+            }
+        </AdminLayout>
+    }
+}
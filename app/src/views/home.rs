@@ -1,14 +1,25 @@
 use yew::prelude::*;
 
+use crate::auth::use_auth;
+use crate::i18n::use_i18n;
+
 // Home view
 #[function_component(Home)]
 pub fn home() -> Html {
+    let auth = use_auth();
+    let i18n = use_i18n();
+
     html! {
         <div class="flex flex-col md:flex-row justify-center items-center rounded-md space-y-10 border-slate-300 dark:border-slate-700 border-bg-slate-100 text-slate-800 dark:text-slate-100 shadow-md mx-10 p-10 dark:bg-slate-900">
             <div class="flex flex-col justify-center text-center space-y-2">
                 <h1 class="text-4xl">{"rustenv"}</h1>
-                <p>{"Rust application development environment"}</p>
-                <p>{"Made by Spectrum Studios"}</p>
+                <p>{i18n.t("home-tagline")}</p>
+                <p>{i18n.t("home-made-by")}</p>
+                if let Some(user) = &auth.user {
+                    <p class="text-sm pt-4">
+                        {i18n.t_args("home-logged-in-as", &[("name", user.display_name.as_ref().unwrap_or(&user.username).as_str().into())])}
+                    </p>
+                }
             </div>
         </div>
     }
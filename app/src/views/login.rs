@@ -0,0 +1,100 @@
+use rustenv_client::ApiClient;
+use rustenv_shared::auth::AuthErrorType;
+use rustenv_shared::user::UserLogin;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::auth::{AuthAction, use_auth};
+use crate::components::form::{FormAlert, SubmitButton, TextField};
+use crate::hooks::form::use_form;
+use crate::i18n::use_i18n;
+use crate::{AppRoute, RedirectQuery};
+
+// Login view
+#[function_component(Login)]
+pub fn login() -> Html {
+    let client = use_context::<ApiClient>().expect("API client context");
+    let auth = use_auth();
+    let i18n = use_i18n();
+    let redirect = use_location()
+        .and_then(|location| location.query::<RedirectQuery>().ok())
+        .unwrap_or_default();
+    let form = use_form::<UserLogin>();
+    let two_factor = use_state(|| false);
+
+    let onsubmit = {
+        let (form, two_factor) = (form.clone(), two_factor.clone());
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            let Some(payload) = form.submit() else {
+                return;
+            };
+
+            // Log in, after which the guest-only route returns to the page that asked for it
+            let (client, auth, form, two_factor) =
+                (client.clone(), auth.clone(), form.clone(), two_factor.clone());
+            spawn_local(async move {
+                match client.login(&payload).await {
+                    Ok(user) => auth.dispatch(AuthAction::LoggedIn(user)),
+                    Err(error) => {
+                        // Ask for an authenticator code once the password is known to be right
+                        if matches!(
+                            error.error_type(),
+                            AuthErrorType::TwoFactorRequired | AuthErrorType::InvalidTwoFactorCode
+                        ) {
+                            two_factor.set(true);
+                        }
+                        form.fail(&error);
+                    }
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="flex flex-col w-full max-w-sm rounded-md space-y-6 border border-slate-300 dark:border-slate-700 text-slate-800 dark:text-slate-100 shadow-md mx-10 p-10 bg-white dark:bg-slate-900">
+            <h1 class="text-3xl text-center">{i18n.t("login-title")}</h1>
+            <form class="flex flex-col space-y-4" novalidate=true {onsubmit}>
+                <FormAlert message={(*form.error).clone()} />
+                <TextField
+                    label={i18n.t("field-login")}
+                    name="username"
+                    autocomplete="username"
+                    value={form.payload.username.clone()}
+                    errors={form.field_errors("username")}
+                    disabled={*form.loading}
+                    oninput={form.oninput("username", |payload: &UserLogin, value| UserLogin { username: value, ..payload.clone() })}
+                />
+                <TextField
+                    label={i18n.t("field-password")}
+                    name="password"
+                    input_type="password"
+                    autocomplete="current-password"
+                    value={form.payload.password.clone()}
+                    errors={form.field_errors("password")}
+                    disabled={*form.loading}
+                    oninput={form.oninput("password", |payload: &UserLogin, value| UserLogin { password: value, ..payload.clone() })}
+                />
+                if *two_factor {
+                    <TextField
+                        label={i18n.t("field-two-factor-code")}
+                        name="two_factor_code"
+                        autocomplete="one-time-code"
+                        value={form.payload.two_factor_code.clone().unwrap_or_default()}
+                        errors={form.field_errors("two_factor_code")}
+                        disabled={*form.loading}
+                        oninput={form.oninput("two_factor_code", |payload: &UserLogin, value: String| UserLogin { two_factor_code: Some(value.trim().to_string()).filter(|code| !code.is_empty()), ..payload.clone() })}
+                    />
+                }
+                <SubmitButton label={i18n.t("login-submit")} loading_label={i18n.t("login-submitting")} loading={*form.loading} />
+            </form>
+            <p class="text-sm text-center">
+                {i18n.t("login-no-account")}{" "}
+                <Link<AppRoute, RedirectQuery> to={AppRoute::Register} query={redirect.redirect.is_some().then_some(redirect)} classes="text-sky-600 dark:text-sky-400 hover:underline">
+                    {i18n.t("nav-register")}
+                </Link<AppRoute, RedirectQuery>>
+            </p>
+        </div>
+    }
+}
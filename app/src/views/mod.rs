@@ -1,2 +1,7 @@
+pub mod admin;
+pub mod forbidden;
 pub mod home;
+pub mod login;
 pub mod not_found;
+pub mod register;
+pub mod settings;
@@ -1,12 +1,16 @@
 use yew::prelude::*;
 
+use crate::i18n::use_i18n;
+
 // Not found view
 #[function_component(NotFound)]
 pub fn not_found() -> Html {
+    let i18n = use_i18n();
+
     html! {
         <div class="flex flex-col justify-center items-center space-y-4">
-            <p class="text-8xl text-slate-200 pb-6">{"404"}</p>
-            <p class="text-2xl text-slate-200">{"Page not found :("}</p>
+            <p class="text-8xl text-slate-400 dark:text-slate-200 pb-6">{"404"}</p>
+            <p class="text-2xl text-slate-500 dark:text-slate-200">{i18n.t("not-found")}</p>
         </div>
     }
 }
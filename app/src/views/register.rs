@@ -0,0 +1,87 @@
+use rustenv_client::ApiClient;
+use rustenv_shared::user::UserRegister;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::auth::{AuthAction, use_auth};
+use crate::components::form::{FormAlert, SubmitButton, TextField};
+use crate::hooks::form::use_form;
+use crate::i18n::use_i18n;
+use crate::{AppRoute, RedirectQuery};
+
+// Register view
+#[function_component(Register)]
+pub fn register() -> Html {
+    let client = use_context::<ApiClient>().expect("API client context");
+    let auth = use_auth();
+    let i18n = use_i18n();
+    let redirect = use_location()
+        .and_then(|location| location.query::<RedirectQuery>().ok())
+        .unwrap_or_default();
+    let form = use_form::<UserRegister>();
+
+    let onsubmit = {
+        let form = form.clone();
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            let Some(payload) = form.submit() else {
+                return;
+            };
+
+            // Register, which also logs in, after which the guest-only route returns to the page that asked for it
+            let (client, auth, form) = (client.clone(), auth.clone(), form.clone());
+            spawn_local(async move {
+                match client.register(&payload).await {
+                    Ok(user) => auth.dispatch(AuthAction::LoggedIn(user)),
+                    Err(error) => form.fail(&error),
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="flex flex-col w-full max-w-sm rounded-md space-y-6 border border-slate-300 dark:border-slate-700 text-slate-800 dark:text-slate-100 shadow-md mx-10 p-10 bg-white dark:bg-slate-900">
+            <h1 class="text-3xl text-center">{i18n.t("register-title")}</h1>
+            <form class="flex flex-col space-y-4" novalidate=true {onsubmit}>
+                <FormAlert message={(*form.error).clone()} />
+                <TextField
+                    label={i18n.t("field-username")}
+                    name="username"
+                    autocomplete="username"
+                    value={form.payload.username.clone()}
+                    errors={form.field_errors("username")}
+                    disabled={*form.loading}
+                    oninput={form.oninput("username", |payload: &UserRegister, value| UserRegister { username: value, ..payload.clone() })}
+                />
+                <TextField
+                    label={i18n.t("field-email")}
+                    name="email"
+                    input_type="email"
+                    autocomplete="email"
+                    value={form.payload.email.clone()}
+                    errors={form.field_errors("email")}
+                    disabled={*form.loading}
+                    oninput={form.oninput("email", |payload: &UserRegister, value| UserRegister { email: value, ..payload.clone() })}
+                />
+                <TextField
+                    label={i18n.t("field-password")}
+                    name="password"
+                    input_type="password"
+                    autocomplete="new-password"
+                    value={form.payload.password.clone()}
+                    errors={form.field_errors("password")}
+                    disabled={*form.loading}
+                    oninput={form.oninput("password", |payload: &UserRegister, value| UserRegister { password: value, ..payload.clone() })}
+                />
+                <SubmitButton label={i18n.t("register-submit")} loading_label={i18n.t("register-submitting")} loading={*form.loading} />
+            </form>
+            <p class="text-sm text-center">
+                {i18n.t("register-have-account")}{" "}
+                <Link<AppRoute, RedirectQuery> to={AppRoute::Login} query={redirect.redirect.is_some().then_some(redirect)} classes="text-sky-600 dark:text-sky-400 hover:underline">
+                    {i18n.t("nav-login")}
+                </Link<AppRoute, RedirectQuery>>
+            </p>
+        </div>
+    }
+}
@@ -0,0 +1,85 @@
+use rustenv_client::ApiClient;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::AppRoute;
+use crate::api::error_message;
+use crate::components::confirm_dialog::ConfirmDialog;
+use crate::components::form::FormAlert;
+use crate::i18n::use_i18n;
+use crate::toast::use_toast;
+use crate::views::settings::SettingsLayout;
+
+// Account deletion view
+#[function_component(SettingsAccount)]
+pub fn settings_account() -> Html {
+    let client = use_context::<ApiClient>().expect("API client context");
+    let navigator = use_navigator().expect("SettingsAccount must be rendered inside a router");
+    let toaster = use_toast();
+    let i18n = use_i18n();
+    let error = use_state(|| None::<String>);
+    let busy = use_state(|| false);
+    let confirming = use_state(|| false);
+
+    let confirm = |open: bool| {
+        let confirming = confirming.clone();
+        Callback::from(move |()| confirming.set(open))
+    };
+
+    // Deleting clears the session, so leave for the home page before the login redirect kicks in
+    let ondelete = {
+        let (i18n, error, busy, confirming) =
+            (i18n.clone(), error.clone(), busy.clone(), confirming.clone());
+        Callback::from(move |()| {
+            let (client, navigator, toaster, i18n, error, busy, confirming) = (
+                client.clone(),
+                navigator.clone(),
+                toaster.clone(),
+                i18n.clone(),
+                error.clone(),
+                busy.clone(),
+                confirming.clone(),
+            );
+            busy.set(true);
+            spawn_local(async move {
+                match client.delete_me().await {
+                    Ok(()) => {
+                        toaster.success(i18n.t("account-deleted"));
+                        navigator.push(&AppRoute::Home);
+                    }
+                    Err(result) => {
+                        error.set(Some(error_message(&i18n, &result)));
+                        busy.set(false);
+                        confirming.set(false);
+                    }
+                }
+            });
+        })
+    };
+
+    html! {
+        <SettingsLayout title={i18n.t("account-delete-title")}>
+            <FormAlert message={(*error).clone()} />
+            <p>{i18n.t("account-delete-intro")}</p>
+            <button
+                type="button"
+                disabled={*busy}
+                onclick={confirm(true).reform(|_| ())}
+                class="w-fit rounded-md bg-red-600 hover:bg-red-700 text-white font-medium px-4 py-2 disabled:opacity-50 disabled:cursor-not-allowed"
+            >
+                {i18n.t("account-delete")}
+            </button>
+            if *confirming {
+                <ConfirmDialog
+                    title={i18n.t("account-delete-confirm-title")}
+                    message={i18n.t("account-delete-confirm")}
+                    confirm_label={i18n.t("account-delete")}
+                    busy={*busy}
+                    onconfirm={ondelete}
+                    oncancel={confirm(false)}
+                />
+            }
+        </SettingsLayout>
+    }
+}
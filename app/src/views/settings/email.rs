@@ -0,0 +1,79 @@
+use rustenv_client::ApiClient;
+use rustenv_shared::user::UserEmailChange;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::auth::{AuthAction, use_auth};
+use crate::components::form::{FormAlert, FormSuccess, SubmitButton, TextField};
+use crate::hooks::form::use_form_with;
+use crate::i18n::use_i18n;
+use crate::views::settings::SettingsLayout;
+
+// Email change view
+#[function_component(SettingsEmail)]
+pub fn settings_email() -> Html {
+    let client = use_context::<ApiClient>().expect("API client context");
+    let auth = use_auth();
+    let i18n = use_i18n();
+    let email = auth.user.as_ref().map(|user| user.email.clone()).unwrap_or_default();
+    let form = use_form_with(|| UserEmailChange { email, ..Default::default() });
+    let saved = use_state(|| false);
+
+    let onsubmit = {
+        let (form, saved) = (form.clone(), saved.clone());
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            saved.set(false);
+            let Some(payload) = form.submit() else {
+                return;
+            };
+
+            let (client, auth, form, saved) =
+                (client.clone(), auth.clone(), form.clone(), saved.clone());
+            spawn_local(async move {
+                match client.change_email(&payload).await {
+                    Ok(user) => {
+                        form.payload.set(UserEmailChange {
+                            email: user.email.clone(),
+                            ..Default::default()
+                        });
+                        auth.dispatch(AuthAction::UserUpdated(user));
+                        form.done();
+                        saved.set(true);
+                    }
+                    Err(error) => form.fail(&error),
+                }
+            });
+        })
+    };
+
+    html! {
+        <SettingsLayout title={i18n.t("nav-settings-email")}>
+            <form class="flex flex-col space-y-4" novalidate=true {onsubmit}>
+                <FormAlert message={(*form.error).clone()} />
+                <FormSuccess message={saved.then(|| AttrValue::from(i18n.t("email-changed")))} />
+                <TextField
+                    label={i18n.t("field-email")}
+                    name="email"
+                    input_type="email"
+                    autocomplete="email"
+                    value={form.payload.email.clone()}
+                    errors={form.field_errors("email")}
+                    disabled={*form.loading}
+                    oninput={form.oninput("email", |payload: &UserEmailChange, value| UserEmailChange { email: value, ..payload.clone() })}
+                />
+                <TextField
+                    label={i18n.t("field-password")}
+                    name="password"
+                    input_type="password"
+                    autocomplete="current-password"
+                    value={form.payload.password.clone()}
+                    errors={form.field_errors("password")}
+                    disabled={*form.loading}
+                    oninput={form.oninput("password", |payload: &UserEmailChange, value| UserEmailChange { password: value, ..payload.clone() })}
+                />
+                <SubmitButton label={i18n.t("email-submit")} loading_label={i18n.t("saving")} loading={*form.loading} />
+            </form>
+        </SettingsLayout>
+    }
+}
@@ -0,0 +1,32 @@
+pub mod account;
+pub mod email;
+pub mod password;
+pub mod profile;
+pub mod sessions;
+pub mod two_factor;
+
+use yew::prelude::*;
+
+use crate::i18n::use_i18n;
+
+#[derive(Properties, PartialEq)]
+pub struct SettingsLayoutProps {
+    pub title: AttrValue,
+    pub children: Html,
+}
+
+// Settings frame with the title of the settings view, whose pages are listed in the sidebar
+#[function_component(SettingsLayout)]
+pub fn settings_layout(props: &SettingsLayoutProps) -> Html {
+    let i18n = use_i18n();
+
+    html! {
+        <div class="flex flex-col self-start w-full max-w-3xl space-y-6 text-slate-800 dark:text-slate-100 px-10">
+            <h1 class="text-3xl">{i18n.t("nav-settings")}</h1>
+            <section class="flex flex-col space-y-4">
+                <h2 class="text-xl">{&props.title}</h2>
+                {props.children.clone()}
+            </section>
+        </div>
+    }
+}
@@ -0,0 +1,71 @@
+use rustenv_client::ApiClient;
+use rustenv_shared::user::UserPasswordChange;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::components::form::{FormAlert, FormSuccess, SubmitButton, TextField};
+use crate::hooks::form::use_form;
+use crate::i18n::use_i18n;
+use crate::views::settings::SettingsLayout;
+
+// Password change view
+#[function_component(SettingsPassword)]
+pub fn settings_password() -> Html {
+    let client = use_context::<ApiClient>().expect("API client context");
+    let i18n = use_i18n();
+    let form = use_form::<UserPasswordChange>();
+    let saved = use_state(|| false);
+
+    let onsubmit = {
+        let (form, saved) = (form.clone(), saved.clone());
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            saved.set(false);
+            let Some(payload) = form.submit() else {
+                return;
+            };
+
+            let (client, form, saved) = (client.clone(), form.clone(), saved.clone());
+            spawn_local(async move {
+                match client.change_password(&payload).await {
+                    Ok(()) => {
+                        form.payload.set(UserPasswordChange::default());
+                        form.done();
+                        saved.set(true);
+                    }
+                    Err(error) => form.fail(&error),
+                }
+            });
+        })
+    };
+
+    html! {
+        <SettingsLayout title={i18n.t("nav-settings-password")}>
+            <form class="flex flex-col space-y-4" novalidate=true {onsubmit}>
+                <FormAlert message={(*form.error).clone()} />
+                <FormSuccess message={saved.then(|| AttrValue::from(i18n.t("password-changed")))} />
+                <TextField
+                    label={i18n.t("field-current-password")}
+                    name="current_password"
+                    input_type="password"
+                    autocomplete="current-password"
+                    value={form.payload.current_password.clone()}
+                    errors={form.field_errors("current_password")}
+                    disabled={*form.loading}
+                    oninput={form.oninput("current_password", |payload: &UserPasswordChange, value| UserPasswordChange { current_password: value, ..payload.clone() })}
+                />
+                <TextField
+                    label={i18n.t("field-new-password")}
+                    name="new_password"
+                    input_type="password"
+                    autocomplete="new-password"
+                    value={form.payload.new_password.clone()}
+                    errors={form.field_errors("new_password")}
+                    disabled={*form.loading}
+                    oninput={form.oninput("new_password", |payload: &UserPasswordChange, value| UserPasswordChange { new_password: value, ..payload.clone() })}
+                />
+                <SubmitButton label={i18n.t("password-submit")} loading_label={i18n.t("saving")} loading={*form.loading} />
+            </form>
+        </SettingsLayout>
+    }
+}
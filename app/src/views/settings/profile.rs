@@ -0,0 +1,211 @@
+use js_sys::Uint8Array;
+use rustenv_client::ApiClient;
+use rustenv_shared::media::AVATAR_CONTENT_TYPES;
+use rustenv_shared::user::UserProfileUpdate;
+use wasm_bindgen_futures::{JsFuture, spawn_local};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::api::error_message;
+use crate::auth::{AuthAction, use_auth};
+use crate::components::form::{FormAlert, FormSuccess, SubmitButton, TextField};
+use crate::hooks::form::use_form_with;
+use crate::i18n::use_i18n;
+use crate::views::settings::SettingsLayout;
+
+// Avatar thumbnail size shown in settings
+const AVATAR_SIZE: u32 = 128;
+
+// Profile settings view with display name, locale, timezone and avatar
+#[function_component(SettingsProfile)]
+pub fn settings_profile() -> Html {
+    let client = use_context::<ApiClient>().expect("API client context");
+    let auth = use_auth();
+    let i18n = use_i18n();
+    let user = auth.user.clone().unwrap_or_default();
+    let form = use_form_with(|| UserProfileUpdate {
+        display_name: Some(user.display_name.clone()),
+        locale: Some(user.locale.clone()),
+        timezone: Some(user.timezone.clone()),
+        ..Default::default()
+    });
+    let saved = use_state(|| false);
+    let avatar = use_state(|| None::<String>);
+    let avatar_error = use_state(|| None::<String>);
+    let avatar_busy = use_state(|| false);
+
+    // Show the uploaded avatar, if any
+    use_effect_with((), {
+        let (client, avatar) = (client.clone(), avatar.clone());
+        move |_| {
+            spawn_local(async move {
+                if let Ok(urls) = client.my_avatar().await {
+                    avatar.set(urls.urls.get(&AVATAR_SIZE).map(|url| client.url(url)));
+                }
+            });
+        }
+    });
+
+    let onsubmit = {
+        let (client, auth, form, saved) =
+            (client.clone(), auth.clone(), form.clone(), saved.clone());
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            saved.set(false);
+            let Some(payload) = form.submit() else {
+                return;
+            };
+
+            let (client, auth, form, saved) =
+                (client.clone(), auth.clone(), form.clone(), saved.clone());
+            spawn_local(async move {
+                match client.update_me(&payload).await {
+                    Ok(user) => {
+                        auth.dispatch(AuthAction::UserUpdated(user));
+                        form.done();
+                        saved.set(true);
+                    }
+                    Err(error) => form.fail(&error),
+                }
+            });
+        })
+    };
+
+    let onavatar = {
+        let (client, i18n, avatar, avatar_error, avatar_busy) = (
+            client.clone(),
+            i18n.clone(),
+            avatar.clone(),
+            avatar_error.clone(),
+            avatar_busy.clone(),
+        );
+        Callback::from(move |event: Event| {
+            let input = event.target_unchecked_into::<HtmlInputElement>();
+            let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                return;
+            };
+            // Allow choosing the same file again
+            input.set_value("");
+            if !AVATAR_CONTENT_TYPES.contains(&file.type_().as_str()) {
+                avatar_error.set(Some(i18n.t("avatar-wrong-type")));
+                return;
+            }
+
+            let (client, i18n, avatar, avatar_error, avatar_busy) = (
+                client.clone(),
+                i18n.clone(),
+                avatar.clone(),
+                avatar_error.clone(),
+                avatar_busy.clone(),
+            );
+            avatar_busy.set(true);
+            spawn_local(async move {
+                let Ok(buffer) = JsFuture::from(file.array_buffer()).await else {
+                    avatar_error.set(Some(i18n.t("avatar-unreadable")));
+                    avatar_busy.set(false);
+                    return;
+                };
+                let data = Uint8Array::new(&buffer).to_vec();
+                match client.upload_avatar(data, &file.name(), &file.type_()).await {
+                    Ok(urls) => {
+                        avatar.set(urls.urls.get(&AVATAR_SIZE).map(|url| client.url(url)));
+                        avatar_error.set(None);
+                    }
+                    Err(error) => avatar_error.set(Some(error_message(&i18n, &error))),
+                }
+                avatar_busy.set(false);
+            });
+        })
+    };
+
+    let onremove = {
+        let (client, i18n, avatar, avatar_error, avatar_busy) = (
+            client.clone(),
+            i18n.clone(),
+            avatar.clone(),
+            avatar_error.clone(),
+            avatar_busy.clone(),
+        );
+        Callback::from(move |_: MouseEvent| {
+            let (client, i18n, avatar, avatar_error, avatar_busy) = (
+                client.clone(),
+                i18n.clone(),
+                avatar.clone(),
+                avatar_error.clone(),
+                avatar_busy.clone(),
+            );
+            avatar_busy.set(true);
+            spawn_local(async move {
+                match client.delete_avatar().await {
+                    Ok(()) => {
+                        avatar.set(None);
+                        avatar_error.set(None);
+                    }
+                    Err(error) => avatar_error.set(Some(error_message(&i18n, &error))),
+                }
+                avatar_busy.set(false);
+            });
+        })
+    };
+
+    let optional = |value: &Option<Option<String>>| value.clone().flatten().unwrap_or_default();
+    let button = "rounded-md border border-slate-300 dark:border-slate-600 px-4 py-2 hover:bg-slate-100 dark:hover:bg-slate-800 disabled:opacity-50 disabled:cursor-not-allowed";
+
+    html! {
+        <SettingsLayout title={i18n.t("nav-settings-profile")}>
+            <div class="flex items-center space-x-4">
+                if let Some(url) = &*avatar {
+                    <img src={url.clone()} alt={i18n.t("avatar")} width="64" height="64" class="w-16 h-16 rounded-full object-cover" />
+                } else {
+                    <div aria-hidden="true" class="flex justify-center items-center w-16 h-16 rounded-full bg-slate-200 dark:bg-slate-700 text-2xl">
+                        {user.username.chars().next().map(|initial| initial.to_uppercase().to_string()).unwrap_or_default()}
+                    </div>
+                }
+                <label class={classes!(button, "cursor-pointer", avatar_busy.then_some("opacity-50"))}>
+                    { if *avatar_busy { i18n.t("avatar-uploading") } else { i18n.t("avatar-upload") } }
+                    <input
+                        type="file"
+                        accept={AVATAR_CONTENT_TYPES.join(",")}
+                        disabled={*avatar_busy}
+                        class="sr-only"
+                        onchange={onavatar}
+                    />
+                </label>
+                if avatar.is_some() {
+                    <button type="button" class={button} disabled={*avatar_busy} onclick={onremove}>{i18n.t("avatar-remove")}</button>
+                }
+            </div>
+            <FormAlert message={(*avatar_error).clone()} />
+            <form class="flex flex-col space-y-4" novalidate=true {onsubmit}>
+                <FormAlert message={(*form.error).clone()} />
+                <FormSuccess message={saved.then(|| AttrValue::from(i18n.t("profile-saved")))} />
+                <TextField
+                    label={i18n.t("field-display-name")}
+                    name="display_name"
+                    autocomplete="nickname"
+                    value={optional(&form.payload.display_name)}
+                    errors={form.field_errors("display_name")}
+                    disabled={*form.loading}
+                    oninput={form.oninput("display_name", |payload: &UserProfileUpdate, value: String| UserProfileUpdate { display_name: Some(Some(value).filter(|value| !value.is_empty())), ..payload.clone() })}
+                />
+                <TextField
+                    label={i18n.t("field-locale")}
+                    name="locale"
+                    value={optional(&form.payload.locale)}
+                    errors={form.field_errors("locale")}
+                    disabled={*form.loading}
+                    oninput={form.oninput("locale", |payload: &UserProfileUpdate, value: String| UserProfileUpdate { locale: Some(Some(value).filter(|value| !value.is_empty())), ..payload.clone() })}
+                />
+                <TextField
+                    label={i18n.t("field-timezone")}
+                    name="timezone"
+                    value={optional(&form.payload.timezone)}
+                    errors={form.field_errors("timezone")}
+                    disabled={*form.loading}
+                    oninput={form.oninput("timezone", |payload: &UserProfileUpdate, value: String| UserProfileUpdate { timezone: Some(Some(value).filter(|value| !value.is_empty())), ..payload.clone() })}
+                />
+                <SubmitButton label={i18n.t("profile-submit")} loading_label={i18n.t("saving")} loading={*form.loading} />
+            </form>
+        </SettingsLayout>
+    }
+}
@@ -0,0 +1,121 @@
+use rustenv_client::ApiClient;
+use rustenv_shared::session::SessionInformation;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::api::error_message;
+use crate::components::form::FormAlert;
+use crate::i18n::use_i18n;
+use crate::views::settings::SettingsLayout;
+
+// Active sessions view, revoking sessions on other devices or logging out of this one
+#[function_component(SettingsSessions)]
+pub fn settings_sessions() -> Html {
+    let client = use_context::<ApiClient>().expect("API client context");
+    let i18n = use_i18n();
+    let sessions = use_state(|| None::<Vec<SessionInformation>>);
+    let error = use_state(|| None::<String>);
+    let busy = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+
+    use_effect_with(*reload, {
+        let (client, i18n, sessions, error) =
+            (client.clone(), i18n.clone(), sessions.clone(), error.clone());
+        move |_| {
+            spawn_local(async move {
+                match client.sessions().await {
+                    Ok(result) => sessions.set(Some(result)),
+                    Err(result) => error.set(Some(error_message(&i18n, &result))),
+                }
+            });
+        }
+    });
+
+    // Revoke session, logging out entirely when it is this one
+    let revoke = {
+        let (client, i18n, error, busy, reload) =
+            (client.clone(), i18n.clone(), error.clone(), busy.clone(), reload.clone());
+        move |session: &SessionInformation| {
+            let (client, i18n, error, busy, reload) =
+                (client.clone(), i18n.clone(), error.clone(), busy.clone(), reload.clone());
+            let (uuid, current) = (session.uuid.clone(), session.current);
+            Callback::from(move |_: MouseEvent| {
+                let (client, i18n, error, busy, reload, uuid) = (
+                    client.clone(),
+                    i18n.clone(),
+                    error.clone(),
+                    busy.clone(),
+                    reload.clone(),
+                    uuid.clone(),
+                );
+                busy.set(Some(uuid.clone()));
+                spawn_local(async move {
+                    if current {
+                        return client.logout().await;
+                    }
+                    match client.revoke_session(&uuid).await {
+                        Ok(()) => {
+                            error.set(None);
+                            reload.set(*reload + 1);
+                        }
+                        Err(result) => error.set(Some(error_message(&i18n, &result))),
+                    }
+                    busy.set(None);
+                });
+            })
+        }
+    };
+
+    let button = "rounded-md border border-slate-300 dark:border-slate-600 px-3 py-1 hover:bg-slate-100 dark:hover:bg-slate-800 disabled:opacity-50 disabled:cursor-not-allowed";
+    let row = |session: &SessionInformation| {
+        let label = match session.current {
+            true => i18n.t("nav-logout"),
+            false => i18n.t("sessions-revoke"),
+        };
+        html! {
+            <tr class="border-t border-slate-200 dark:border-slate-700">
+                <td class="py-2 pr-4 break-all">
+                    {session.user_agent.clone().unwrap_or_else(|| i18n.t("sessions-unknown-device"))}
+                    if session.current {
+                        <span class="ml-2 rounded bg-sky-100 dark:bg-sky-900 text-sky-700 dark:text-sky-300 px-2 py-0.5 text-xs">{i18n.t("sessions-this-device")}</span>
+                    }
+                </td>
+                <td class="py-2 pr-4">{session.ip.clone().unwrap_or_else(|| "—".to_string())}</td>
+                <td class="py-2 pr-4">{i18n.timestamp(session.created_at)}</td>
+                <td class="py-2 pr-4">{i18n.timestamp(session.last_used_at)}</td>
+                <td class="py-2 text-right">
+                    <button type="button" class={button} disabled={busy.is_some()} onclick={revoke(session)}>
+                        { if busy.as_deref() == Some(session.uuid.as_str()) { "...".to_string() } else { label } }
+                    </button>
+                </td>
+            </tr>
+        }
+    };
+
+    html! {
+        <SettingsLayout title={i18n.t("nav-settings-sessions")}>
+            <FormAlert message={(*error).clone()} />
+            if let Some(sessions) = &*sessions {
+                <p class="text-sm text-slate-500 dark:text-slate-400">
+                    {i18n.t_args("sessions-count", &[("count", sessions.len().into())])}
+                </p>
+                <table class="w-full text-sm text-left">
+                    <thead>
+                        <tr>
+                            <th class="py-2 pr-4 font-medium">{i18n.t("sessions-device")}</th>
+                            <th class="py-2 pr-4 font-medium">{i18n.t("sessions-ip")}</th>
+                            <th class="py-2 pr-4 font-medium">{i18n.t("sessions-created")}</th>
+                            <th class="py-2 pr-4 font-medium">{i18n.t("sessions-last-used")}</th>
+                            <th class="py-2"><span class="sr-only">{i18n.t("actions")}</span></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for sessions.iter().map(row) }
+                    </tbody>
+                </table>
+            } else if error.is_none() {
+                <p aria-busy="true" class="text-slate-500 dark:text-slate-400">{i18n.t("loading")}</p>
+            }
+        </SettingsLayout>
+    }
+}
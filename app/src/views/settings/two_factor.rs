@@ -0,0 +1,147 @@
+use qrcode::QrCode;
+use qrcode::render::svg;
+use rustenv_client::ApiClient;
+use rustenv_shared::two_factor::{TwoFactorCode, TwoFactorSetup};
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::api::error_message;
+use crate::auth::{AuthAction, use_auth};
+use crate::components::form::{FormAlert, SubmitButton, TextField};
+use crate::hooks::form::use_form;
+use crate::i18n::use_i18n;
+use crate::views::settings::SettingsLayout;
+
+// QR code of an otpauth URL as inline SVG
+fn qr_code(url: &str) -> Html {
+    let Ok(code) = QrCode::new(url.as_bytes()) else {
+        return html! {};
+    };
+    let image = code.render::<svg::Color>().min_dimensions(200, 200).build();
+
+    // Drop the XML declaration so the SVG can be inlined into HTML
+    let image = image.find("<svg").map_or(image.as_str(), |start| &image[start..]);
+    Html::from_html_unchecked(AttrValue::from(image.to_string()))
+}
+
+// Two-factor authentication view, enrolling an authenticator app or turning it off
+#[function_component(SettingsTwoFactor)]
+pub fn settings_two_factor() -> Html {
+    let client = use_context::<ApiClient>().expect("API client context");
+    let auth = use_auth();
+    let i18n = use_i18n();
+    let form = use_form::<TwoFactorCode>();
+    let setup = use_state(|| None::<TwoFactorSetup>);
+    let error = use_state(|| None::<String>);
+    let starting = use_state(|| false);
+    let enabled_at = auth.user.as_ref().and_then(|user| user.two_factor_enabled_at);
+
+    let onstart = {
+        let (client, i18n, setup, error, starting) =
+            (client.clone(), i18n.clone(), setup.clone(), error.clone(), starting.clone());
+        Callback::from(move |_: MouseEvent| {
+            let (client, i18n, setup, error, starting) =
+                (client.clone(), i18n.clone(), setup.clone(), error.clone(), starting.clone());
+            starting.set(true);
+            spawn_local(async move {
+                match client.start_two_factor().await {
+                    Ok(result) => {
+                        setup.set(Some(result));
+                        error.set(None);
+                    }
+                    Err(result) => error.set(Some(error_message(&i18n, &result))),
+                }
+                starting.set(false);
+            });
+        })
+    };
+
+    // Confirm a code to enable, or to disable once enabled
+    let onsubmit = {
+        let (form, setup) = (form.clone(), setup.clone());
+        let enabled = enabled_at.is_some();
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            let Some(payload) = form.submit() else {
+                return;
+            };
+
+            let (client, auth, form, setup) =
+                (client.clone(), auth.clone(), form.clone(), setup.clone());
+            spawn_local(async move {
+                let result = match enabled {
+                    true => client.disable_two_factor(&payload).await,
+                    false => client.confirm_two_factor(&payload).await,
+                };
+                match result {
+                    Ok(user) => {
+                        auth.dispatch(AuthAction::UserUpdated(user));
+                        form.payload.set(TwoFactorCode::default());
+                        form.done();
+                        setup.set(None);
+                    }
+                    Err(error) => form.fail(&error),
+                }
+            });
+        })
+    };
+
+    let code_form = |submit_label: String, loading_label: String| {
+        html! {
+            <form class="flex flex-col space-y-4 max-w-xs" novalidate=true onsubmit={onsubmit.clone()}>
+                <FormAlert message={(*form.error).clone()} />
+                <TextField
+                    label={i18n.t("field-two-factor-code")}
+                    name="code"
+                    autocomplete="one-time-code"
+                    value={form.payload.code.clone()}
+                    errors={form.field_errors("code")}
+                    disabled={*form.loading}
+                    oninput={form.oninput("code", |_: &TwoFactorCode, value: String| TwoFactorCode { code: value.trim().to_string() })}
+                />
+                <SubmitButton label={submit_label} {loading_label} loading={*form.loading} />
+            </form>
+        }
+    };
+
+    let content = match (enabled_at, &*setup) {
+        (Some(enabled_at), _) => html! {
+            <>
+                <p>{i18n.t_args("two-factor-enabled-since", &[("date", i18n.timestamp(enabled_at).into())])}</p>
+                <p class="text-sm text-slate-500 dark:text-slate-400">{i18n.t("two-factor-disable-hint")}</p>
+                {code_form(i18n.t("two-factor-disable"), i18n.t("two-factor-disabling"))}
+            </>
+        },
+        (None, Some(setup)) => html! {
+            <>
+                <p>{i18n.t("two-factor-scan")}</p>
+                <div class="w-fit rounded-md bg-white p-2">{qr_code(&setup.otpauth_url)}</div>
+                <p class="text-sm">
+                    {i18n.t("two-factor-manual-key")}{" "}
+                    <code class="break-all font-mono">{&setup.secret}</code>
+                </p>
+                {code_form(i18n.t("two-factor-enable"), i18n.t("two-factor-enabling"))}
+            </>
+        },
+        (None, None) => html! {
+            <>
+                <p>{i18n.t("two-factor-intro")}</p>
+                <button
+                    type="button"
+                    disabled={*starting}
+                    onclick={onstart}
+                    class="w-fit rounded-md bg-sky-600 hover:bg-sky-700 disabled:opacity-60 disabled:cursor-wait text-white font-medium px-4 py-2"
+                >
+                    { if *starting { i18n.t("two-factor-starting") } else { i18n.t("two-factor-start") } }
+                </button>
+            </>
+        },
+    };
+
+    html! {
+        <SettingsLayout title={i18n.t("two-factor-title")}>
+            <FormAlert message={(*error).clone()} />
+            {content}
+        </SettingsLayout>
+    }
+}
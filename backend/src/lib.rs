@@ -1,23 +1,133 @@
-use std::net::SocketAddr;
-
-use axum::Router;
-use axum::http::Method;
-use tokio::net::TcpListener;
-use tower_http::cors::{Any, CorsLayer};
-
-pub async fn app(port: u16) {
-    let app = Router::new().layer(CorsLayer::new().allow_origin(Any).allow_methods(vec![
-        Method::GET,
-        Method::POST,
-        Method::PUT,
-        Method::DELETE,
-        Method::PATCH,
-    ]));
-
-    let addr = SocketAddr::from(([127, 0, 0, 1], port));
-    println!("Backend listening on http://{}", addr);
-
-    let listener = TcpListener::bind(addr).await.unwrap();
-
-    axum::serve(listener, app.into_make_service()).await.unwrap();
-}
+use std::future::{Future, IntoFuture};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use axum::http::Method;
+use axum::middleware::from_fn;
+use axum::routing::get;
+use axum::{Json, Router};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use once_cell::sync::OnceCell;
+use rustenv_service::{SECONDS_BUCKETS, drain_timeout, track_metrics};
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+use tower_http::LatencyUnit;
+use tower_http::cors::{Any, CorsLayer};
+use tower_http::trace::{DefaultMakeSpan, DefaultOnResponse, TraceLayer};
+use tracing::Level;
+
+// Prometheus recorder handle
+static METRICS: OnceCell<PrometheusHandle> = OnceCell::new();
+
+// Interval between recorder upkeep runs
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+// Handle for stopping a running backend
+#[derive(Clone, Debug, Default)]
+pub struct ShutdownHandle {
+    token: CancellationToken,
+}
+
+impl ShutdownHandle {
+    // Stop accepting connections and drain in-flight requests
+    pub fn shutdown(&self) {
+        self.token.cancel();
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+// Create backend server future along with a handle to shut it down
+pub fn app(port: u16) -> (ShutdownHandle, impl Future<Output = ()> + Send + 'static) {
+    let shutdown = ShutdownHandle::default();
+    (shutdown.clone(), serve(port, shutdown))
+}
+
+async fn serve(port: u16, shutdown: ShutdownHandle) {
+    // Install metrics recorder and drain histogram data periodically
+    let handle = metrics_handle();
+    let upkeep_shutdown = shutdown.token.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(UPKEEP_INTERVAL) => handle.run_upkeep(),
+                _ = upkeep_shutdown.cancelled() => break,
+            }
+        }
+    });
+
+    let app = Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .route("/metrics", get(render_metrics))
+        .layer(from_fn(track_metrics))
+        .layer(CorsLayer::new().allow_origin(Any).allow_methods(vec![
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::DELETE,
+            Method::PATCH,
+        ]))
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(DefaultMakeSpan::new().level(Level::INFO))
+                .on_response(
+                    DefaultOnResponse::new().level(Level::INFO).latency_unit(LatencyUnit::Millis),
+                ),
+        );
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    tracing::info!("Backend listening on http://{}", addr);
+
+    let listener = TcpListener::bind(addr).await.unwrap();
+
+    // Serve until shut down, then give in-flight requests time to drain
+    let server = axum::serve(listener, app.into_make_service())
+        .with_graceful_shutdown(shutdown.token.clone().cancelled_owned());
+    let drain_timeout = drain_timeout();
+    let drain = async {
+        shutdown.token.cancelled().await;
+        tokio::time::sleep(drain_timeout).await;
+    };
+
+    tokio::select! {
+        result = server.into_future() => match result {
+            Ok(_) => tracing::info!("Backend stopped"),
+            Err(error) => tracing::error!(%error, "Backend failed"),
+        },
+        _ = drain => tracing::warn!("Drain timeout elapsed, dropping remaining connections"),
+    }
+}
+
+// Get Prometheus handle, installing the recorder on first use
+fn metrics_handle() -> &'static PrometheusHandle {
+    METRICS.get_or_init(|| {
+        let builder = PrometheusBuilder::new()
+            .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), SECONDS_BUCKETS)
+            .expect("Invalid metrics buckets");
+        let recorder = builder.build_recorder();
+        let handle = recorder.handle();
+        if metrics::set_global_recorder(recorder).is_err() {
+            tracing::warn!("Metrics recorder already installed, backend metrics will be empty");
+        }
+        handle
+    })
+}
+
+// Liveness route
+async fn healthz() -> Json<Value> {
+    Json(json!({ "status": "up" }))
+}
+
+// Readiness route (the backend has no external dependencies yet)
+async fn readyz() -> Json<Value> {
+    Json(json!({ "status": "up", "checks": {} }))
+}
+
+// Metrics route
+async fn render_metrics() -> String {
+    metrics_handle().render()
+}
@@ -0,0 +1,55 @@
+use reqwest::Method;
+use rustenv_shared::session::SessionInformation;
+use rustenv_shared::two_factor::{TwoFactorCode, TwoFactorSetup};
+use rustenv_shared::user::{UserEmailChange, UserInformation, UserPasswordChange};
+
+use crate::client::ApiClient;
+use crate::error::ClientError;
+
+impl ApiClient {
+    // Change own password, which ends every other session
+    pub async fn change_password(&self, payload: &UserPasswordChange) -> Result<(), ClientError> {
+        self.send_authorized(|| self.request(Method::PUT, "/users/me/password").json(payload))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn change_email(
+        &self,
+        payload: &UserEmailChange,
+    ) -> Result<UserInformation, ClientError> {
+        self.send_json(Method::PUT, "/users/me/email", payload).await
+    }
+
+    // Start two-factor setup, returning the pending secret to confirm
+    pub async fn start_two_factor(&self) -> Result<TwoFactorSetup, ClientError> {
+        Ok(self
+            .send_authorized(|| self.request(Method::POST, "/users/me/two-factor"))
+            .await?
+            .json()
+            .await?)
+    }
+
+    pub async fn confirm_two_factor(
+        &self,
+        payload: &TwoFactorCode,
+    ) -> Result<UserInformation, ClientError> {
+        self.send_json(Method::POST, "/users/me/two-factor/confirm", payload).await
+    }
+
+    pub async fn disable_two_factor(
+        &self,
+        payload: &TwoFactorCode,
+    ) -> Result<UserInformation, ClientError> {
+        self.send_json(Method::POST, "/users/me/two-factor/disable", payload).await
+    }
+
+    // Active sessions, with the one held by this client marked current
+    pub async fn sessions(&self) -> Result<Vec<SessionInformation>, ClientError> {
+        self.get_json("/users/me/sessions").await
+    }
+
+    pub async fn revoke_session(&self, uuid: &str) -> Result<(), ClientError> {
+        self.send_empty(Method::DELETE, &format!("/users/me/sessions/{}", uuid)).await
+    }
+}
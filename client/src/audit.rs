@@ -0,0 +1,22 @@
+use reqwest::Method;
+use rustenv_shared::audit::{AuditEvent, AuditQuery};
+use rustenv_shared::pagination::Page;
+
+use crate::client::ApiClient;
+use crate::error::ClientError;
+
+impl ApiClient {
+    // Page of audit events matching query (admin only)
+    pub async fn audit_events(&self, query: &AuditQuery) -> Result<Page<AuditEvent>, ClientError> {
+        self.get_query_json("/audit/events", query).await
+    }
+
+    // Every audit event matching query as CSV or JSON Lines, per the query's format (admin only)
+    pub async fn export_audit_events(&self, query: &AuditQuery) -> Result<String, ClientError> {
+        Ok(self
+            .send_authorized(|| self.request(Method::GET, "/audit/events/export").query(query))
+            .await?
+            .text()
+            .await?)
+    }
+}
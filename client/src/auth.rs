@@ -0,0 +1,41 @@
+use reqwest::Method;
+use rustenv_shared::user::{UserInformation, UserLogin, UserRegister};
+
+use crate::client::{ApiClient, Tokens, authorization_token};
+use crate::error::ClientError;
+
+impl ApiClient {
+    // Register user and log in as them
+    pub async fn register(&self, payload: &UserRegister) -> Result<UserInformation, ClientError> {
+        let response =
+            self.send(self.request(Method::POST, "/auth/register").json(payload)).await?;
+        self.start_session(response).await
+    }
+
+    // Log in by username or email and password
+    pub async fn login(&self, payload: &UserLogin) -> Result<UserInformation, ClientError> {
+        let response = self.send(self.request(Method::POST, "/auth/login").json(payload)).await?;
+        self.start_session(response).await
+    }
+
+    // End the session on the server and forget held tokens, even if the server is unreachable
+    pub async fn logout(&self) {
+        if let Some(request_token) = self.tokens().request_token {
+            let request = self.request(Method::POST, "/auth/logout").bearer_auth(request_token);
+            let _ = self.send(request).await;
+        }
+        self.clear_tokens();
+    }
+
+    // Keep the request token from a login response and exchange it for an access token
+    async fn start_session(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<UserInformation, ClientError> {
+        let request_token = authorization_token(&response)?;
+        let user_info = response.json::<UserInformation>().await?;
+        self.set_tokens(Tokens { request_token: Some(request_token), access_token: None });
+        self.refresh().await?;
+        Ok(user_info)
+    }
+}
@@ -0,0 +1,234 @@
+use std::sync::{Arc, RwLock};
+
+use http::StatusCode;
+use http::header::AUTHORIZATION;
+use reqwest::{Method, RequestBuilder, Response};
+use rustenv_shared::auth::AuthToken;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ClientError;
+
+// Tokens held by the client, persisted by callers between sessions
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Tokens {
+    // Long-lived token from login, exchanged for access tokens
+    pub request_token: Option<String>,
+    // Short-lived token sent with authenticated requests
+    pub access_token: Option<String>,
+}
+
+impl Tokens {
+    pub fn is_empty(&self) -> bool {
+        self.request_token.is_none() && self.access_token.is_none()
+    }
+}
+
+type TokenListener = Arc<dyn Fn(&Tokens) + Send + Sync>;
+
+type ErrorListener = Arc<dyn Fn(&ClientError) + Send + Sync>;
+
+// Typed client for the rustenv server API, using fetch on wasm and hyper natively
+#[derive(Clone)]
+pub struct ApiClient {
+    base_url: String,
+    http: reqwest::Client,
+    tokens: Arc<RwLock<Tokens>>,
+    listener: Option<TokenListener>,
+    error_listener: Option<ErrorListener>,
+}
+
+impl ApiClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_http(base_url, reqwest::Client::new())
+    }
+
+    pub fn with_http(base_url: impl Into<String>, http: reqwest::Client) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            http,
+            tokens: Arc::default(),
+            listener: None,
+            error_listener: None,
+        }
+    }
+
+    // Restore previously persisted tokens
+    pub fn with_tokens(self, tokens: Tokens) -> Self {
+        *self.tokens.write().unwrap_or_else(|error| error.into_inner()) = tokens;
+        self
+    }
+
+    // Call listener whenever the held tokens change, including automatic refreshes
+    pub fn on_tokens_changed(mut self, listener: impl Fn(&Tokens) + Send + Sync + 'static) -> Self {
+        self.listener = Some(Arc::new(listener));
+        self
+    }
+
+    // Call listener with every failed request, such as to show connection problems globally
+    pub fn on_error(mut self, listener: impl Fn(&ClientError) + Send + Sync + 'static) -> Self {
+        self.error_listener = Some(Arc::new(listener));
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn tokens(&self) -> Tokens {
+        self.tokens.read().unwrap_or_else(|error| error.into_inner()).clone()
+    }
+
+    pub fn set_tokens(&self, tokens: Tokens) {
+        *self.tokens.write().unwrap_or_else(|error| error.into_inner()) = tokens.clone();
+        if let Some(listener) = &self.listener {
+            listener(&tokens);
+        }
+    }
+
+    pub fn clear_tokens(&self) {
+        self.set_tokens(Tokens::default());
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        self.tokens().request_token.is_some()
+    }
+
+    // Absolute URL for a server path or a URL the server returned, such as page and media links
+    pub fn url(&self, path: &str) -> String {
+        match path.starts_with("http://") || path.starts_with("https://") {
+            true => path.to_string(),
+            false => format!("{}{}", self.base_url, path),
+        }
+    }
+
+    pub(crate) fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http.request(method, self.url(path))
+    }
+
+    // Pass error to the error listener where the request failed, so each is reported once
+    fn report(&self, error: ClientError) -> ClientError {
+        if let Some(listener) = &self.error_listener {
+            listener(&error);
+        }
+        error
+    }
+
+    // Send unauthenticated request, turning error responses into client errors
+    pub(crate) async fn send(&self, request: RequestBuilder) -> Result<Response, ClientError> {
+        let response = request.send().await.map_err(|error| self.report(error.into()))?;
+        match response.status().is_success() {
+            true => Ok(response),
+            false => Err(self.report(ClientError::from_response(response).await)),
+        }
+    }
+
+    // Send request with the access token, refreshing it once if the server rejects it
+    pub(crate) async fn send_authorized<F>(&self, build: F) -> Result<Response, ClientError>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let access_token = match self.tokens().access_token {
+            Some(access_token) => access_token,
+            None => self.refresh().await?.access_token,
+        };
+        let response = build()
+            .bearer_auth(&access_token)
+            .send()
+            .await
+            .map_err(|error| self.report(error.into()))?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return match response.status().is_success() {
+                true => Ok(response),
+                false => Err(self.report(ClientError::from_response(response).await)),
+            };
+        }
+
+        // Another request may already have refreshed the token
+        let refreshed = match self.tokens().access_token {
+            Some(current) if current != access_token => current,
+            _ => self.refresh().await?.access_token,
+        };
+        self.send(build().bearer_auth(refreshed)).await
+    }
+
+    // Exchange the request token for a new access token
+    pub async fn refresh(&self) -> Result<AuthToken, ClientError> {
+        let request_token = self.tokens().request_token.ok_or(ClientError::Unauthenticated)?;
+        let response =
+            self.send(self.request(Method::POST, "/auth/token").bearer_auth(&request_token)).await;
+
+        // A rejected request token cannot be recovered from, so log out
+        let response = match response {
+            Err(error) if error.status() == Some(StatusCode::UNAUTHORIZED) => {
+                self.clear_tokens();
+                return Err(error);
+            }
+            response => response?,
+        };
+        let auth_token = response.json::<AuthToken>().await?;
+        self.set_tokens(Tokens {
+            request_token: Some(request_token),
+            access_token: Some(auth_token.access_token.clone()),
+        });
+        Ok(auth_token)
+    }
+
+    pub(crate) async fn get_json<T>(&self, path: &str) -> Result<T, ClientError>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(self.send_authorized(|| self.request(Method::GET, path)).await?.json().await?)
+    }
+
+    pub(crate) async fn get_query_json<Q, T>(&self, path: &str, query: &Q) -> Result<T, ClientError>
+    where
+        Q: Serialize,
+        T: DeserializeOwned,
+    {
+        Ok(self
+            .send_authorized(|| self.request(Method::GET, path).query(query))
+            .await?
+            .json()
+            .await?)
+    }
+
+    pub(crate) async fn send_json<B, T>(
+        &self,
+        method: Method,
+        path: &str,
+        body: &B,
+    ) -> Result<T, ClientError>
+    where
+        B: Serialize,
+        T: DeserializeOwned,
+    {
+        Ok(self
+            .send_authorized(|| self.request(method.clone(), path).json(body))
+            .await?
+            .json()
+            .await?)
+    }
+
+    pub(crate) async fn send_empty(&self, method: Method, path: &str) -> Result<(), ClientError> {
+        self.send_authorized(|| self.request(method.clone(), path)).await?;
+        Ok(())
+    }
+}
+
+// Clients are equal when they share token state, as clones do
+impl PartialEq for ApiClient {
+    fn eq(&self, other: &Self) -> bool {
+        self.base_url == other.base_url && Arc::ptr_eq(&self.tokens, &other.tokens)
+    }
+}
+
+// Token from the authorization header of a login or register response
+pub(crate) fn authorization_token(response: &Response) -> Result<String, ClientError> {
+    response
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim_start_matches("Bearer ").to_string())
+        .ok_or_else(|| ClientError::Decode("Response is missing the request token".to_string()))
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+
+use http::StatusCode;
+use rustenv_shared::auth::AuthErrorType;
+use rustenv_shared::error::{PROBLEM_JSON, ProblemDetails};
+
+// API client error
+#[derive(Clone, Debug)]
+pub enum ClientError {
+    Api(Box<ProblemDetails>),
+    Unauthenticated,
+    Network(String),
+    Decode(String),
+}
+
+impl ClientError {
+    pub fn error_type(&self) -> AuthErrorType {
+        match self {
+            ClientError::Api(problem) => problem.body.error_type.to_owned(),
+            ClientError::Unauthenticated => AuthErrorType::InvalidToken,
+            ClientError::Network(_) => AuthErrorType::ServiceUnavailable,
+            ClientError::Decode(_) => AuthErrorType::ServerError,
+        }
+    }
+
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ClientError::Api(problem) => StatusCode::from_u16(problem.status).ok(),
+            _ => None,
+        }
+    }
+
+    // Problem details of an error response from the server
+    pub fn problem(&self) -> Option<&ProblemDetails> {
+        match self {
+            ClientError::Api(problem) => Some(problem),
+            _ => None,
+        }
+    }
+
+    // Decode error response, falling back to a problem built from the status for other bodies
+    pub(crate) async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let is_problem = response
+            .headers()
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with(PROBLEM_JSON));
+        let body = match response.text().await {
+            Ok(body) => body,
+            Err(error) => return ClientError::from(error),
+        };
+
+        if is_problem {
+            if let Ok(problem) = serde_json::from_str::<ProblemDetails>(&body) {
+                return ClientError::Api(Box::new(problem));
+            }
+        }
+        let detail = Some(body).filter(|body| !body.is_empty());
+        let mut problem = ProblemDetails::from_type(status_error_type(status), detail);
+        problem.status = status.as_u16();
+        ClientError::Api(Box::new(problem))
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::Api(problem) => write!(f, "{}", problem.body.error_message),
+            ClientError::Unauthenticated => write!(f, "Not logged in"),
+            ClientError::Network(detail) | ClientError::Decode(detail) => write!(f, "{}", detail),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(error: reqwest::Error) -> Self {
+        match error.is_decode() {
+            true => ClientError::Decode(format!("Error decoding response: {}", error)),
+            false => ClientError::Network(format!("Error sending request: {}", error)),
+        }
+    }
+}
+
+// Closest error type for responses without problem details
+fn status_error_type(status: StatusCode) -> AuthErrorType {
+    match status {
+        StatusCode::BAD_REQUEST
+        | StatusCode::METHOD_NOT_ALLOWED
+        | StatusCode::PAYLOAD_TOO_LARGE
+        | StatusCode::UNSUPPORTED_MEDIA_TYPE => AuthErrorType::BadRequest,
+        StatusCode::UNAUTHORIZED => AuthErrorType::InvalidToken,
+        StatusCode::FORBIDDEN => AuthErrorType::Forbidden,
+        StatusCode::NOT_FOUND => AuthErrorType::NotFound,
+        StatusCode::CONFLICT => AuthErrorType::Conflict,
+        StatusCode::UNPROCESSABLE_ENTITY => AuthErrorType::InvalidInput,
+        StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT => {
+            AuthErrorType::ServiceUnavailable
+        }
+        _ => AuthErrorType::ServerError,
+    }
+}
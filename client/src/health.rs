@@ -0,0 +1,22 @@
+use http::StatusCode;
+use reqwest::Method;
+use rustenv_shared::health::HealthReport;
+
+use crate::client::ApiClient;
+use crate::error::ClientError;
+
+impl ApiClient {
+    // Liveness report
+    pub async fn healthz(&self) -> Result<HealthReport, ClientError> {
+        Ok(self.send(self.request(Method::GET, "/healthz")).await?.json().await?)
+    }
+
+    // Readiness report, returned whether or not the server is ready
+    pub async fn readyz(&self) -> Result<HealthReport, ClientError> {
+        let response = self.request(Method::GET, "/readyz").send().await?;
+        match response.status() {
+            StatusCode::OK | StatusCode::SERVICE_UNAVAILABLE => Ok(response.json().await?),
+            _ => Err(ClientError::from_response(response).await),
+        }
+    }
+}
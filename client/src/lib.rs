@@ -0,0 +1,12 @@
+mod account;
+mod audit;
+mod auth;
+mod client;
+mod error;
+mod health;
+mod users;
+
+pub use rustenv_shared as types;
+
+pub use crate::client::{ApiClient, Tokens};
+pub use crate::error::ClientError;
@@ -0,0 +1,126 @@
+use reqwest::Method;
+use reqwest::multipart::{Form, Part};
+use rustenv_shared::media::AvatarUrls;
+use rustenv_shared::pagination::Page;
+use rustenv_shared::user::{
+    UserDataExport, UserInformation, UserListQuery, UserProfileUpdate, UserRoleUpdate,
+};
+use serde::de::DeserializeOwned;
+
+use crate::client::ApiClient;
+use crate::error::ClientError;
+
+impl ApiClient {
+    // Page of users matching query (admin only)
+    pub async fn list_users(
+        &self,
+        query: &UserListQuery,
+    ) -> Result<Page<UserInformation>, ClientError> {
+        self.get_query_json("/users", query).await
+    }
+
+    // Page behind a next or prev link of a previous page
+    pub async fn page<T>(&self, link: &str) -> Result<Page<T>, ClientError>
+    where
+        T: DeserializeOwned,
+    {
+        self.get_json(link).await
+    }
+
+    pub async fn me(&self) -> Result<UserInformation, ClientError> {
+        self.get_json("/users/me").await
+    }
+
+    // Update own profile, where a field set to Some(None) is cleared
+    pub async fn update_me(
+        &self,
+        update: &UserProfileUpdate,
+    ) -> Result<UserInformation, ClientError> {
+        Ok(self
+            .send_authorized(|| self.request(Method::PATCH, "/users/me").json(update))
+            .await?
+            .json()
+            .await?)
+    }
+
+    // Delete own account
+    pub async fn delete_me(&self) -> Result<(), ClientError> {
+        self.send_empty(Method::DELETE, "/users/me").await?;
+        self.clear_tokens();
+        Ok(())
+    }
+
+    // Personal data export
+    pub async fn export_me(&self) -> Result<UserDataExport, ClientError> {
+        self.get_json("/users/me/export").await
+    }
+
+    pub async fn my_avatar(&self) -> Result<AvatarUrls, ClientError> {
+        self.get_json("/users/me/avatar").await
+    }
+
+    // Upload JPEG, PNG or WebP image as own avatar
+    pub async fn upload_avatar(
+        &self,
+        data: Vec<u8>,
+        file_name: &str,
+        content_type: &str,
+    ) -> Result<AvatarUrls, ClientError> {
+        // Multipart forms cannot be cloned, so build one per attempt
+        let part =
+            || Part::bytes(data.clone()).file_name(file_name.to_string()).mime_str(content_type);
+        part()?;
+        Ok(self
+            .send_authorized(|| {
+                let part = part().expect("content type was validated above");
+                self.request(Method::PUT, "/users/me/avatar")
+                    .multipart(Form::new().part("avatar", part))
+            })
+            .await?
+            .json()
+            .await?)
+    }
+
+    pub async fn delete_avatar(&self) -> Result<(), ClientError> {
+        self.send_empty(Method::DELETE, "/users/me/avatar").await
+    }
+
+    pub async fn user_avatar(&self, uuid: &str) -> Result<AvatarUrls, ClientError> {
+        self.get_json(&format!("/users/{}/avatar", uuid)).await
+    }
+
+    // User by UUID (admin only)
+    pub async fn user(&self, uuid: &str) -> Result<UserInformation, ClientError> {
+        self.get_json(&format!("/users/{}", uuid)).await
+    }
+
+    // Grant or revoke admin role of user (admin only)
+    pub async fn set_user_role(
+        &self,
+        uuid: &str,
+        is_admin: bool,
+    ) -> Result<UserInformation, ClientError> {
+        let path = format!("/users/{}/role", uuid);
+        let update = UserRoleUpdate { is_admin };
+        Ok(self
+            .send_authorized(|| self.request(Method::PUT, &path).json(&update))
+            .await?
+            .json()
+            .await?)
+    }
+
+    // Delete user (admin only)
+    pub async fn delete_user(&self, uuid: &str) -> Result<(), ClientError> {
+        self.send_empty(Method::DELETE, &format!("/users/{}", uuid)).await
+    }
+
+    // Disable user, preventing them from logging in (admin only)
+    pub async fn disable_user(&self, uuid: &str) -> Result<(), ClientError> {
+        self.send_empty(Method::POST, &format!("/users/{}/disable", uuid)).await
+    }
+
+    // Enable disabled user (admin only)
+    pub async fn enable_user(&self, uuid: &str) -> Result<(), ClientError> {
+        self.send_empty(Method::POST, &format!("/users/{}/enable", uuid)).await
+    }
+}
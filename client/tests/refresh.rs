@@ -0,0 +1,159 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use axum::extract::State;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use rustenv_client::types::auth::AuthToken;
+use rustenv_client::types::user::UserLogin;
+use rustenv_client::{ApiClient, Tokens};
+use serde_json::json;
+use tokio::net::TcpListener;
+
+// Request token the mock server accepts
+const REQUEST_TOKEN: &str = "request-token";
+
+// Mock server state counting calls, accepting only the last access token it issued
+#[derive(Default)]
+struct Mock {
+    token_calls: AtomicUsize,
+    me_calls: AtomicUsize,
+    access_token: Mutex<Option<String>>,
+    // Reject every access token, as for an account disabled between refreshes
+    reject_all: bool,
+}
+
+fn bearer(headers: &HeaderMap) -> String {
+    let value = headers.get(AUTHORIZATION).and_then(|value| value.to_str().ok());
+    value.unwrap_or_default().trim_start_matches("Bearer ").to_string()
+}
+
+async fn login() -> Response {
+    let user = json!({
+        "uuid": "user-uuid",
+        "username": "user",
+        "email": "user@example.com",
+        "is_admin": false,
+        "display_name": null,
+        "avatar_url": null,
+        "locale": null,
+        "timezone": null,
+        "created_at": 0,
+        "updated_at": 0,
+        "last_login_at": null,
+        "disabled_at": null,
+        "two_factor_enabled_at": null,
+    });
+    ([(AUTHORIZATION, format!("Bearer {}", REQUEST_TOKEN))], Json(user)).into_response()
+}
+
+async fn token(State(mock): State<Arc<Mock>>, headers: HeaderMap) -> Response {
+    let calls = mock.token_calls.fetch_add(1, Ordering::SeqCst) + 1;
+    if bearer(&headers) != REQUEST_TOKEN {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    let access_token = format!("access-{}", calls);
+    *mock.access_token.lock().unwrap() = Some(access_token.clone());
+    Json(AuthToken::new(access_token)).into_response()
+}
+
+async fn me(State(mock): State<Arc<Mock>>, headers: HeaderMap) -> Response {
+    mock.me_calls.fetch_add(1, Ordering::SeqCst);
+    let valid = mock.access_token.lock().unwrap().clone();
+    match !mock.reject_all && valid == Some(bearer(&headers)) {
+        true => login().await,
+        false => StatusCode::UNAUTHORIZED.into_response(),
+    }
+}
+
+// Start mock server, returning a client for it along with every token change it reports
+async fn serve(mock: Mock) -> (ApiClient, Arc<Mock>, Arc<Mutex<Vec<Tokens>>>) {
+    let mock = Arc::new(mock);
+    let app = Router::new()
+        .route("/auth/login", post(login))
+        .route("/auth/token", post(token))
+        .route("/users/me", get(me))
+        .with_state(mock.clone());
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let listener_changes = changes.clone();
+    let client = ApiClient::new(base_url)
+        .on_tokens_changed(move |tokens| listener_changes.lock().unwrap().push(tokens.clone()));
+    (client, mock, changes)
+}
+
+fn tokens(request_token: &str, access_token: &str) -> Tokens {
+    Tokens {
+        request_token: Some(request_token.to_string()),
+        access_token: Some(access_token.to_string()),
+    }
+}
+
+// Logging in keeps the request token from the authorization header and exchanges it
+#[tokio::test]
+async fn start_session_uses_authorization_header() {
+    let (client, mock, changes) = serve(Mock::default()).await;
+    let payload = UserLogin {
+        username: "user".to_string(),
+        password: "password".to_string(),
+        two_factor_code: None,
+    };
+
+    let user = client.login(&payload).await.unwrap();
+    assert_eq!(user.username, "user");
+    assert_eq!(client.tokens(), tokens(REQUEST_TOKEN, "access-1"));
+    assert_eq!(mock.token_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(changes.lock().unwrap().last(), Some(&tokens(REQUEST_TOKEN, "access-1")));
+}
+
+// A rejected access token is refreshed and the request retried once
+#[tokio::test]
+async fn expired_access_token_is_refreshed_once() {
+    let (client, mock, _) = serve(Mock::default()).await;
+    let client = client.with_tokens(tokens(REQUEST_TOKEN, "expired"));
+
+    assert_eq!(client.me().await.unwrap().uuid, "user-uuid");
+    assert_eq!(mock.token_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(mock.me_calls.load(Ordering::SeqCst), 2);
+    assert_eq!(client.tokens(), tokens(REQUEST_TOKEN, "access-1"));
+
+    // The refreshed token is reused without another exchange
+    client.me().await.unwrap();
+    assert_eq!(mock.token_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(mock.me_calls.load(Ordering::SeqCst), 3);
+}
+
+// A request still rejected after refreshing fails instead of refreshing again
+#[tokio::test]
+async fn rejected_retry_is_not_repeated() {
+    let (client, mock, _) = serve(Mock { reject_all: true, ..Mock::default() }).await;
+    let client = client.with_tokens(tokens(REQUEST_TOKEN, "expired"));
+
+    let error = client.me().await.unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::UNAUTHORIZED));
+    assert_eq!(mock.token_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(mock.me_calls.load(Ordering::SeqCst), 2);
+    // The request token itself was accepted, so the session is kept
+    assert_eq!(client.tokens(), tokens(REQUEST_TOKEN, "access-1"));
+}
+
+// A rejected request token logs the client out
+#[tokio::test]
+async fn rejected_refresh_clears_tokens() {
+    let (client, mock, changes) = serve(Mock::default()).await;
+    let client = client.with_tokens(tokens("revoked", "expired"));
+
+    let error = client.me().await.unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::UNAUTHORIZED));
+    assert_eq!(mock.token_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(mock.me_calls.load(Ordering::SeqCst), 1);
+    assert!(client.tokens().is_empty());
+    assert!(!client.is_authenticated());
+    assert_eq!(changes.lock().unwrap().as_slice(), [Tokens::default()]);
+}
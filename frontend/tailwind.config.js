@@ -1,4 +1,6 @@
 module.exports = {
+    // Dark styles follow the class set from the theme preference
+    darkMode: "class",
     content: {
         relative: true,
         files: ["./*.html", "../app/src/**/*.rs"],
@@ -0,0 +1,4 @@
+ALTER TABLE "users"
+    ADD COLUMN IF NOT EXISTS created_at BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW())::BIGINT);
+
+CREATE INDEX IF NOT EXISTS users_created_at_idx ON "users" (created_at, id);
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS "audit_events" (
+    id BIGSERIAL PRIMARY KEY,
+    event_type TEXT NOT NULL,
+    outcome TEXT NOT NULL,
+    actor_uuid TEXT,
+    target TEXT,
+    ip TEXT,
+    user_agent TEXT,
+    detail TEXT,
+    created_at BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS audit_events_created_at_idx ON "audit_events" (created_at, id);
+CREATE INDEX IF NOT EXISTS audit_events_actor_uuid_idx ON "audit_events" (actor_uuid);
+CREATE INDEX IF NOT EXISTS audit_events_target_idx ON "audit_events" (target);
@@ -0,0 +1,5 @@
+ALTER TABLE "users"
+    ADD COLUMN IF NOT EXISTS deleted_at BIGINT,
+    ADD COLUMN IF NOT EXISTS disabled_at BIGINT;
+
+CREATE INDEX IF NOT EXISTS users_deleted_at_idx ON "users" (deleted_at);
@@ -0,0 +1,9 @@
+ALTER TABLE "users"
+    ADD COLUMN IF NOT EXISTS updated_at BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS last_login_at BIGINT,
+    ADD COLUMN IF NOT EXISTS display_name TEXT,
+    ADD COLUMN IF NOT EXISTS avatar_url TEXT,
+    ADD COLUMN IF NOT EXISTS locale TEXT,
+    ADD COLUMN IF NOT EXISTS timezone TEXT;
+
+UPDATE "users" SET updated_at = created_at WHERE updated_at = 0;
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS "sessions" (
+    id BIGSERIAL PRIMARY KEY,
+    uuid TEXT NOT NULL UNIQUE,
+    user_uuid TEXT NOT NULL,
+    ip TEXT,
+    user_agent TEXT,
+    created_at BIGINT NOT NULL,
+    last_used_at BIGINT NOT NULL,
+    expires_at BIGINT NOT NULL,
+    revoked_at BIGINT
+);
+
+CREATE INDEX IF NOT EXISTS sessions_user_uuid_idx ON "sessions" (user_uuid);
+CREATE INDEX IF NOT EXISTS sessions_expires_at_idx ON "sessions" (expires_at);
@@ -0,0 +1,3 @@
+ALTER TABLE "users"
+    ADD COLUMN IF NOT EXISTS totp_secret TEXT,
+    ADD COLUMN IF NOT EXISTS totp_enabled_at BIGINT;
@@ -0,0 +1,6 @@
+-- Time step of the last accepted authenticator code, so each code is accepted only once
+--
+-- totp_secret stays in plaintext: verifying codes needs the raw secret, and encrypting it here would
+-- only move the risk to a key stored next to the database credentials. Treat database backups as
+-- holding second factors.
+ALTER TABLE "users" ADD COLUMN IF NOT EXISTS totp_last_step BIGINT;
@@ -0,0 +1,6 @@
+-- SQLite only allows constant defaults when adding a column, so backfill existing rows
+ALTER TABLE "users" ADD COLUMN created_at INTEGER NOT NULL DEFAULT 0;
+
+UPDATE "users" SET created_at = CAST(strftime('%s', 'now') AS INTEGER);
+
+CREATE INDEX IF NOT EXISTS users_created_at_idx ON "users" (created_at, id);
@@ -0,0 +1,5 @@
+ALTER TABLE "users" ADD COLUMN deleted_at INTEGER;
+
+ALTER TABLE "users" ADD COLUMN disabled_at INTEGER;
+
+CREATE INDEX IF NOT EXISTS users_deleted_at_idx ON "users" (deleted_at);
@@ -0,0 +1,13 @@
+ALTER TABLE "users" ADD COLUMN updated_at INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE "users" ADD COLUMN last_login_at INTEGER;
+
+ALTER TABLE "users" ADD COLUMN display_name TEXT;
+
+ALTER TABLE "users" ADD COLUMN avatar_url TEXT;
+
+ALTER TABLE "users" ADD COLUMN locale TEXT;
+
+ALTER TABLE "users" ADD COLUMN timezone TEXT;
+
+UPDATE "users" SET updated_at = created_at WHERE updated_at = 0;
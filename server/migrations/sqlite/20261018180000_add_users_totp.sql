@@ -0,0 +1,3 @@
+ALTER TABLE "users" ADD COLUMN totp_secret TEXT;
+
+ALTER TABLE "users" ADD COLUMN totp_enabled_at INTEGER;
@@ -0,0 +1,37 @@
+use std::env;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::error::AppError;
+
+// Get environment variable
+pub fn env_var(key: &str) -> Result<String, AppError> {
+    env::var(key).map_err(|error| {
+        AppError::Internal(format!("Error with {} environment variable: {}", key, error))
+    })
+}
+
+// Get environment variable parsed as T
+pub fn env_parse<T>(key: &str) -> Result<T, AppError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    env_var(key)?.parse::<T>().map_err(|error| {
+        AppError::Internal(format!("Error parsing {} environment variable: {}", key, error))
+    })
+}
+
+// Get optional environment variable parsed as T, treating unset or empty as None
+pub fn env_parse_opt<T>(key: &str) -> Result<Option<T>, AppError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    match env::var(key) {
+        Ok(value) if !value.is_empty() => value.parse::<T>().map(Some).map_err(|error| {
+            AppError::Internal(format!("Error parsing {} environment variable: {}", key, error))
+        }),
+        _ => Ok(None),
+    }
+}
@@ -0,0 +1,279 @@
+use std::borrow::Cow;
+
+use axum::Router;
+use axum::routing::{delete, get, post, put};
+use bcrypt::verify;
+use http::StatusCode;
+use utoipa::OpenApi;
+use validator::{ValidationError, ValidationErrors};
+
+use crate::error::AppError;
+use crate::extract::{Json, Path, ValidatedJson};
+use crate::middleware::transaction::Tx;
+use crate::pool::Db;
+use crate::state::AppState;
+use crate::strategies::audit_strategy::AuditContext;
+use crate::strategies::auth_strategy::AuthClaims;
+use crate::strategies::{session_strategy, two_factor_strategy, user_strategy};
+use crate::types::audit::AuditEventType;
+use crate::types::error::ProblemDetails;
+use crate::types::session::SessionInformation;
+use crate::types::two_factor::{TwoFactorCode, TwoFactorSetup};
+use crate::types::user::{User, UserEmailChange, UserInformation, UserPasswordChange};
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    change_password,
+    change_email,
+    start_two_factor,
+    confirm_two_factor,
+    disable_two_factor,
+    list_sessions,
+    revoke_session
+))]
+pub struct AccountApi;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/me/password", put(change_password))
+        .route("/me/email", put(change_email))
+        .route("/me/two-factor", post(start_two_factor))
+        .route("/me/two-factor/confirm", post(confirm_two_factor))
+        .route("/me/two-factor/disable", post(disable_two_factor))
+        .route("/me/sessions", get(list_sessions))
+        .route("/me/sessions/:uuid", delete(revoke_session))
+}
+
+// Change own password route, ending every other session
+#[utoipa::path(
+    put,
+    path = "/me/password",
+    tag = "account",
+    security(("bearer" = [])),
+    request_body = UserPasswordChange,
+    responses(
+        (status = 204, description = "Password changed"),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid input or incorrect current password", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn change_password(
+    claims: AuthClaims,
+    mut tx: Tx,
+    audit: AuditContext,
+    ValidatedJson(payload): ValidatedJson<UserPasswordChange>,
+) -> Result<StatusCode, AppError> {
+    let user = user_strategy::get_db_user_by_uuid(&mut *tx, claims.sub.clone()).await?;
+    confirm_password(&payload.current_password, &user.password, "current_password")?;
+
+    let audit = audit.with_actor(&claims.sub);
+    user_strategy::update_db_user_password(&mut tx, &audit, user.uuid, payload.new_password)
+        .await?;
+    session_strategy::revoke_db_user_sessions(&mut *tx, &claims.sub, claims.sid.as_deref()).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Change own email route
+#[utoipa::path(
+    put,
+    path = "/me/email",
+    tag = "account",
+    security(("bearer" = [])),
+    request_body = UserEmailChange,
+    responses(
+        (status = 200, description = "Updated profile", body = UserInformation),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid input, incorrect password or email in use", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn change_email(
+    claims: AuthClaims,
+    mut tx: Tx,
+    audit: AuditContext,
+    ValidatedJson(payload): ValidatedJson<UserEmailChange>,
+) -> Result<Json<UserInformation>, AppError> {
+    let user = user_strategy::get_db_user_by_uuid(&mut *tx, claims.sub.clone()).await?;
+    confirm_password(&payload.password, &user.password, "password")?;
+
+    let audit = audit.with_actor(&claims.sub);
+    let user = user_strategy::update_db_user_email(&mut tx, &audit, user.uuid, payload.email)
+        .await
+        .map_err(|error| match error {
+            AppError::Conflict(_) => field_error("email", "email_taken", "Email is already in use"),
+            error => error,
+        })?;
+    Ok(Json(UserInformation::from(user)))
+}
+
+// Start two-factor setup route, replacing any pending secret
+#[utoipa::path(
+    post,
+    path = "/me/two-factor",
+    tag = "account",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Pending authenticator secret", body = TwoFactorSetup),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Two-factor authentication is already enabled", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn start_two_factor(
+    claims: AuthClaims,
+    mut tx: Tx,
+) -> Result<Json<TwoFactorSetup>, AppError> {
+    let user = user_strategy::get_db_user_by_uuid(&mut *tx, claims.sub).await?;
+    if user.has_two_factor() {
+        return Err(AppError::Conflict("Two-factor authentication is already enabled".to_string()));
+    }
+
+    let setup = two_factor_strategy::generate_setup(&user.username)?;
+    two_factor_strategy::set_db_user_totp_secret(&mut *tx, user.uuid, setup.secret.clone()).await?;
+    Ok(Json(setup))
+}
+
+// Enable two-factor authentication route, confirming a code from the pending secret
+#[utoipa::path(
+    post,
+    path = "/me/two-factor/confirm",
+    tag = "account",
+    security(("bearer" = [])),
+    request_body = TwoFactorCode,
+    responses(
+        (status = 200, description = "Updated profile", body = UserInformation),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Two-factor authentication is already enabled or was not started", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid or incorrect code", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn confirm_two_factor(
+    claims: AuthClaims,
+    mut tx: Tx,
+    audit: AuditContext,
+    ValidatedJson(payload): ValidatedJson<TwoFactorCode>,
+) -> Result<Json<UserInformation>, AppError> {
+    let user = user_strategy::get_db_user_by_uuid(&mut *tx, claims.sub.clone()).await?;
+    if user.has_two_factor() {
+        return Err(AppError::Conflict("Two-factor authentication is already enabled".to_string()));
+    }
+    let secret = user
+        .totp_secret
+        .as_deref()
+        .ok_or(AppError::Conflict("Two-factor setup has not been started".to_string()))?;
+    confirm_code(&mut tx, &user, secret, &payload.code).await?;
+
+    let audit = audit.with_actor(&claims.sub);
+    let user =
+        two_factor_strategy::set_db_user_two_factor(&mut tx, &audit, user.uuid, true).await?;
+    Ok(Json(UserInformation::from(user)))
+}
+
+// Disable two-factor authentication route, confirming a current code
+#[utoipa::path(
+    post,
+    path = "/me/two-factor/disable",
+    tag = "account",
+    security(("bearer" = [])),
+    request_body = TwoFactorCode,
+    responses(
+        (status = 200, description = "Updated profile", body = UserInformation),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Two-factor authentication is not enabled", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid or incorrect code", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn disable_two_factor(
+    claims: AuthClaims,
+    mut tx: Tx,
+    audit: AuditContext,
+    ValidatedJson(payload): ValidatedJson<TwoFactorCode>,
+) -> Result<Json<UserInformation>, AppError> {
+    let user = user_strategy::get_db_user_by_uuid(&mut *tx, claims.sub.clone()).await?;
+    let secret = user
+        .totp_secret
+        .as_deref()
+        .filter(|_| user.has_two_factor())
+        .ok_or(AppError::Conflict("Two-factor authentication is not enabled".to_string()))?;
+    confirm_code(&mut tx, &user, secret, &payload.code).await?;
+
+    let audit = audit.with_actor(&claims.sub);
+    let user =
+        two_factor_strategy::set_db_user_two_factor(&mut tx, &audit, user.uuid, false).await?;
+    Ok(Json(UserInformation::from(user)))
+}
+
+// Own active sessions route
+#[utoipa::path(
+    get,
+    path = "/me/sessions",
+    tag = "account",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Active sessions, most recently used first", body = Vec<SessionInformation>),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn list_sessions(
+    claims: AuthClaims,
+    db: Db,
+) -> Result<Json<Vec<SessionInformation>>, AppError> {
+    let sessions =
+        session_strategy::get_db_user_sessions(&mut *db.reader().await?, &claims.sub).await?;
+    let current = claims.sid.as_deref();
+    Ok(Json(sessions.into_iter().map(|session| session.information(current)).collect()))
+}
+
+// Revoke own session route
+#[utoipa::path(
+    delete,
+    path = "/me/sessions/{uuid}",
+    tag = "account",
+    security(("bearer" = [])),
+    params(("uuid" = String, Path, description = "Session UUID")),
+    responses(
+        (status = 204, description = "Session revoked"),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Session not found", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn revoke_session(
+    claims: AuthClaims,
+    mut tx: Tx,
+    audit: AuditContext,
+    Path(uuid): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let audit = audit.with_actor(&claims.sub);
+    session_strategy::revoke_db_session(
+        &mut tx,
+        &audit,
+        AuditEventType::SessionRevoke,
+        &claims.sub,
+        &uuid,
+    )
+    .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Validation error on a single field
+fn field_error(field: &'static str, code: &'static str, message: &'static str) -> AppError {
+    let mut errors = ValidationErrors::new();
+    errors.add(field, ValidationError::new(code).with_message(Cow::from(message)));
+    AppError::Validation(errors)
+}
+
+// Reject password that does not match the stored hash as an error on field
+fn confirm_password(password: &str, hash: &str, field: &'static str) -> Result<(), AppError> {
+    let verified = verify(password, hash)
+        .map_err(|error| AppError::Internal(format!("Error verifying password: {}", error)))?;
+    match verified {
+        true => Ok(()),
+        false => Err(field_error(field, "password_incorrect", "Password is incorrect")),
+    }
+}
+
+// Reject authenticator code that is not current for secret or was already used
+async fn confirm_code(tx: &mut Tx, user: &User, secret: &str, code: &str) -> Result<(), AppError> {
+    match two_factor_strategy::accept_code(&mut **tx, user, secret, code).await? {
+        true => Ok(()),
+        false => Err(field_error("code", "code_incorrect", "Code is incorrect")),
+    }
+}
@@ -0,0 +1,107 @@
+use axum::Router;
+use axum::body::Body;
+use axum::extract::OriginalUri;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use utoipa::OpenApi;
+
+use crate::error::AppError;
+use crate::extract::{Json, ValidatedQuery};
+use crate::pool::Db;
+use crate::state::AppState;
+use crate::strategies::audit_strategy;
+use crate::strategies::auth_strategy::AdminClaims;
+use crate::types::audit::{AuditEvent, AuditQuery};
+use crate::types::error::ProblemDetails;
+use crate::types::pagination::{DEFAULT_PAGE_LIMIT, Page, neighbour_offsets, page_link};
+
+#[derive(OpenApi)]
+#[openapi(paths(list_audit_events, export_audit_events))]
+pub struct AuditApi;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/events", get(list_audit_events))
+        .route("/events/export", get(export_audit_events))
+}
+
+// Audit event listing route
+#[utoipa::path(
+    get,
+    path = "/events",
+    tag = "audit",
+    security(("bearer" = [])),
+    params(AuditQuery),
+    responses(
+        (status = 200, description = "Page of audit events, newest first", body = Page<AuditEvent>),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "Insufficient permissions", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid input", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn list_audit_events(
+    _admin: AdminClaims,
+    db: Db,
+    OriginalUri(uri): OriginalUri,
+    ValidatedQuery(query): ValidatedQuery<AuditQuery>,
+) -> Result<Json<Page<AuditEvent>>, AppError> {
+    // Get page of audit events from database
+    let (events, total) =
+        audit_strategy::get_db_audit_events(&mut *db.reader().await?, &query).await?;
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+
+    // Link neighbouring pages by offset
+    let offset_link =
+        |offset: u64| page_link(uri.path(), &AuditQuery { offset: Some(offset), ..query.clone() });
+    let (next, prev) = neighbour_offsets(offset, limit, total);
+
+    Ok(Json(Page {
+        items: events,
+        total,
+        limit,
+        offset: Some(offset),
+        next: next.map(offset_link).transpose()?,
+        prev: prev.map(offset_link).transpose()?,
+    }))
+}
+
+// Audit event export route, streaming every matching event as CSV or JSON Lines
+#[utoipa::path(
+    get,
+    path = "/events/export",
+    tag = "audit",
+    security(("bearer" = [])),
+    params(AuditQuery),
+    responses(
+        (status = 200, description = "Matching audit events", content(
+            (String = "text/csv"),
+            (String = "application/x-ndjson"),
+        )),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "Insufficient permissions", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid input", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn export_audit_events(
+    _admin: AdminClaims,
+    db: Db,
+    ValidatedQuery(query): ValidatedQuery<AuditQuery>,
+) -> Result<Response, AppError> {
+    let format = query.format.unwrap_or_default();
+    let pool = db.reader_pool()?;
+    let stream = audit_strategy::export_audit_events(pool, query, format);
+
+    Ok((
+        [
+            (CONTENT_TYPE, format.content_type().to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"audit_events.{}\"", format.extension()),
+            ),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response())
+}
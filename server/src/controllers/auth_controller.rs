@@ -1,88 +1,312 @@
+use axum::Router;
 use axum::routing::post;
-use axum::{Json, Router};
 use bcrypt::verify;
 use http::header::AUTHORIZATION;
 use http::{HeaderMap, HeaderValue, StatusCode};
+use jsonwebtoken::get_current_timestamp;
+use metrics::counter;
+use tracing::Span;
+use utoipa::OpenApi;
 
-use crate::strategies::auth_strategy::{AuthError, AuthRequestClaims, JWTClaims};
-use crate::strategies::user_strategy;
+use crate::error::AppError;
+use crate::extract::{Json, ValidatedJson};
+use crate::middleware::transaction::Tx;
+use crate::pool::Db;
+use crate::state::AppState;
+use crate::strategies::audit_strategy::{self, AuditContext};
+use crate::strategies::auth_strategy::{self, AuthClaims, AuthRequestClaims, JWTClaims};
+use crate::strategies::{session_strategy, two_factor_strategy, user_strategy};
+use crate::types::audit::{AuditEventType, AuditOutcome};
 use crate::types::auth::{AuthErrorType, AuthToken};
+use crate::types::error::ProblemDetails;
+use crate::types::session::Session;
 use crate::types::user::{UserInformation, UserLogin, UserRegister};
 
-pub fn routes() -> Router {
-    Router::new().route("/register", post(register)).route("/login", post(login))
+#[derive(OpenApi)]
+#[openapi(paths(register, login, token, logout))]
+pub struct AuthApi;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/register", post(register))
+        .route("/login", post(login))
+        .route("/token", post(token))
+        .route("/logout", post(logout))
 }
 
 // User register route
+#[utoipa::path(
+    post,
+    path = "/register",
+    tag = "auth",
+    request_body = UserRegister,
+    responses(
+        (status = 201, description = "User registered", body = UserInformation, headers(
+            ("Authorization" = String, description = "Request token to exchange at /auth/token")
+        )),
+        (status = 409, description = "User already exists", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid input", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
 async fn register(
-    Json(payload): Json<UserRegister>,
-) -> Result<(StatusCode, HeaderMap, Json<UserInformation>), AuthError> {
-    // Attempt to insert user into database
-    let db_result = user_strategy::insert_db_user(payload).await;
-    if let Err(error) = db_result {
-        if error.to_string().contains("duplicate key") {
-            return Err(AuthError::from_type(AuthErrorType::UserExists));
+    db: Db,
+    mut tx: Tx,
+    audit: AuditContext,
+    ValidatedJson(payload): ValidatedJson<UserRegister>,
+) -> Result<(StatusCode, HeaderMap, Json<UserInformation>), AppError> {
+    // Attempt to insert user into database within the request transaction
+    let username = payload.username.clone();
+    let user = match user_strategy::insert_db_user(&mut *tx, payload).await {
+        Ok(user) => user,
+        Err(error) => {
+            let error = match error {
+                AppError::Conflict(_) => AppError::Auth(AuthErrorType::UserExists),
+                error => error,
+            };
+
+            // Roll back first, since the failed insert can hold the write lock the audit insert needs
+            if let Err(error) = tx.rollback().await {
+                tracing::error!(%error, "Error rolling back registration");
+            }
+
+            // Record failure outside the rolled back request transaction
+            let event = audit
+                .event(AuditEventType::Register, AuditOutcome::Failure)
+                .target(username)
+                .detail(error.error_type().slug());
+            audit_strategy::record_audit_event(&mut *db.writer().await?, &event).await;
+            return Err(error);
         }
-        return Err(AuthError::from_type(AuthErrorType::ServerError));
-    }
+    };
+
+    // Record registration atomically with the new user
+    let event = audit
+        .with_actor(&user.uuid)
+        .event(AuditEventType::Register, AuditOutcome::Success)
+        .target(&user.uuid);
+    audit_strategy::insert_audit_event(&mut *tx, &event).await?;
+
+    // Start session in the same transaction
+    let expires_at = session_expiry()?;
+    let session =
+        session_strategy::insert_db_session(&mut *tx, &audit, &user.uuid, expires_at).await?;
 
     // Create user information from user result
-    let user = db_result.unwrap();
-    let user_info = UserInformation::from_user(user);
-
-    // Generate authentication token from UUID
-    let auth_token: AuthToken;
-    let request_result =
-        AuthRequestClaims::new(user_info.uuid.clone()).await.unwrap().generate_token();
-    match request_result {
-        Ok(token) => auth_token = token,
-        Err(error) => {
-            println!("Error generating token from UUID {}: {:?}", user_info.uuid, error);
-            return Err(AuthError::from_type(AuthErrorType::TokenGeneration));
-        }
-    }
+    let user_info = UserInformation::from(user);
 
-    // Insert token into header map
-    let mut header_map = HeaderMap::new();
-    header_map.insert(AUTHORIZATION, HeaderValue::from_str(&auth_token.to_string()).unwrap());
+    // Generate authentication token header for session
+    let header_map = request_token_header(&session)?;
 
     // Return success response
     Ok((StatusCode::CREATED, header_map, Json(user_info)))
 }
 
 // User login route
+#[utoipa::path(
+    post,
+    path = "/login",
+    tag = "auth",
+    request_body = UserLogin,
+    responses(
+        (status = 200, description = "User logged in", body = UserInformation, headers(
+            ("Authorization" = String, description = "Request token to exchange at /auth/token")
+        )),
+        (status = 401, description = "Wrong credentials, or missing or invalid two-factor code", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "Account is disabled", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "User does not exist", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid input", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
 async fn login(
-    Json(payload): Json<UserLogin>,
-) -> Result<(StatusCode, HeaderMap, Json<UserInformation>), AuthError> {
+    db: Db,
+    audit: AuditContext,
+    ValidatedJson(payload): ValidatedJson<UserLogin>,
+) -> Result<(StatusCode, HeaderMap, Json<UserInformation>), AppError> {
+    // Attempt to log in and record outcome
+    let identifier = payload.username.clone();
+    let result = login_user(&db, &audit, payload).await;
+    let event = match &result {
+        Ok((_, _, Json(user_info))) => {
+            counter!("auth_login_total", "outcome" => "success").increment(1);
+            audit
+                .with_actor(&user_info.uuid)
+                .event(AuditEventType::Login, AuditOutcome::Success)
+                .target(&user_info.uuid)
+        }
+        Err(error) => {
+            counter!(
+                "auth_login_total",
+                "outcome" => "failure",
+                "error_type" => error.error_type().slug()
+            )
+            .increment(1);
+            audit
+                .event(AuditEventType::Login, AuditOutcome::Failure)
+                .target(identifier)
+                .detail(error.error_type().slug())
+        }
+    };
+    audit_strategy::record_audit_event(&mut *db.writer().await?, &event).await;
+    result
+}
+
+// Log in user by identifier and password
+async fn login_user(
+    db: &Db,
+    audit: &AuditContext,
+    payload: UserLogin,
+) -> Result<(StatusCode, HeaderMap, Json<UserInformation>), AppError> {
     // Attempt to get user from database
-    let db_result = user_strategy::get_db_user_by_identifier(payload.username).await;
-    if let Err(_) = db_result {
-        return Err(AuthError::from_type(AuthErrorType::UserNotExists));
-    }
+    let user = user_strategy::get_db_user_by_identifier(&mut *db.reader().await?, payload.username)
+        .await
+        .map_err(|error| match error {
+            AppError::NotFound(_) => AppError::Auth(AuthErrorType::UserNotExists),
+            error => error,
+        })?;
 
     // Verify user by password
-    let user = db_result.unwrap();
-    if verify(payload.password, &user.password).unwrap() {
-        // Generate authentication token from UUID
-        let user_info = UserInformation::from_user(user);
-        let auth_token: AuthToken;
-        let request_result =
-            AuthRequestClaims::new(user_info.uuid.clone()).await.unwrap().generate_token();
-        match request_result {
-            Ok(token) => auth_token = token,
-            Err(error) => {
-                println!("Error generating token from UUID {}: {:?}", user_info.uuid, error);
-                return Err(AuthError::from_type(AuthErrorType::TokenGeneration));
-            }
+    let verified = verify(payload.password, &user.password)
+        .map_err(|error| AppError::Internal(format!("Error verifying password: {}", error)))?;
+    if !verified {
+        return Err(AppError::Auth(AuthErrorType::WrongCredentials));
+    }
+
+    // Reject disabled accounts only once the password proves the caller may learn their state
+    if user.is_disabled() {
+        return Err(AppError::Auth(AuthErrorType::AccountDisabled));
+    }
+
+    // Require a current authenticator code once two-factor authentication is enabled
+    if let Some(secret) = user.totp_secret.as_deref().filter(|_| user.has_two_factor()) {
+        let code =
+            payload.two_factor_code.ok_or(AppError::Auth(AuthErrorType::TwoFactorRequired))?;
+        if !two_factor_strategy::accept_code(&mut *db.writer().await?, &user, secret, &code).await?
+        {
+            return Err(AppError::Auth(AuthErrorType::InvalidTwoFactorCode));
         }
+    }
+
+    // Record login time and create user information from updated user
+    let user = user_strategy::touch_db_user_last_login(&mut *db.writer().await?, user.uuid).await?;
+    let user_info = UserInformation::from(user);
 
-        // Insert token into header map
-        let mut header_map = HeaderMap::new();
-        header_map.insert(AUTHORIZATION, HeaderValue::from_str(&auth_token.to_string()).unwrap());
+    // Start session and generate authentication token header for it
+    let session = session_strategy::insert_db_session(
+        &mut *db.writer().await?,
+        audit,
+        &user_info.uuid,
+        session_expiry()?,
+    )
+    .await?;
+    let header_map = request_token_header(&session)?;
+
+    // Return success response
+    Ok((StatusCode::OK, header_map, Json(user_info)))
+}
+
+// Exchange authentication request token for an access token carrying the user's roles
+#[utoipa::path(
+    post,
+    path = "/token",
+    tag = "auth",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Access token issued", body = AuthToken, headers(
+            ("Authorization" = String, description = "Access token")
+        )),
+        (status = 401, description = "Invalid request token or revoked session", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "Account is disabled", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn token(
+    db: Db,
+    audit: AuditContext,
+    claims: AuthRequestClaims,
+) -> Result<(StatusCode, HeaderMap, Json<AuthToken>), AppError> {
+    // Generate access token for active session from current database user and record outcome
+    let result = async {
+        session_strategy::touch_db_session(&mut *db.writer().await?, &claims.sid, &claims.sub)
+            .await?;
+        let mut auth_claims = AuthClaims::new(&db, claims.sub.clone()).await?;
+        auth_claims.sid = Some(claims.sid.clone());
+        auth_claims.generate_token()
+    }
+    .await;
+    let outcome = match &result {
+        Ok(_) => AuditOutcome::Success,
+        Err(_) => AuditOutcome::Failure,
+    };
+    let event = audit
+        .with_actor(&claims.sub)
+        .event(AuditEventType::TokenExchange, outcome)
+        .target(&claims.sub);
+    audit_strategy::record_audit_event(&mut *db.writer().await?, &event).await;
+    let auth_token = result?;
+
+    // Insert token into header map
+    let header_value = HeaderValue::from_str(&auth_token.access_token)
+        .map_err(|_| AppError::Auth(AuthErrorType::TokenGeneration))?;
+    let mut header_map = HeaderMap::new();
+    header_map.insert(AUTHORIZATION, header_value);
+    Ok((StatusCode::OK, header_map, Json(auth_token)))
+}
 
-        // Return success response
-        Ok((StatusCode::OK, header_map, Json(user_info)))
-    } else {
-        Err(AuthError::from_type(AuthErrorType::WrongCredentials))
+// End session of request token route
+#[utoipa::path(
+    post,
+    path = "/logout",
+    tag = "auth",
+    security(("bearer" = [])),
+    responses(
+        (status = 204, description = "Session ended"),
+        (status = 401, description = "Invalid request token", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn logout(
+    db: Db,
+    audit: AuditContext,
+    claims: AuthRequestClaims,
+) -> Result<StatusCode, AppError> {
+    let audit = audit.with_actor(&claims.sub);
+    let result = session_strategy::revoke_db_session(
+        &mut *db.writer().await?,
+        &audit,
+        AuditEventType::Logout,
+        &claims.sub,
+        &claims.sid,
+    )
+    .await;
+    match result {
+        // Session already ended
+        Ok(()) | Err(AppError::NotFound(_)) => Ok(StatusCode::NO_CONTENT),
+        Err(error) => Err(error),
     }
 }
+
+// Expiry of a session started now
+fn session_expiry() -> Result<i64, AppError> {
+    Ok((get_current_timestamp() + auth_strategy::request_token_lifetime()?) as i64)
+}
+
+// Build header map with authentication request token for session
+fn request_token_header(session: &Session) -> Result<HeaderMap, AppError> {
+    // Generate authentication token for session
+    let uuid = session.user_uuid.as_str();
+    let auth_token = match AuthRequestClaims::for_session(session)?.generate_token() {
+        Ok(token) => token,
+        Err(error) => {
+            tracing::error!(uuid, ?error, "Error generating token");
+            return Err(AppError::Auth(AuthErrorType::TokenGeneration));
+        }
+    };
+
+    // Attach user to request span
+    Span::current().record("user.sub", uuid);
+
+    // Insert token into header map
+    let header_value = HeaderValue::from_str(&auth_token.to_string())
+        .map_err(|_| AppError::Auth(AuthErrorType::TokenGeneration))?;
+    let mut header_map = HeaderMap::new();
+    header_map.insert(AUTHORIZATION, header_value);
+    Ok(header_map)
+}
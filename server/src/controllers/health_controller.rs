@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use axum::Router;
+use axum::extract::State;
+use axum::routing::get;
+use http::StatusCode;
+use utoipa::OpenApi;
+
+use crate::error::AppError;
+use crate::extract::Json;
+use crate::pool::Database;
+use crate::state::AppState;
+use crate::strategies::auth_strategy;
+use crate::types::health::{HealthCheck, HealthReport, HealthStatus};
+
+#[derive(OpenApi)]
+#[openapi(paths(healthz, readyz))]
+pub struct HealthApi;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/healthz", get(healthz)).route("/readyz", get(readyz))
+}
+
+// Liveness route
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "health",
+    responses((status = 200, description = "Server is running", body = HealthReport))
+)]
+async fn healthz() -> Json<HealthReport> {
+    Json(HealthReport::from_checks(BTreeMap::new()))
+}
+
+// Readiness route
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "health",
+    responses(
+        (status = 200, description = "Server is ready to serve requests", body = HealthReport),
+        (status = 503, description = "A dependency is down", body = HealthReport),
+    )
+)]
+async fn readyz(State(db): State<Database>) -> (StatusCode, Json<HealthReport>) {
+    let mut checks = BTreeMap::new();
+
+    // Ping database through pool
+    let start = Instant::now();
+    let database = match db.ping().await {
+        Ok(()) => HealthCheck::up(Some(start.elapsed().as_millis() as u64)),
+        Err(error) => failed("database", Some(start.elapsed().as_millis() as u64), error),
+    };
+    checks.insert("database".to_string(), database);
+
+    // Check migrations are applied
+    let migrations = match db.pending_migrations().await {
+        Ok(0) => HealthCheck::up(None),
+        Ok(pending) => HealthCheck::down(None, format!("{} pending migrations", pending)),
+        Err(error) => failed("migrations", None, error),
+    };
+    checks.insert("migrations".to_string(), migrations);
+
+    // Check signing keys are loaded
+    let signing_keys = match auth_strategy::load_keys() {
+        Ok(()) => HealthCheck::up(None),
+        Err(error) => failed("signing_keys", None, error),
+    };
+    checks.insert("signing_keys".to_string(), signing_keys);
+
+    let report = HealthReport::from_checks(checks);
+    let status = match report.status {
+        HealthStatus::Up => StatusCode::OK,
+        HealthStatus::Down => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    (status, Json(report))
+}
+
+// Log failed check, keeping error details such as hosts and key paths out of the public report
+fn failed(check: &str, latency_ms: Option<u64>, error: AppError) -> HealthCheck {
+    tracing::warn!(check, %error, "Readiness check failed");
+    HealthCheck::down(latency_ms, "unavailable".to_string())
+}
@@ -0,0 +1,68 @@
+use axum::Router;
+use axum::extract::State;
+use axum::routing::get;
+use bytes::Bytes;
+use http::header::{CACHE_CONTROL, CONTENT_TYPE, X_CONTENT_TYPE_OPTIONS};
+use http::{HeaderMap, HeaderValue, StatusCode};
+use jsonwebtoken::get_current_timestamp;
+use utoipa::OpenApi;
+
+use crate::error::AppError;
+use crate::extract::{Path, Query};
+use crate::state::AppState;
+use crate::storage::SharedBlobStore;
+use crate::types::error::ProblemDetails;
+use crate::types::media::MediaSignature;
+
+#[derive(OpenApi)]
+#[openapi(paths(get_media))]
+pub struct MediaApi;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/media/*key", get(get_media))
+}
+
+// Signed media route for stores served by this server
+#[utoipa::path(
+    get,
+    path = "/media/{key}",
+    tag = "media",
+    params(("key" = String, Path, description = "Media key"), MediaSignature),
+    responses(
+        (status = 200, description = "Media contents", content(
+            (Vec<u8> = "image/webp"),
+            (Vec<u8> = "image/png"),
+        )),
+        (status = 403, description = "Invalid or expired media signature", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Media not found", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn get_media(
+    State(store): State<SharedBlobStore>,
+    Path(key): Path<String>,
+    Query(query): Query<MediaSignature>,
+) -> Result<(HeaderMap, Bytes), AppError> {
+    if !store.verify_signature(&key, query.expires, &query.signature) {
+        return Err(AppError::Rejected(
+            StatusCode::FORBIDDEN,
+            "Invalid or expired media signature".to_string(),
+        ));
+    }
+    let blob = store.get(&key).await?;
+
+    // Cache privately until the signature expires
+    let max_age = query.expires.saturating_sub(get_current_timestamp());
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_str(&blob.content_type)
+            .unwrap_or(HeaderValue::from_static("application/octet-stream")),
+    );
+    headers.insert(X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+    headers.insert(
+        CACHE_CONTROL,
+        HeaderValue::from_str(&format!("private, max-age={}", max_age))
+            .map_err(|error| AppError::Internal(error.to_string()))?,
+    );
+    Ok((headers, blob.data))
+}
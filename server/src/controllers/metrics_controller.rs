@@ -0,0 +1,18 @@
+use axum::Router;
+use axum::extract::State;
+use axum::routing::get;
+use metrics_exporter_prometheus::PrometheusHandle;
+
+use crate::pool::Databases;
+use crate::state::AppState;
+
+pub fn routes(handle: PrometheusHandle) -> Router<AppState> {
+    Router::new().route(
+        "/metrics",
+        get(move |State(db): State<Databases>| async move {
+            // Refresh gauges before rendering
+            db.record_metrics();
+            handle.render()
+        }),
+    )
+}
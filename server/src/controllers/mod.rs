@@ -1 +1,8 @@
+pub mod account_controller;
+pub mod audit_controller;
 pub mod auth_controller;
+pub mod health_controller;
+pub mod media_controller;
+pub mod metrics_controller;
+pub mod openapi_controller;
+pub mod user_controller;
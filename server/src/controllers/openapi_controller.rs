@@ -0,0 +1,14 @@
+use axum::Router;
+use axum::routing::get;
+use utoipa::OpenApi;
+use utoipa_redoc::{Redoc, Servable};
+
+use crate::extract::Json;
+use crate::openapi::ApiDoc;
+use crate::state::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/openapi.json", get(|| async { Json(ApiDoc::openapi()) }))
+        .merge(Redoc::with_url("/docs", ApiDoc::openapi()))
+}
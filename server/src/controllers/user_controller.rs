@@ -0,0 +1,517 @@
+use axum::Router;
+use axum::extract::{DefaultBodyLimit, OriginalUri, State};
+use axum::routing::{get, post, put};
+use bytes::{Bytes, BytesMut};
+use http::header::CONTENT_DISPOSITION;
+use http::{HeaderMap, HeaderValue, StatusCode};
+use jsonwebtoken::get_current_timestamp;
+use utoipa::OpenApi;
+
+use crate::error::AppError;
+use crate::extract::{Json, Multipart, Path, ValidatedJson, ValidatedQuery};
+use crate::middleware::transaction::Tx;
+use crate::pool::Db;
+use crate::state::AppState;
+use crate::storage::{BlobStore, SharedBlobStore};
+use crate::strategies::audit_strategy::{self, AuditContext};
+use crate::strategies::auth_strategy::{AdminClaims, AuthClaims};
+use crate::strategies::{avatar_strategy, session_strategy, user_strategy};
+use crate::types::audit::{AuditEventType, AuditOutcome};
+use crate::types::error::ProblemDetails;
+use crate::types::media::{AVATAR_CONTENT_TYPES, AvatarUpload, AvatarUrls};
+use crate::types::pagination::{DEFAULT_PAGE_LIMIT, Page, neighbour_offsets, page_link};
+use crate::types::session::SessionExport;
+use crate::types::user::{
+    UserCursor, UserDataExport, UserInformation, UserListQuery, UserProfileUpdate, UserRoleUpdate,
+};
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    list_users,
+    get_me,
+    update_me,
+    delete_me,
+    get_my_avatar,
+    upload_avatar,
+    delete_avatar,
+    export_me,
+    get_user,
+    get_avatar,
+    delete_user,
+    set_user_role,
+    disable_user,
+    enable_user
+))]
+pub struct UserApi;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_users))
+        .route("/me", get(get_me).patch(update_me).delete(delete_me))
+        .route(
+            "/me/avatar",
+            get(get_my_avatar)
+                .put(upload_avatar)
+                .delete(delete_avatar)
+                // Leave room for multipart framing around the image
+                .layer(DefaultBodyLimit::max(avatar_strategy::max_upload_bytes() + 64 * 1024)),
+        )
+        .route("/me/export", get(export_me))
+        .route("/:uuid", get(get_user).delete(delete_user))
+        .route("/:uuid/avatar", get(get_avatar))
+        .route("/:uuid/disable", post(disable_user))
+        .route("/:uuid/enable", post(enable_user))
+        .route("/:uuid/role", put(set_user_role))
+}
+
+// User listing route
+#[utoipa::path(
+    get,
+    path = "",
+    tag = "users",
+    security(("bearer" = [])),
+    params(UserListQuery),
+    responses(
+        (status = 200, description = "Page of users", body = Page<UserInformation>),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "Insufficient permissions", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid input", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn list_users(
+    _admin: AdminClaims,
+    db: Db,
+    OriginalUri(uri): OriginalUri,
+    ValidatedQuery(query): ValidatedQuery<UserListQuery>,
+) -> Result<Json<Page<UserInformation>>, AppError> {
+    // Get page of users from database
+    let page = user_strategy::get_db_user_page(&mut *db.reader().await?, &query).await?;
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+
+    // Link neighbouring pages by offset or by cursor, matching the request
+    let (next, prev) = match query.offset {
+        Some(offset) => {
+            let offset_link = |offset: u64| {
+                page_link(uri.path(), &UserListQuery { offset: Some(offset), ..query.clone() })
+            };
+            let (next, prev) = neighbour_offsets(offset, limit, page.total);
+            (next.map(offset_link), prev.map(offset_link))
+        }
+        None => {
+            let cursor_link = |cursor: UserCursor| {
+                page_link(
+                    uri.path(),
+                    &UserListQuery { cursor: Some(cursor.encode()), ..query.clone() },
+                )
+            };
+            (page.next_cursor.map(cursor_link), page.prev_cursor.map(cursor_link))
+        }
+    };
+
+    Ok(Json(Page {
+        items: page.users,
+        total: page.total,
+        limit,
+        offset: query.offset,
+        next: next.transpose()?,
+        prev: prev.transpose()?,
+    }))
+}
+
+// Own profile route
+#[utoipa::path(
+    get,
+    path = "/me",
+    tag = "users",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Own profile", body = UserInformation),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn get_me(claims: AuthClaims, db: Db) -> Result<Json<UserInformation>, AppError> {
+    let user = user_strategy::get_db_user_by_uuid(&mut *db.reader().await?, claims.sub).await?;
+    Ok(Json(UserInformation::from(user)))
+}
+
+// Update own profile route
+#[utoipa::path(
+    patch,
+    path = "/me",
+    tag = "users",
+    security(("bearer" = [])),
+    request_body(content = UserProfileUpdate, description = "Fields to change, null clears a field"),
+    responses(
+        (status = 200, description = "Updated profile", body = UserInformation),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid input", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn update_me(
+    claims: AuthClaims,
+    mut tx: Tx,
+    ValidatedJson(payload): ValidatedJson<UserProfileUpdate>,
+) -> Result<Json<UserInformation>, AppError> {
+    let user = user_strategy::update_db_user_profile(&mut tx, claims.sub, payload).await?;
+    Ok(Json(UserInformation::from(user)))
+}
+
+// Own avatar URLs route
+#[utoipa::path(
+    get,
+    path = "/me/avatar",
+    tag = "users",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Signed avatar URLs by size", body = AvatarUrls),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Avatar not found", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn get_my_avatar(
+    claims: AuthClaims,
+    db: Db,
+    State(store): State<SharedBlobStore>,
+) -> Result<Json<AvatarUrls>, AppError> {
+    avatar_urls(&db, store.as_ref(), claims.sub).await
+}
+
+// User avatar URLs route
+#[utoipa::path(
+    get,
+    path = "/{uuid}/avatar",
+    tag = "users",
+    security(("bearer" = [])),
+    params(("uuid" = String, Path, description = "User UUID")),
+    responses(
+        (status = 200, description = "Signed avatar URLs by size", body = AvatarUrls),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Avatar not found", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn get_avatar(
+    _claims: AuthClaims,
+    db: Db,
+    State(store): State<SharedBlobStore>,
+    Path(uuid): Path<String>,
+) -> Result<Json<AvatarUrls>, AppError> {
+    avatar_urls(&db, store.as_ref(), uuid).await
+}
+
+// Signed URLs of user's uploaded avatar
+async fn avatar_urls(
+    db: &Db,
+    store: &dyn BlobStore,
+    uuid: String,
+) -> Result<Json<AvatarUrls>, AppError> {
+    let user = user_strategy::get_db_user_by_uuid(&mut *db.reader().await?, uuid).await?;
+    let avatar_key = user.avatar_key.ok_or(AppError::NotFound("Avatar not found".to_string()))?;
+    Ok(Json(avatar_strategy::avatar_urls(store, &avatar_key)?))
+}
+
+// Avatar upload route, taking the image from the multipart "avatar" field
+#[utoipa::path(
+    put,
+    path = "/me/avatar",
+    tag = "users",
+    security(("bearer" = [])),
+    request_body(content = AvatarUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Signed URLs of the new avatar by size", body = AvatarUrls),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 413, description = "Avatar is too large", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 415, description = "Avatar is not a JPEG, PNG or WebP image", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Avatar could not be decoded", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn upload_avatar(
+    claims: AuthClaims,
+    db: Db,
+    State(store): State<SharedBlobStore>,
+    Multipart(mut multipart): Multipart,
+) -> Result<Json<AvatarUrls>, AppError> {
+    let data = read_avatar_field(&mut multipart).await?;
+    let user = user_strategy::get_db_user_by_uuid(&mut *db.writer().await?, claims.sub).await?;
+
+    // Store thumbnails before pointing the user at them, then drop the previous avatar
+    let avatar_key = avatar_strategy::store_avatar(store.as_ref(), &user.uuid, data).await?;
+    let result = user_strategy::set_db_user_avatar_key(
+        &mut *db.writer().await?,
+        user.uuid,
+        Some(avatar_key.clone()),
+    )
+    .await;
+    if let Err(error) = result {
+        avatar_strategy::delete_avatar(store.as_ref(), &avatar_key).await;
+        return Err(error);
+    }
+    if let Some(previous_key) = user.avatar_key {
+        avatar_strategy::delete_avatar(store.as_ref(), &previous_key).await;
+    }
+    Ok(Json(avatar_strategy::avatar_urls(store.as_ref(), &avatar_key)?))
+}
+
+// Read avatar field, enforcing the content type allowlist and size limit
+async fn read_avatar_field(multipart: &mut axum::extract::Multipart) -> Result<Bytes, AppError> {
+    let max_bytes = avatar_strategy::max_upload_bytes();
+    while let Some(mut field) = multipart.next_field().await? {
+        if field.name() != Some("avatar") {
+            continue;
+        }
+        let content_type = field.content_type().unwrap_or_default();
+        if !AVATAR_CONTENT_TYPES.contains(&content_type) {
+            return Err(AppError::Rejected(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!("Avatar content type must be one of {}", AVATAR_CONTENT_TYPES.join(", ")),
+            ));
+        }
+
+        let mut data = BytesMut::new();
+        while let Some(chunk) = field.chunk().await? {
+            if data.len() + chunk.len() > max_bytes {
+                return Err(AppError::Rejected(
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    format!("Avatar must be at most {} bytes", max_bytes),
+                ));
+            }
+            data.extend_from_slice(&chunk);
+        }
+        return Ok(data.freeze());
+    }
+    Err(AppError::Rejected(StatusCode::BAD_REQUEST, "Missing avatar field".to_string()))
+}
+
+// Remove own avatar route
+#[utoipa::path(
+    delete,
+    path = "/me/avatar",
+    tag = "users",
+    security(("bearer" = [])),
+    responses(
+        (status = 204, description = "Avatar removed"),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Avatar not found", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn delete_avatar(
+    claims: AuthClaims,
+    db: Db,
+    State(store): State<SharedBlobStore>,
+) -> Result<StatusCode, AppError> {
+    let user = user_strategy::get_db_user_by_uuid(&mut *db.writer().await?, claims.sub).await?;
+    let avatar_key = user.avatar_key.ok_or(AppError::NotFound("Avatar not found".to_string()))?;
+    user_strategy::set_db_user_avatar_key(&mut *db.writer().await?, user.uuid, None).await?;
+    avatar_strategy::delete_avatar(store.as_ref(), &avatar_key).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Delete own account route
+#[utoipa::path(
+    delete,
+    path = "/me",
+    tag = "users",
+    security(("bearer" = [])),
+    responses(
+        (status = 204, description = "Account deleted"),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn delete_me(
+    claims: AuthClaims,
+    mut tx: Tx,
+    audit: AuditContext,
+) -> Result<StatusCode, AppError> {
+    let audit = audit.with_actor(&claims.sub);
+    user_strategy::delete_db_user_by_uuid(&mut tx, &audit, claims.sub).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Personal data export route
+#[utoipa::path(
+    get,
+    path = "/me/export",
+    tag = "users",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Everything stored about the user", body = UserDataExport),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn export_me(
+    claims: AuthClaims,
+    mut tx: Tx,
+    audit: AuditContext,
+) -> Result<(HeaderMap, Json<UserDataExport>), AppError> {
+    // Collect everything stored about the user
+    let user = user_strategy::get_db_user_by_uuid(&mut *tx, claims.sub.clone()).await?;
+    let sessions = session_strategy::get_db_stored_user_sessions(&mut *tx, &user.uuid).await?;
+    let audit_events = audit_strategy::get_db_audit_events_for_user(&mut *tx, &user).await?;
+
+    // Record export
+    let event = audit
+        .with_actor(&claims.sub)
+        .event(AuditEventType::DataExport, AuditOutcome::Success)
+        .target(&claims.sub);
+    audit_strategy::insert_audit_event(&mut *tx, &event).await?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        CONTENT_DISPOSITION,
+        HeaderValue::from_static("attachment; filename=\"user_data.json\""),
+    );
+    Ok((
+        headers,
+        Json(UserDataExport {
+            exported_at: get_current_timestamp() as i64,
+            profile: UserInformation::from(user),
+            sessions: sessions.into_iter().map(SessionExport::from).collect(),
+            audit_events,
+        }),
+    ))
+}
+
+// User route
+#[utoipa::path(
+    get,
+    path = "/{uuid}",
+    tag = "users",
+    security(("bearer" = [])),
+    params(("uuid" = String, Path, description = "User UUID")),
+    responses(
+        (status = 200, description = "User", body = UserInformation),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "Insufficient permissions", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "User not found", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn get_user(
+    _admin: AdminClaims,
+    db: Db,
+    Path(uuid): Path<String>,
+) -> Result<Json<UserInformation>, AppError> {
+    let user = user_strategy::get_db_user_by_uuid(&mut *db.reader().await?, uuid).await.map_err(
+        |error| match error {
+            AppError::NotFound(_) => AppError::NotFound("User not found".to_string()),
+            error => error,
+        },
+    )?;
+    Ok(Json(UserInformation::from(user)))
+}
+
+// Delete user route
+#[utoipa::path(
+    delete,
+    path = "/{uuid}",
+    tag = "users",
+    security(("bearer" = [])),
+    params(("uuid" = String, Path, description = "User UUID")),
+    responses(
+        (status = 204, description = "User deleted"),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "Insufficient permissions", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "User not found", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn delete_user(
+    AdminClaims(claims): AdminClaims,
+    mut tx: Tx,
+    audit: AuditContext,
+    Path(uuid): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let audit = audit.with_actor(&claims.sub);
+    user_strategy::delete_db_user_by_uuid(&mut tx, &audit, uuid).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Disable user route
+#[utoipa::path(
+    post,
+    path = "/{uuid}/disable",
+    tag = "users",
+    security(("bearer" = [])),
+    params(("uuid" = String, Path, description = "User UUID")),
+    responses(
+        (status = 204, description = "User disabled"),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "Insufficient permissions", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "User not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Cannot disable your own account", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn disable_user(
+    AdminClaims(claims): AdminClaims,
+    mut tx: Tx,
+    audit: AuditContext,
+    Path(uuid): Path<String>,
+) -> Result<StatusCode, AppError> {
+    // Admins cannot lock themselves out
+    if uuid == claims.sub {
+        return Err(AppError::Rejected(
+            StatusCode::CONFLICT,
+            "Cannot disable your own account".to_string(),
+        ));
+    }
+    let audit = audit.with_actor(&claims.sub);
+    user_strategy::set_db_user_disabled(&mut tx, &audit, uuid, true).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Enable user route
+#[utoipa::path(
+    post,
+    path = "/{uuid}/enable",
+    tag = "users",
+    security(("bearer" = [])),
+    params(("uuid" = String, Path, description = "User UUID")),
+    responses(
+        (status = 204, description = "User enabled"),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "Insufficient permissions", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "User not found", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn enable_user(
+    AdminClaims(claims): AdminClaims,
+    mut tx: Tx,
+    audit: AuditContext,
+    Path(uuid): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let audit = audit.with_actor(&claims.sub);
+    user_strategy::set_db_user_disabled(&mut tx, &audit, uuid, false).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Set user role route
+#[utoipa::path(
+    put,
+    path = "/{uuid}/role",
+    tag = "users",
+    security(("bearer" = [])),
+    params(("uuid" = String, Path, description = "User UUID")),
+    request_body = UserRoleUpdate,
+    responses(
+        (status = 200, description = "Updated user", body = UserInformation),
+        (status = 401, description = "Invalid token", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "Insufficient permissions", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "User not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Cannot remove your own admin role", body = ProblemDetails, content_type = "application/problem+json"),
+    )
+)]
+async fn set_user_role(
+    AdminClaims(claims): AdminClaims,
+    mut tx: Tx,
+    audit: AuditContext,
+    Path(uuid): Path<String>,
+    Json(payload): Json<UserRoleUpdate>,
+) -> Result<Json<UserInformation>, AppError> {
+    // Admins cannot lock themselves out
+    if uuid == claims.sub && !payload.is_admin {
+        return Err(AppError::Rejected(
+            StatusCode::CONFLICT,
+            "Cannot remove your own admin role".to_string(),
+        ));
+    }
+    let audit = audit.with_actor(&claims.sub);
+    let user = user_strategy::set_db_user_admin(&mut tx, &audit, uuid, payload.is_admin).await?;
+    Ok(Json(UserInformation::from(user)))
+}
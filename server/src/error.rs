@@ -0,0 +1,173 @@
+use std::fmt;
+
+use axum::Json;
+use axum::body::Body;
+use axum::extract::multipart::{MultipartError, MultipartRejection};
+use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+use axum::response::{IntoResponse, Response};
+use http::StatusCode;
+use http::header::CONTENT_TYPE;
+use sqlx::error::ErrorKind;
+use validator::ValidationErrors;
+
+use crate::middleware::request_context;
+use crate::types::auth::AuthErrorType;
+use crate::types::error::{PROBLEM_JSON, ProblemDetails};
+use crate::types::validation::field_errors;
+
+// Application error
+#[derive(Debug)]
+pub enum AppError {
+    Auth(AuthErrorType),
+    Validation(ValidationErrors),
+    Rejected(StatusCode, String),
+    NotFound(String),
+    Conflict(String),
+    Unavailable(String),
+    Internal(String),
+}
+
+impl AppError {
+    pub fn error_type(&self) -> AuthErrorType {
+        match self {
+            AppError::Auth(error_type) => error_type.to_owned(),
+            AppError::Validation(_) => AuthErrorType::InvalidInput,
+            AppError::Rejected(_, _) => AuthErrorType::BadRequest,
+            AppError::NotFound(_) => AuthErrorType::NotFound,
+            AppError::Conflict(_) => AuthErrorType::Conflict,
+            AppError::Unavailable(_) => AuthErrorType::ServiceUnavailable,
+            AppError::Internal(_) => AuthErrorType::ServerError,
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::Rejected(status, _) => status.to_owned(),
+            _ => self.error_type().status(),
+        }
+    }
+
+    // Detail safe to return to clients
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            AppError::Rejected(_, detail)
+            | AppError::NotFound(detail)
+            | AppError::Conflict(detail) => Some(detail.to_owned()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AppError::Auth(error_type) => write!(f, "{}", error_type.message()),
+            AppError::Validation(errors) => write!(f, "{}", errors),
+            AppError::Rejected(_, detail)
+            | AppError::NotFound(detail)
+            | AppError::Conflict(detail)
+            | AppError::Unavailable(detail)
+            | AppError::Internal(detail) => write!(f, "{}", detail),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<AuthErrorType> for AppError {
+    fn from(error_type: AuthErrorType) -> Self {
+        AppError::Auth(error_type)
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        AppError::Validation(errors)
+    }
+}
+
+impl From<JsonRejection> for AppError {
+    fn from(rejection: JsonRejection) -> Self {
+        AppError::Rejected(rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<PathRejection> for AppError {
+    fn from(rejection: PathRejection) -> Self {
+        AppError::Rejected(rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for AppError {
+    fn from(rejection: QueryRejection) -> Self {
+        AppError::Rejected(rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<MultipartRejection> for AppError {
+    fn from(rejection: MultipartRejection) -> Self {
+        AppError::Rejected(rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<MultipartError> for AppError {
+    fn from(error: MultipartError) -> Self {
+        AppError::Rejected(error.status(), error.body_text())
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        match &error {
+            sqlx::Error::RowNotFound => AppError::NotFound("Resource not found".to_string()),
+            sqlx::Error::Database(database_error) => match database_error.kind() {
+                ErrorKind::UniqueViolation => {
+                    AppError::Conflict("Resource already exists".to_string())
+                }
+                ErrorKind::ForeignKeyViolation => {
+                    AppError::Conflict("Resource is referenced by another resource".to_string())
+                }
+                ErrorKind::NotNullViolation | ErrorKind::CheckViolation => AppError::Rejected(
+                    StatusCode::BAD_REQUEST,
+                    "Resource violates a database constraint".to_string(),
+                ),
+                _ => AppError::Internal(format!("Database error: {}", error)),
+            },
+            sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::Io(_)
+            | sqlx::Error::Tls(_) => {
+                AppError::Unavailable(format!("Database unavailable: {}", error))
+            }
+            _ => AppError::Internal(format!("Database error: {}", error)),
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response<Body> {
+        // Log errors whose details are not returned to clients
+        if let AppError::Unavailable(detail) | AppError::Internal(detail) = &self {
+            tracing::error!(error_type = ?self.error_type(), detail, "Request failed");
+        }
+
+        // Build problem details
+        let status = self.status();
+        let mut problem = ProblemDetails::from_type(self.error_type(), self.detail());
+        problem.status = status.as_u16();
+        if let AppError::Validation(errors) = &self {
+            problem.fields = field_errors(errors);
+        }
+        if let Some(context) = request_context::current() {
+            problem.instance = Some(context.path);
+            problem.request_id = context.request_id;
+        }
+
+        (status, [(CONTENT_TYPE, PROBLEM_JSON)], Json(problem)).into_response()
+    }
+}
+
+// Fallback for unknown routes
+pub async fn not_found() -> AppError {
+    AppError::NotFound("Route not found".to_string())
+}
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use jsonwebtoken::get_current_timestamp;
+
+use crate::config::env_parse_opt;
+use crate::pool::Database;
+use crate::storage::SharedBlobStore;
+use crate::strategies::{avatar_strategy, session_strategy, user_strategy};
+
+// Interval between purges of soft deleted users
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+// Days soft deleted users are kept before purging when USER_RETENTION_DAYS is unset
+const DEFAULT_RETENTION_DAYS: u64 = 30;
+
+// Permanently delete users soft deleted longer than USER_RETENTION_DAYS ago, once per interval
+pub async fn purge_deleted_users(db: Database, store: SharedBlobStore) {
+    let retention_days = match env_parse_opt::<u64>("USER_RETENTION_DAYS") {
+        Ok(days) => days.unwrap_or(DEFAULT_RETENTION_DAYS),
+        Err(error) => {
+            tracing::error!(%error, "Invalid user retention, using default");
+            DEFAULT_RETENTION_DAYS
+        }
+    };
+    let retention = Duration::from_secs(retention_days * 24 * 60 * 60);
+
+    loop {
+        tokio::time::sleep(PURGE_INTERVAL).await;
+
+        let cutoff = get_current_timestamp().saturating_sub(retention.as_secs()) as i64;
+        let result = async {
+            let mut connection = db.acquire().await?;
+            user_strategy::purge_db_users_deleted_before(&mut connection, cutoff).await
+        }
+        .await;
+        match result {
+            Ok(purged) if purged.is_empty() => {}
+            Ok(purged) => {
+                tracing::info!(count = purged.len(), "Purged deleted users");
+
+                // Remove uploaded avatars of purged users
+                for avatar_key in purged.iter().filter_map(|(_, avatar_key)| avatar_key.as_ref()) {
+                    avatar_strategy::delete_avatar(store.as_ref(), avatar_key).await;
+                }
+            }
+            Err(error) => tracing::error!(%error, "Error purging deleted users"),
+        }
+    }
+}
+
+// Delete expired and revoked sessions, once per interval
+pub async fn purge_ended_sessions(db: Database) {
+    loop {
+        tokio::time::sleep(PURGE_INTERVAL).await;
+
+        let cutoff = get_current_timestamp() as i64;
+        let result = async {
+            let mut connection = db.acquire().await?;
+            session_strategy::purge_db_sessions_before(&mut *connection, cutoff).await
+        }
+        .await;
+        match result {
+            Ok(0) => {}
+            Ok(purged) => tracing::info!(count = purged, "Purged ended sessions"),
+            Err(error) => tracing::error!(%error, "Error purging ended sessions"),
+        }
+    }
+}
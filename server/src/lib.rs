@@ -0,0 +1,15 @@
+pub mod config;
+pub mod controllers;
+pub mod error;
+pub mod extract;
+pub mod jobs;
+pub mod middleware;
+pub mod openapi;
+pub mod pool;
+pub mod query;
+pub mod shutdown;
+pub mod state;
+pub mod storage;
+pub mod strategies;
+pub mod telemetry;
+pub mod types;
@@ -1,46 +1,126 @@
-mod controllers;
-mod pool;
-mod strategies;
-mod types;
-
+use std::future::IntoFuture;
 use std::net::SocketAddr;
-use std::path::PathBuf;
 
 use axum::Router;
-use controllers::auth_controller;
+use axum::middleware::from_fn;
 use http::header::{AUTHORIZATION, CONTENT_TYPE};
+use rustenv_server::controllers::{
+    account_controller, audit_controller, auth_controller, health_controller, media_controller,
+    metrics_controller, openapi_controller, user_controller,
+};
+use rustenv_server::middleware::{
+    catch_panic, request_context, request_metrics, trace, transaction,
+};
+use rustenv_server::pool::Databases;
+use rustenv_server::state::AppState;
+use rustenv_server::strategies::auth_strategy;
+use rustenv_server::{error, jobs, pool, shutdown, storage, telemetry};
 use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
 use tower::ServiceBuilder;
+use tower_http::catch_panic::CatchPanicLayer;
 use tower_http::cors::{Any, CorsLayer};
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
 
 #[tokio::main]
 async fn main() {
-    if cfg!(debug_assertions) {
-        match dotenv::dotenv() {
-            Ok(path) => path,
-            Err(e) => {
-                println!("dotenv failed: {:?}", e);
-                PathBuf::from("")
+    let dotenv_result = if cfg!(debug_assertions) { Some(dotenv::dotenv()) } else { None };
+
+    let _telemetry = telemetry::init_telemetry("rustenv_server");
+
+    if let Some(Err(e)) = dotenv_result {
+        tracing::warn!("dotenv failed: {:?}", e);
+    }
+
+    if let Err(error) = auth_strategy::load_keys() {
+        tracing::error!(%error, "Error loading authentication keys");
+    }
+
+    // Connect to database, either before serving or in the background
+    let db = Databases::from_env()
+        .unwrap_or_else(|error| panic!("Error configuring database pools: {}", error));
+    if pool::wait_for_pool() {
+        if let Err(error) = db.connect().await {
+            panic!("Error creating database pool: {}", error);
+        }
+    } else {
+        let db = db.clone();
+        tokio::spawn(async move {
+            if let Err(error) = db.connect().await {
+                tracing::error!(%error, "Error creating database pool");
             }
-        };
+        });
     }
 
+    // Route reads back to the replica once it recovers
+    let probe = db.clone();
+    tokio::spawn(async move { probe.probe_replica().await });
+
+    let media = storage::from_env()
+        .unwrap_or_else(|error| panic!("Error configuring media store: {}", error));
+
+    // Purge soft deleted users after the retention period
+    tokio::spawn(jobs::purge_deleted_users(db.primary.clone(), media.clone()));
+
+    // Remove ended sessions
+    tokio::spawn(jobs::purge_ended_sessions(db.primary.clone()));
+
+    let metrics_handle =
+        request_metrics::install_recorder().expect("Error installing metrics recorder");
+
     let cors = CorsLayer::permissive()
         .allow_origin(Any)
         .allow_headers([AUTHORIZATION, CONTENT_TYPE])
         .expose_headers(Any);
 
     let app = Router::new()
+        .nest("/audit", audit_controller::routes())
         .nest("/auth", auth_controller::routes())
-        .layer(ServiceBuilder::new().layer(cors));
+        .nest("/users", user_controller::routes().merge(account_controller::routes()))
+        .merge(health_controller::routes())
+        .merge(media_controller::routes())
+        .merge(metrics_controller::routes(metrics_handle))
+        .merge(openapi_controller::routes())
+        .fallback(error::not_found)
+        .with_state(AppState { db: db.clone(), media })
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+                .layer(PropagateRequestIdLayer::x_request_id())
+                .layer(trace::trace_layer())
+                .layer(from_fn(request_metrics::track_metrics))
+                .layer(cors)
+                .layer(from_fn(request_context::scope_request_context))
+                .layer(from_fn(transaction::transaction_scope))
+                .layer(CatchPanicLayer::custom(catch_panic::panic_response)),
+        );
 
     let addr = SocketAddr::from(([127, 0, 0, 1], 3001));
-    println!("Server listening on http://{}", addr);
+    tracing::info!("Server listening on http://{}", addr);
 
     let listener = TcpListener::bind(addr).await.unwrap();
 
-    match axum::serve(listener, app).await {
-        Ok(_) => {}
-        Err(e) => panic!("Server failed to start on http://{}: {:?}", addr, e),
+    // Serve until SIGINT/SIGTERM, then give in-flight requests time to drain
+    let shutdown = CancellationToken::new();
+    let signal = shutdown.clone();
+    let server = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(async move {
+            shutdown::shutdown_signal().await;
+            signal.cancel();
+        });
+    let drain_timeout = shutdown::drain_timeout();
+    let drain = async {
+        shutdown.cancelled().await;
+        tokio::time::sleep(drain_timeout).await;
+    };
+
+    tokio::select! {
+        result = server.into_future() => match result {
+            Ok(_) => tracing::info!("Server stopped"),
+            Err(e) => panic!("Server failed to start on http://{}: {:?}", addr, e),
+        },
+        _ = drain => tracing::warn!("Drain timeout elapsed, dropping remaining connections"),
     }
+
+    db.close(drain_timeout).await;
 }
@@ -0,0 +1,18 @@
+use std::any::Any;
+
+use axum::response::{IntoResponse, Response};
+
+use crate::error::AppError;
+
+// Turn a handler panic into a problem details response
+pub fn panic_response(panic: Box<dyn Any + Send + 'static>) -> Response {
+    let message = if let Some(message) = panic.downcast_ref::<String>() {
+        message.to_owned()
+    } else if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else {
+        "Unknown panic payload".to_string()
+    };
+
+    AppError::Internal(format!("Handler panicked: {}", message)).into_response()
+}
@@ -0,0 +1,5 @@
+pub mod catch_panic;
+pub mod request_context;
+pub mod request_metrics;
+pub mod trace;
+pub mod transaction;
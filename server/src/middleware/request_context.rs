@@ -0,0 +1,32 @@
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use tower_http::request_id::RequestId;
+
+tokio::task_local! {
+    // Context of the request currently being handled
+    static REQUEST_CONTEXT: RequestContext;
+}
+
+#[derive(Clone, Debug)]
+pub struct RequestContext {
+    pub request_id: Option<String>,
+    pub path: String,
+}
+
+// Get context of the request currently being handled
+pub fn current() -> Option<RequestContext> {
+    REQUEST_CONTEXT.try_with(|context| context.clone()).ok()
+}
+
+// Make request context available to everything running inside the handler
+pub async fn scope_request_context(request: Request, next: Next) -> Response {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .and_then(|request_id| request_id.header_value().to_str().ok())
+        .map(str::to_owned);
+    let context = RequestContext { request_id, path: request.uri().path().to_string() };
+
+    REQUEST_CONTEXT.scope(context, next.run(request)).await
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use metrics::{Unit, describe_counter, describe_gauge, describe_histogram};
+use metrics_exporter_prometheus::{BuildError, Matcher, PrometheusBuilder, PrometheusHandle};
+use rustenv_service::SECONDS_BUCKETS;
+pub use rustenv_service::track_metrics;
+
+// Interval between recorder upkeep runs
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+// Install Prometheus recorder and describe application metrics
+pub fn install_recorder() -> Result<PrometheusHandle, BuildError> {
+    let handle = PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), SECONDS_BUCKETS)?
+        .install_recorder()?;
+
+    // Drain histogram data periodically
+    let upkeep_handle = handle.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(UPKEEP_INTERVAL).await;
+            upkeep_handle.run_upkeep();
+        }
+    });
+
+    describe_counter!("http_requests_total", "HTTP requests by method, route and status");
+    describe_histogram!(
+        "http_request_duration_seconds",
+        Unit::Seconds,
+        "HTTP request latency by method, route and status"
+    );
+    describe_counter!("auth_login_total", "Login attempts by outcome and error type");
+    describe_counter!("auth_tokens_issued_total", "Authentication tokens issued by token type");
+    describe_gauge!("db_pool_connections", "Open database pool connections");
+    describe_gauge!("db_pool_idle_connections", "Idle database pool connections");
+    describe_gauge!("db_pool_max_connections", "Maximum database pool connections");
+    describe_gauge!("db_replica_available", "Whether reads are routed to the read replica");
+    describe_histogram!(
+        "db_pool_acquire_wait_seconds",
+        Unit::Seconds,
+        "Time spent waiting to acquire a database pool connection"
+    );
+
+    Ok(handle)
+}
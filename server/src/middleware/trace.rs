@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use axum::extract::{MatchedPath, Request};
+use axum::response::Response;
+use tower_http::classify::{ServerErrorsAsFailures, SharedClassifier};
+use tower_http::trace::{DefaultOnRequest, TraceLayer};
+use tracing::Span;
+use tracing::field::Empty;
+
+type MakeSpanFn = fn(&Request) -> Span;
+type OnResponseFn = fn(&Response, Duration, &Span);
+
+// Build request tracing layer
+pub fn trace_layer()
+-> TraceLayer<SharedClassifier<ServerErrorsAsFailures>, MakeSpanFn, DefaultOnRequest, OnResponseFn>
+{
+    TraceLayer::new_for_http()
+        .make_span_with(make_span as MakeSpanFn)
+        .on_response(on_response as OnResponseFn)
+}
+
+// Create span for request
+fn make_span(request: &Request) -> Span {
+    let route = match request.extensions().get::<MatchedPath>() {
+        Some(path) => path.as_str(),
+        None => "",
+    };
+    let request_id = match request.headers().get("x-request-id") {
+        Some(value) => value.to_str().unwrap_or_default(),
+        None => "",
+    };
+
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        route,
+        request_id,
+        status = Empty,
+        latency_ms = Empty,
+        user.sub = Empty,
+    )
+}
+
+// Record response status and latency on request span
+fn on_response(response: &Response, latency: Duration, span: &Span) {
+    span.record("status", response.status().as_u16());
+    span.record("latency_ms", latency.as_millis() as u64);
+    tracing::info!(
+        status = response.status().as_u16(),
+        latency_ms = latency.as_millis() as u64,
+        "Request completed"
+    );
+}
@@ -0,0 +1,96 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use axum::extract::{FromRef, FromRequestParts, Request};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::{RequestPartsExt, async_trait};
+use http::request::Parts;
+use sqlx::any::Any;
+use sqlx::{AnyConnection, Transaction};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use crate::error::AppError;
+use crate::pool::{Databases, Db};
+
+// Transaction shared between the request extractor and the middleware finishing it
+#[derive(Clone, Default)]
+struct TransactionSlot(Arc<Mutex<Option<Transaction<'static, Any>>>>);
+
+// Commit the request transaction on success responses and roll it back on error responses
+pub async fn transaction_scope(mut request: Request, next: Next) -> Response {
+    let slot = TransactionSlot::default();
+    request.extensions_mut().insert(slot.clone());
+
+    let response = next.run(request).await;
+
+    // Transaction is only begun when a handler extracts Tx
+    let Some(transaction) = slot.0.lock().await.take() else {
+        return response;
+    };
+    if response.status().is_client_error() || response.status().is_server_error() {
+        if let Err(error) = transaction.rollback().await {
+            tracing::error!(%error, "Error rolling back request transaction");
+        }
+        return response;
+    }
+    match transaction.commit().await {
+        Ok(()) => response,
+        Err(error) => AppError::from(error).into_response(),
+    }
+}
+
+// Request-scoped transaction on the primary, begun on first extraction
+pub struct Tx(OwnedMutexGuard<Option<Transaction<'static, Any>>>);
+
+impl Tx {
+    // Roll back the transaction before the response is sent, releasing its locks for other writes
+    pub async fn rollback(mut self) -> Result<(), AppError> {
+        if let Some(transaction) = self.0.take() {
+            transaction.rollback().await?;
+        }
+        Ok(())
+    }
+}
+
+impl Deref for Tx {
+    type Target = AnyConnection;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_deref().expect("Request transaction is open while Tx is held")
+    }
+}
+
+impl DerefMut for Tx {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0.as_deref_mut().expect("Request transaction is open while Tx is held")
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Tx
+where
+    Databases: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let slot = parts.extensions.get::<TransactionSlot>().cloned().ok_or(AppError::Internal(
+            "Transaction extractor used without transaction_scope middleware".to_string(),
+        ))?;
+
+        // Pin reads through Db to the primary so they see this transaction's writes once committed
+        let db = parts.extract_with_state::<Db, S>(state).await?;
+        db.pin();
+
+        // Only one Tx may be held per request at a time
+        let mut guard = slot.0.try_lock_owned().map_err(|_| {
+            AppError::Internal("Request transaction is already extracted".to_string())
+        })?;
+        if guard.is_none() {
+            *guard = Some(Databases::from_ref(state).primary.pool()?.begin().await?);
+        }
+        Ok(Tx(guard))
+    }
+}
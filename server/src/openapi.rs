@@ -0,0 +1,53 @@
+use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::controllers::{
+    account_controller, audit_controller, auth_controller, health_controller, media_controller,
+    user_controller,
+};
+
+// OpenAPI document for the server's routes, nested the same way as the routers
+#[derive(OpenApi)]
+#[openapi(
+    nest(
+        (path = "/audit", api = audit_controller::AuditApi),
+        (path = "/auth", api = auth_controller::AuthApi),
+        (path = "/users", api = user_controller::UserApi),
+        (path = "/users", api = account_controller::AccountApi),
+    ),
+    modifiers(&MergedRoutes, &BearerSecurity),
+    tags(
+        (name = "account", description = "Password, email, two-factor and session management"),
+        (name = "audit", description = "Security audit log, admin only"),
+        (name = "auth", description = "Registration, login and token exchange"),
+        (name = "health", description = "Liveness and readiness probes"),
+        (name = "media", description = "Signed media downloads"),
+        (name = "users", description = "User profiles and administration"),
+    )
+)]
+pub struct ApiDoc;
+
+// Routes merged at the root rather than nested under a prefix
+struct MergedRoutes;
+
+impl Modify for MergedRoutes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi.merge(health_controller::HealthApi::openapi());
+        openapi.merge(media_controller::MediaApi::openapi());
+    }
+}
+
+// Bearer access token scheme referenced by authenticated routes
+struct BearerSecurity;
+
+impl Modify for BearerSecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(
+                Http::builder().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build(),
+            ),
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use sqlx::any::{Any, AnyArguments};
+use sqlx::{Arguments, Encode, Type};
+
+use crate::error::AppError;
+
+// SQL builder numbering placeholders as $1, $2, ... so dynamic queries run on every driver
+//
+// sqlx::QueryBuilder formats Any placeholders as `?`, which Postgres rejects.
+pub struct SqlBuilder<'q> {
+    sql: String,
+    arguments: AnyArguments<'q>,
+    error: Option<String>,
+}
+
+impl<'q> SqlBuilder<'q> {
+    pub fn new(sql: impl Into<String>) -> Self {
+        Self { sql: sql.into(), arguments: AnyArguments::default(), error: None }
+    }
+
+    // Append SQL verbatim
+    pub fn push(&mut self, sql: impl AsRef<str>) -> &mut Self {
+        self.sql.push_str(sql.as_ref());
+        self
+    }
+
+    // Append placeholder bound to value
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'q + Encode<'q, Any> + Type<Any>,
+    {
+        if let Err(error) = self.arguments.add(value) {
+            self.error.get_or_insert(error.to_string());
+        }
+        self.sql.push_str(&format!("${}", self.arguments.len()));
+        self
+    }
+
+    // Finish into SQL and bound arguments
+    pub fn build(self) -> Result<(String, AnyArguments<'q>), AppError> {
+        match self.error {
+            Some(error) => {
+                Err(AppError::Internal(format!("Error binding query argument: {}", error)))
+            }
+            None => Ok((self.sql, self.arguments)),
+        }
+    }
+}
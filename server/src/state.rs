@@ -0,0 +1,29 @@
+use axum::extract::FromRef;
+
+use crate::pool::{Database, Databases};
+use crate::storage::SharedBlobStore;
+
+// Shared application state
+#[derive(Clone)]
+pub struct AppState {
+    pub db: Databases,
+    pub media: SharedBlobStore,
+}
+
+impl FromRef<AppState> for Databases {
+    fn from_ref(state: &AppState) -> Self {
+        state.db.clone()
+    }
+}
+
+impl FromRef<AppState> for Database {
+    fn from_ref(state: &AppState) -> Self {
+        state.db.primary.clone()
+    }
+}
+
+impl FromRef<AppState> for SharedBlobStore {
+    fn from_ref(state: &AppState) -> Self {
+        state.media.clone()
+    }
+}
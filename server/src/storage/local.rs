@@ -0,0 +1,209 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use axum::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use bytes::Bytes;
+use hmac::{Hmac, Mac};
+use jsonwebtoken::get_current_timestamp;
+use sha2::Sha256;
+use uuid::Uuid;
+
+use super::{Blob, BlobStore, content_type_for, required_env, validate_key};
+use crate::config::env_parse_opt;
+use crate::error::AppError;
+
+// URL prefix local media is served under when MEDIA_URL_BASE is unset
+const DEFAULT_URL_BASE: &str = "/media";
+
+// Blob store on the local filesystem, served through /media with HMAC-signed URLs
+pub struct LocalBlobStore {
+    root: PathBuf,
+    url_base: String,
+    signing_key: Vec<u8>,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: impl Into<PathBuf>, url_base: impl Into<String>, signing_key: &[u8]) -> Self {
+        Self {
+            root: root.into(),
+            url_base: url_base.into().trim_end_matches('/').to_string(),
+            signing_key: signing_key.to_vec(),
+        }
+    }
+
+    // Create store from MEDIA_LOCAL_PATH, MEDIA_URL_BASE and MEDIA_SIGNING_KEY
+    pub fn from_env() -> Result<Self, AppError> {
+        let root = env_parse_opt::<PathBuf>("MEDIA_LOCAL_PATH")?.unwrap_or(PathBuf::from("media"));
+        let url_base =
+            env_parse_opt::<String>("MEDIA_URL_BASE")?.unwrap_or(DEFAULT_URL_BASE.to_string());
+        let signing_key = required_env("MEDIA_SIGNING_KEY")?;
+        Ok(Self::new(root, url_base, signing_key.as_bytes()))
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, AppError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+
+    fn mac(&self, key: &str, expires: u64) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.signing_key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(format!("{}\n{}", key, expires).as_bytes());
+        mac
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, data: Bytes, _content_type: &str) -> Result<(), AppError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(storage_error)?;
+        }
+
+        // Write to a temporary file first so readers never see a partial object
+        let temporary = path.with_extension(format!("{}.tmp", Uuid::new_v4()));
+        tokio::fs::write(&temporary, &data).await.map_err(storage_error)?;
+        if let Err(error) = tokio::fs::rename(&temporary, &path).await {
+            let _ = tokio::fs::remove_file(&temporary).await;
+            return Err(storage_error(error));
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Blob, AppError> {
+        let path = self.path(key)?;
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(Blob {
+                data: Bytes::from(data),
+                content_type: content_type_for(key).to_string(),
+            }),
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                Err(AppError::NotFound("Media not found".to_string()))
+            }
+            Err(error) => Err(storage_error(error)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let path = self.path(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Err(error) if error.kind() != ErrorKind::NotFound => Err(storage_error(error)),
+            _ => Ok(()),
+        }
+    }
+
+    fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String, AppError> {
+        validate_key(key)?;
+        let expires = get_current_timestamp() + expires_in.as_secs();
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(key, expires).finalize().into_bytes());
+        Ok(format!("{}/{}?expires={}&signature={}", self.url_base, key, expires, signature))
+    }
+
+    fn verify_signature(&self, key: &str, expires: u64, signature: &str) -> bool {
+        if expires < get_current_timestamp() {
+            return false;
+        }
+        let Ok(signature) = URL_SAFE_NO_PAD.decode(signature) else {
+            return false;
+        };
+        // Constant-time comparison
+        self.mac(key, expires).verify_slice(&signature).is_ok()
+    }
+}
+
+fn storage_error(error: std::io::Error) -> AppError {
+    AppError::Internal(format!("Media storage error: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> LocalBlobStore {
+        let root = std::env::temp_dir().join(format!("rustenv-media-{}", Uuid::new_v4()));
+        LocalBlobStore::new(root, "/media/", b"signing-key")
+    }
+
+    // Expiry and signature query parameters of signed URL
+    fn signature_of(url: &str) -> (u64, String) {
+        let query = url.split_once('?').unwrap().1;
+        let parameters: Vec<(String, String)> = serde_urlencoded::from_str(query).unwrap();
+        let expires = parameters.iter().find(|(name, _)| name == "expires").unwrap().1.parse();
+        let signature = parameters.iter().find(|(name, _)| name == "signature").unwrap();
+        (expires.unwrap(), signature.1.clone())
+    }
+
+    // Signature of key expiring at timestamp
+    fn sign(store: &LocalBlobStore, key: &str, expires: u64) -> String {
+        URL_SAFE_NO_PAD.encode(store.mac(key, expires).finalize().into_bytes())
+    }
+
+    #[test]
+    fn signed_url_verifies() {
+        let store = store();
+        let url = store.signed_url("avatars/a/64.webp", Duration::from_secs(60)).unwrap();
+        assert!(url.starts_with("/media/avatars/a/64.webp?"));
+
+        let (expires, signature) = signature_of(&url);
+        assert!(store.verify_signature("avatars/a/64.webp", expires, &signature));
+    }
+
+    #[test]
+    fn tampered_signature_is_rejected() {
+        let store = store();
+        let url = store.signed_url("avatars/a/64.webp", Duration::from_secs(60)).unwrap();
+        let (expires, signature) = signature_of(&url);
+
+        // Another key, a later expiry, a changed signature or a signature that is not base64
+        assert!(!store.verify_signature("avatars/b/64.webp", expires, &signature));
+        assert!(!store.verify_signature("avatars/a/64.webp", expires + 1, &signature));
+        let mut tampered = signature.into_bytes();
+        tampered[0] = if tampered[0] == b'A' { b'B' } else { b'A' };
+        let tampered = String::from_utf8(tampered).unwrap();
+        assert!(!store.verify_signature("avatars/a/64.webp", expires, &tampered));
+        assert!(!store.verify_signature("avatars/a/64.webp", expires, "not base64!"));
+    }
+
+    #[test]
+    fn signature_of_another_store_is_rejected() {
+        let url = store().signed_url("avatars/a/64.webp", Duration::from_secs(60)).unwrap();
+        let (expires, signature) = signature_of(&url);
+        let other = LocalBlobStore::new("media", "/media", b"other-key");
+        assert!(!other.verify_signature("avatars/a/64.webp", expires, &signature));
+    }
+
+    #[test]
+    fn expired_url_is_rejected() {
+        let store = store();
+        let expires = get_current_timestamp() - 1;
+        let signature = sign(&store, "avatars/a/64.webp", expires);
+        assert!(!store.verify_signature("avatars/a/64.webp", expires, &signature));
+    }
+
+    #[test]
+    fn signed_url_rejects_invalid_key() {
+        assert!(store().signed_url("../secrets", Duration::from_secs(60)).is_err());
+    }
+
+    #[tokio::test]
+    async fn put_get_delete_round_trip() {
+        let store = store();
+        let key = "avatars/a/64.png";
+        store.put(key, Bytes::from_static(b"image"), "image/png").await.unwrap();
+
+        let blob = store.get(key).await.unwrap();
+        assert_eq!(blob.data, Bytes::from_static(b"image"));
+        assert_eq!(blob.content_type, "image/png");
+
+        store.delete(key).await.unwrap();
+        assert!(matches!(store.get(key).await, Err(AppError::NotFound(_))));
+        // Deleting again succeeds
+        store.delete(key).await.unwrap();
+
+        let _ = std::fs::remove_dir_all(&store.root);
+    }
+}
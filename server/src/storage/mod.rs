@@ -0,0 +1,137 @@
+pub mod local;
+pub mod s3;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::async_trait;
+use bytes::Bytes;
+
+use crate::config::{env_parse_opt, env_var};
+use crate::error::AppError;
+use crate::storage::local::LocalBlobStore;
+use crate::storage::s3::S3BlobStore;
+
+// Signed media URL lifetime when MEDIA_URL_EXPIRY is unset
+const DEFAULT_URL_EXPIRY: Duration = Duration::from_secs(60 * 60);
+
+// Stored object contents
+pub struct Blob {
+    pub data: Bytes,
+    pub content_type: String,
+}
+
+// Object storage for uploaded media
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    // Store object under key, replacing any existing object
+    async fn put(&self, key: &str, data: Bytes, content_type: &str) -> Result<(), AppError>;
+
+    // Get object by key, NotFound if absent
+    async fn get(&self, key: &str) -> Result<Blob, AppError>;
+
+    // Delete object by key, succeeding if already absent
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+
+    // Time-limited URL clients can fetch object from
+    fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String, AppError>;
+
+    // Check URL signature issued by this store, for stores served through /media
+    fn verify_signature(&self, _key: &str, _expires: u64, _signature: &str) -> bool {
+        false
+    }
+}
+
+// Shared blob store handle
+pub type SharedBlobStore = Arc<dyn BlobStore>;
+
+// Create blob store from MEDIA_STORE (local or s3)
+pub fn from_env() -> Result<SharedBlobStore, AppError> {
+    let store = env_parse_opt::<String>("MEDIA_STORE")?.unwrap_or("local".to_string());
+    match store.as_str() {
+        "local" => Ok(Arc::new(LocalBlobStore::from_env()?)),
+        "s3" => Ok(Arc::new(S3BlobStore::from_env()?)),
+        store => Err(AppError::Internal(format!("Unsupported MEDIA_STORE: {}", store))),
+    }
+}
+
+// Signed media URL lifetime from MEDIA_URL_EXPIRY in seconds
+pub fn url_expiry() -> Duration {
+    match env_parse_opt::<u64>("MEDIA_URL_EXPIRY") {
+        Ok(seconds) => seconds.map(Duration::from_secs).unwrap_or(DEFAULT_URL_EXPIRY),
+        Err(error) => {
+            tracing::error!(%error, "Invalid media URL expiry, using default");
+            DEFAULT_URL_EXPIRY
+        }
+    }
+}
+
+// Reject keys that could escape the store root or need encoding
+pub fn validate_key(key: &str) -> Result<(), AppError> {
+    let valid = !key.is_empty()
+        && key.len() <= 512
+        && key.split('/').all(|segment| !segment.is_empty() && segment != "." && segment != "..")
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '-' | '_' | '.'));
+    match valid {
+        true => Ok(()),
+        false => Err(AppError::NotFound("Media not found".to_string())),
+    }
+}
+
+// Content type of stored media by file extension
+pub fn content_type_for(key: &str) -> &'static str {
+    match key.rsplit_once('.').map(|(_, extension)| extension) {
+        Some("webp") => "image/webp",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        _ => "application/octet-stream",
+    }
+}
+
+// Required environment variable, treating empty as unset
+fn required_env(key: &str) -> Result<String, AppError> {
+    match env_var(key)? {
+        value if value.is_empty() => {
+            Err(AppError::Internal(format!("{} environment variable is empty", key)))
+        }
+        value => Ok(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_key_accepts_nested_keys() {
+        assert!(validate_key("avatars/uuid/64.webp").is_ok());
+        assert!(validate_key("file_name-1.png").is_ok());
+    }
+
+    #[test]
+    fn validate_key_rejects_traversal() {
+        assert!(validate_key("..").is_err());
+        assert!(validate_key("avatars/../secrets").is_err());
+        assert!(validate_key("avatars/./64.webp").is_err());
+    }
+
+    #[test]
+    fn validate_key_rejects_empty_segments() {
+        assert!(validate_key("").is_err());
+        assert!(validate_key("avatars//64.webp").is_err());
+        assert!(validate_key("avatars/").is_err());
+    }
+
+    #[test]
+    fn validate_key_rejects_absolute_keys() {
+        assert!(validate_key("/avatars/64.webp").is_err());
+        assert!(validate_key("/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn validate_key_rejects_characters_needing_encoding() {
+        assert!(validate_key("avatars/a b.png").is_err());
+        assert!(validate_key("avatars/a?b.png").is_err());
+        assert!(validate_key(&"a".repeat(513)).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use axum::async_trait;
+use bytes::Bytes;
+use http::StatusCode;
+use http::header::CONTENT_TYPE;
+use rusty_s3::{Bucket, Credentials, S3Action, UrlStyle};
+use url::Url;
+
+use super::{Blob, BlobStore, required_env, validate_key};
+use crate::config::env_parse_opt;
+use crate::error::AppError;
+
+// Lifetime of presigned URLs used for the store's own requests
+const REQUEST_SIGNATURE_EXPIRY: Duration = Duration::from_secs(60);
+
+// Blob store in an S3-compatible bucket (AWS S3, MinIO, R2), served through presigned URLs
+pub struct S3BlobStore {
+    bucket: Bucket,
+    credentials: Credentials,
+    client: reqwest::Client,
+}
+
+impl S3BlobStore {
+    pub fn new(bucket: Bucket, credentials: Credentials) -> Self {
+        Self { bucket, credentials, client: reqwest::Client::new() }
+    }
+
+    // Create store from S3_ENDPOINT, S3_BUCKET, S3_REGION, S3_ACCESS_KEY, S3_SECRET_KEY and S3_PATH_STYLE
+    pub fn from_env() -> Result<Self, AppError> {
+        let endpoint = required_env("S3_ENDPOINT")?
+            .parse::<Url>()
+            .map_err(|error| AppError::Internal(format!("Invalid S3_ENDPOINT: {}", error)))?;
+
+        // Path-style requests are needed by most local stand-ins such as MinIO
+        let url_style = match env_parse_opt::<bool>("S3_PATH_STYLE")?.unwrap_or(false) {
+            true => UrlStyle::Path,
+            false => UrlStyle::VirtualHost,
+        };
+        let region = env_parse_opt::<String>("S3_REGION")?.unwrap_or("us-east-1".to_string());
+        let bucket = Bucket::new(endpoint, url_style, required_env("S3_BUCKET")?, region)
+            .map_err(|error| AppError::Internal(format!("Invalid S3 bucket: {}", error)))?;
+        let credentials =
+            Credentials::new(required_env("S3_ACCESS_KEY")?, required_env("S3_SECRET_KEY")?);
+        Ok(Self::new(bucket, credentials))
+    }
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    async fn put(&self, key: &str, data: Bytes, content_type: &str) -> Result<(), AppError> {
+        validate_key(key)?;
+        let url =
+            self.bucket.put_object(Some(&self.credentials), key).sign(REQUEST_SIGNATURE_EXPIRY);
+        let response = self
+            .client
+            .put(url)
+            .header(CONTENT_TYPE, content_type)
+            .body(data)
+            .send()
+            .await
+            .map_err(storage_error)?;
+        check_status(response).await.map(|_| ())
+    }
+
+    async fn get(&self, key: &str) -> Result<Blob, AppError> {
+        validate_key(key)?;
+        let url =
+            self.bucket.get_object(Some(&self.credentials), key).sign(REQUEST_SIGNATURE_EXPIRY);
+        let response = self.client.get(url).send().await.map_err(storage_error)?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(AppError::NotFound("Media not found".to_string()));
+        }
+        let response = check_status(response).await?;
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let data = response.bytes().await.map_err(storage_error)?;
+        Ok(Blob { data, content_type })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        validate_key(key)?;
+        let url =
+            self.bucket.delete_object(Some(&self.credentials), key).sign(REQUEST_SIGNATURE_EXPIRY);
+        let response = self.client.delete(url).send().await.map_err(storage_error)?;
+        // S3 reports deletes of missing objects as success, some stand-ins as not found
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+        check_status(response).await.map(|_| ())
+    }
+
+    fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String, AppError> {
+        validate_key(key)?;
+        Ok(self.bucket.get_object(Some(&self.credentials), key).sign(expires_in).to_string())
+    }
+}
+
+// Turn unsuccessful S3 responses into errors, keeping the response body for logs
+async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, AppError> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    Err(AppError::Unavailable(format!("S3 request failed with {}: {}", status, body)))
+}
+
+fn storage_error(error: reqwest::Error) -> AppError {
+    AppError::Unavailable(format!("S3 request failed: {}", error))
+}
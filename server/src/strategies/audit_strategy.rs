@@ -0,0 +1,317 @@
+use std::env;
+use std::net::SocketAddr;
+
+use axum::async_trait;
+use axum::extract::{ConnectInfo, FromRequestParts};
+use futures_util::stream::{self, Stream};
+use http::header::USER_AGENT;
+use http::request::Parts;
+use jsonwebtoken::get_current_timestamp;
+use sqlx::any::Any;
+use sqlx::{AnyConnection, Executor, FromRow, Pool};
+use tracing::instrument;
+
+use crate::config::env_parse_opt;
+use crate::error::AppError;
+use crate::query::SqlBuilder;
+use crate::types::audit::{
+    AUDIT_CSV_HEADER, AuditEvent, AuditEventType, AuditOutcome, AuditQuery, ExportFormat,
+    NewAuditEvent, audit_csv_row,
+};
+use crate::types::pagination::DEFAULT_PAGE_LIMIT;
+use crate::types::user::User;
+
+// Rows fetched per query while exporting
+const EXPORT_BATCH_SIZE: i64 = 1000;
+
+// Longest user agent kept in the audit log
+const MAX_USER_AGENT_LENGTH: usize = 512;
+
+// Reverse proxies in front of the server appending to X-Forwarded-For, unless TRUSTED_PROXY_HOPS is set
+const DEFAULT_TRUSTED_PROXY_HOPS: usize = 1;
+
+// Request actor and client details attached to audit events
+#[derive(Clone, Debug, Default)]
+pub struct AuditContext {
+    pub actor_uuid: Option<String>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl AuditContext {
+    // Context with actor UUID
+    pub fn with_actor(&self, actor_uuid: impl Into<String>) -> Self {
+        Self { actor_uuid: Some(actor_uuid.into()), ..self.clone() }
+    }
+
+    // Build event from context
+    pub fn event(&self, event_type: AuditEventType, outcome: AuditOutcome) -> NewAuditEvent {
+        NewAuditEvent {
+            event_type,
+            outcome,
+            actor_uuid: self.actor_uuid.clone(),
+            target: None,
+            ip: self.ip.clone(),
+            user_agent: self.user_agent.clone(),
+            detail: None,
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuditContext
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        // Prefer the client address reported by a trusted reverse proxy
+        let forwarded_ip = match env::var("TRUST_PROXY_HEADERS").as_deref() {
+            Ok("true") => {
+                let hops =
+                    env_parse_opt("TRUSTED_PROXY_HOPS")?.unwrap_or(DEFAULT_TRUSTED_PROXY_HOPS);
+                parts
+                    .headers
+                    .get("X-Forwarded-For")
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| forwarded_client_ip(value, hops))
+            }
+            _ => None,
+        };
+        let ip = forwarded_ip.or_else(|| {
+            parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(address)| address.ip().to_string())
+        });
+        let user_agent = parts
+            .headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.chars().take(MAX_USER_AGENT_LENGTH).collect());
+
+        Ok(Self { actor_uuid: None, ip, user_agent })
+    }
+}
+
+// Client address appended by the outermost of hops trusted proxies, ignoring entries before it since
+// clients can send any X-Forwarded-For they like
+fn forwarded_client_ip(header: &str, hops: usize) -> Option<String> {
+    let entries = header.split(',').map(str::trim).collect::<Vec<_>>();
+    let index = entries.len().saturating_sub(hops.max(1));
+    entries.get(index).filter(|entry| !entry.is_empty()).map(|entry| entry.to_string())
+}
+
+// Insert audit event to database
+#[instrument(skip_all, fields(db.operation = "INSERT", db.table = "audit_events", audit.event_type = event.event_type.as_str()), err(level = "debug"))]
+pub async fn insert_audit_event<'e, E>(executor: E, event: &NewAuditEvent) -> Result<(), AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    sqlx::query(
+        r#"
+            INSERT INTO "audit_events" (event_type, outcome, actor_uuid, target, ip, user_agent, detail, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+    )
+    .bind(event.event_type.as_str())
+    .bind(event.outcome.as_str())
+    .bind(event.actor_uuid.clone())
+    .bind(event.target.clone())
+    .bind(event.ip.clone())
+    .bind(event.user_agent.clone())
+    .bind(event.detail.clone())
+    .bind(get_current_timestamp() as i64)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+// Insert audit event, logging instead of failing the request when it cannot be stored
+pub async fn record_audit_event<'e, E>(executor: E, event: &NewAuditEvent)
+where
+    E: Executor<'e, Database = Any>,
+{
+    if let Err(error) = insert_audit_event(executor, event).await {
+        tracing::error!(%error, event = ?event, "Error recording audit event");
+    }
+}
+
+// Remove client details from events performed by user, returning the number changed
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "audit_events", user.uuid = %actor_uuid), err(level = "debug"))]
+pub async fn anonymize_db_audit_events_by_actor<'e, E>(
+    executor: E,
+    actor_uuid: &str,
+) -> Result<u64, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    let result = sqlx::query(
+        r#"
+            UPDATE "audit_events" SET ip = NULL, user_agent = NULL
+            WHERE actor_uuid = $1
+        "#,
+    )
+    .bind(actor_uuid.to_string())
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+// Get page of audit events matching query, newest first
+#[instrument(skip_all, fields(db.operation = "SELECT", db.table = "audit_events"), err(level = "debug"))]
+pub async fn get_db_audit_events(
+    connection: &mut AnyConnection,
+    query: &AuditQuery,
+) -> Result<(Vec<AuditEvent>, i64), AppError> {
+    // Count all matching events
+    let mut count = SqlBuilder::new(r#"SELECT COUNT(*) FROM "audit_events" WHERE 1 = 1"#);
+    push_audit_filters(&mut count, query);
+    let (sql, arguments) = count.build()?;
+    let total: i64 = sqlx::query_scalar_with(&sql, arguments).fetch_one(&mut *connection).await?;
+
+    let mut page = SqlBuilder::new(r#"SELECT * FROM "audit_events" WHERE 1 = 1"#);
+    push_audit_filters(&mut page, query);
+    page.push(" ORDER BY created_at DESC, id DESC LIMIT ")
+        .push_bind(i64::from(query.limit.unwrap_or(DEFAULT_PAGE_LIMIT)))
+        .push(" OFFSET ")
+        .push_bind(query.offset.unwrap_or(0) as i64);
+    let (sql, arguments) = page.build()?;
+    let events = sqlx::query_as_with(&sql, arguments).fetch_all(connection).await?;
+
+    Ok((events, total))
+}
+
+// Get all audit events about a user, matching the actor or any of the user's identifiers as target
+//
+// Actor and client details are removed from events the user did not perform, such as admin
+// actions on the account or failed logins by others, since they belong to someone else.
+#[instrument(skip_all, fields(db.operation = "SELECT", db.table = "audit_events", user.uuid = %user.uuid), err(level = "debug"))]
+pub async fn get_db_audit_events_for_user<'e, E>(
+    executor: E,
+    user: &User,
+) -> Result<Vec<AuditEvent>, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    let events = sqlx::query_as::<_, AuditEvent>(
+        r#"
+            SELECT * FROM "audit_events"
+            WHERE actor_uuid = $1 OR target = $1 OR target = $2 OR target = $3
+            ORDER BY id
+        "#,
+    )
+    .bind(user.uuid.to_owned())
+    .bind(user.username.to_owned())
+    .bind(user.email.to_owned())
+    .fetch_all(executor)
+    .await?;
+
+    Ok(events
+        .into_iter()
+        .map(|event| match event.actor_uuid.as_deref() == Some(user.uuid.as_str()) {
+            true => event,
+            false => AuditEvent { actor_uuid: None, ip: None, user_agent: None, ..event },
+        })
+        .collect())
+}
+
+// Stream all audit events matching query in export format, oldest first, fetching in batches
+pub fn export_audit_events(
+    pool: Pool<Any>,
+    query: AuditQuery,
+    format: ExportFormat,
+) -> impl Stream<Item = Result<String, AppError>> + Send + 'static {
+    let header = match format {
+        ExportFormat::Csv => Some(AUDIT_CSV_HEADER.to_string()),
+        ExportFormat::Jsonl => None,
+    };
+    let header = stream::iter(header.map(Ok));
+
+    let batches = stream::try_unfold(Some(0_i64), move |after_id| {
+        let pool = pool.clone();
+        let query = query.clone();
+        async move {
+            let Some(after_id) = after_id else { return Ok(None) };
+
+            let mut batch = SqlBuilder::new(r#"SELECT * FROM "audit_events" WHERE id > "#);
+            batch.push_bind(after_id);
+            push_audit_filters(&mut batch, &query);
+            batch.push(" ORDER BY id LIMIT ").push_bind(EXPORT_BATCH_SIZE);
+            let (sql, arguments) = batch.build()?;
+            let rows = sqlx::query_with(&sql, arguments).fetch_all(&pool).await?;
+
+            let mut chunk = String::new();
+            let mut last_id = after_id;
+            for row in &rows {
+                let event = AuditEvent::from_row(row)?;
+                last_id = event.id;
+                match format {
+                    ExportFormat::Csv => chunk.push_str(&audit_csv_row(&event)),
+                    ExportFormat::Jsonl => {
+                        chunk.push_str(&serde_json::to_string(&event).map_err(|error| {
+                            AppError::Internal(format!("Error encoding audit event: {}", error))
+                        })?);
+                        chunk.push('\n');
+                    }
+                }
+            }
+
+            // Stop after a short batch
+            let next = (rows.len() as i64 == EXPORT_BATCH_SIZE).then_some(last_id);
+            Ok(match rows.is_empty() {
+                true => None,
+                false => Some((chunk, next)),
+            })
+        }
+    });
+
+    futures_util::StreamExt::chain(header, batches)
+}
+
+// Append audit query filters to query
+fn push_audit_filters(builder: &mut SqlBuilder, query: &AuditQuery) {
+    if let Some(event_type) = query.event_type {
+        builder.push(" AND event_type = ").push_bind(event_type.as_str());
+    }
+    if let Some(outcome) = query.outcome {
+        builder.push(" AND outcome = ").push_bind(outcome.as_str());
+    }
+    if let Some(actor_uuid) = &query.actor_uuid {
+        builder.push(" AND actor_uuid = ").push_bind(actor_uuid.to_owned());
+    }
+    if let Some(target) = &query.target {
+        builder.push(" AND target = ").push_bind(target.to_owned());
+    }
+    if let Some(created_after) = query.created_after {
+        builder.push(" AND created_at >= ").push_bind(created_after);
+    }
+    if let Some(created_before) = query.created_before {
+        builder.push(" AND created_at < ").push_bind(created_before);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forwarded_client_ip_ignores_client_entries() {
+        // The proxy appends the address it saw to whatever the client sent
+        assert_eq!(forwarded_client_ip("198.51.100.9", 1).as_deref(), Some("198.51.100.9"));
+        assert_eq!(
+            forwarded_client_ip("10.0.0.1, 6.6.6.6, 198.51.100.9", 1).as_deref(),
+            Some("198.51.100.9")
+        );
+        // Behind two proxies the outer proxy's entry precedes the inner proxy's
+        assert_eq!(
+            forwarded_client_ip("6.6.6.6, 198.51.100.9, 10.0.0.2", 2).as_deref(),
+            Some("198.51.100.9")
+        );
+        // Fewer entries than hops were all appended by trusted proxies
+        assert_eq!(forwarded_client_ip("198.51.100.9", 3).as_deref(), Some("198.51.100.9"));
+        assert_eq!(forwarded_client_ip("198.51.100.9", 0).as_deref(), Some("198.51.100.9"));
+        assert_eq!(forwarded_client_ip("6.6.6.6, ", 1), None);
+    }
+}
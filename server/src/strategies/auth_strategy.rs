@@ -1,45 +1,34 @@
-use std::env;
+use std::future::Future;
 
-use axum::body::Body;
-use axum::extract::FromRequestParts;
-use axum::response::{IntoResponse, Response};
-use axum::{Json, RequestPartsExt, async_trait};
+use axum::extract::{FromRef, FromRequestParts};
+use axum::{RequestPartsExt, async_trait};
 use axum_extra::TypedHeader;
 use axum_extra::headers::Authorization;
 use axum_extra::headers::authorization::Bearer;
 use base64::prelude::*;
+use http::HeaderMap;
 use http::request::Parts;
-use http::{HeaderMap, StatusCode};
 use jsonwebtoken::{
     Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode, get_current_timestamp,
 };
-use once_cell::sync::Lazy;
+use metrics::counter;
+use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
 use struct_iterable::Iterable;
+use tracing::Span;
 
+use super::audit_strategy::AuditContext;
+use super::session_strategy;
 use super::user_strategy::get_db_user_by_uuid;
-use crate::types::auth::{AuthErrorBody, AuthErrorType, AuthToken};
+use crate::config::{env_parse, env_var};
+use crate::error::AppError;
+use crate::pool::{Databases, Db};
+use crate::types::auth::{AuthErrorType, AuthToken};
+use crate::types::session::Session;
+use crate::types::user::User;
 
 // Keys for encode and decode authentication tokens
-static KEYS: Lazy<Keys> = Lazy::new(|| {
-    let secret = env::var("JWT_SECRET").expect("Missing JWT_SECRET environment variable");
-    Keys::new(secret.as_bytes())
-});
-
-// Authentication token lifetime
-static TOKEN_LIFETIME: Lazy<u64> = Lazy::new(|| {
-    let expiry =
-        env::var("AUTH_TOKEN_EXPIRY").expect("Missing AUTH_TOKEN_EXPIRY environment variable");
-    u64::from_str_radix(&expiry, 10).expect("Cannot pase AUTH_TOKEN_EXPIRY as u64")
-});
-
-// Authentication request token lifetime
-static REQUEST_TOKEN_LIFETIME: Lazy<u64> = Lazy::new(|| {
-    let expiry = env::var("REQUEST_TOKEN_EXPIRY")
-        .expect("Missing REQUEST_TOKEN_EXPIRY environment variable");
-    u64::from_str_radix(&expiry, 10).expect("Cannot pase REQUEST_TOKEN_EXPIRY as u64")
-});
+static KEYS: OnceCell<Keys> = OnceCell::new();
 
 // Encode/decode keys
 struct Keys {
@@ -56,89 +45,130 @@ impl Keys {
     }
 }
 
+// Get keys, loading them from JWT_SECRET on first use
+fn keys() -> Result<&'static Keys, AppError> {
+    KEYS.get_or_try_init(|| Ok(Keys::new(env_var("JWT_SECRET")?.as_bytes())))
+}
+
+// Load keys ahead of the first request
+pub fn load_keys() -> Result<(), AppError> {
+    keys().map(|_| ())
+}
+
+// Authentication token lifetime
+fn token_lifetime() -> Result<u64, AppError> {
+    env_parse::<u64>("AUTH_TOKEN_EXPIRY")
+}
+
+// Authentication request token lifetime, which is also the session lifetime
+pub fn request_token_lifetime() -> Result<u64, AppError> {
+    env_parse::<u64>("AUTH_REQUEST_TOKEN_EXPIRY")
+}
+
+// Build token validation from environment variables
+fn validation() -> Result<Validation, AppError> {
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.leeway = 5;
+    validation.set_audience(&[env_var("JWT_AUDIENCE")?]);
+    validation.set_issuer(&[env_var("JWT_ISSUER")?]);
+    Ok(validation)
+}
+
 // Define trait for JWT claims
 pub trait JWTClaims {
+    // Token type, stored in the typ claim and used to label issued token metrics
+    const TOKEN_TYPE: &'static str;
+
+    // Token type the claims were issued as
+    fn typ(&self) -> &str;
+
     // Create new claims from UUID
-    async fn new(uuid: String) -> Result<Self, AuthError>
+    fn new(db: &Db, uuid: String) -> impl Future<Output = Result<Self, AppError>> + Send
     where
         Self: Sized;
 
     // Create claims from X-Claims header
-    fn from_header(header: &HeaderMap) -> Self
+    fn from_header(header: &HeaderMap) -> Result<Self, AppError>
     where
+        Self: Sized,
         Self: for<'de> Deserialize<'de>,
     {
-        let claims = header.get("X-Claims").unwrap();
-        serde_json::from_str(&String::from_utf8(BASE64_STANDARD.decode(claims).unwrap()).unwrap())
-            .unwrap()
+        let claims = header.get("X-Claims").ok_or(AppError::Auth(AuthErrorType::InvalidToken))?;
+        let decoded = BASE64_STANDARD
+            .decode(claims)
+            .map_err(|_| AppError::Auth(AuthErrorType::InvalidToken))?;
+        serde_json::from_slice(&decoded).map_err(|_| AppError::Auth(AuthErrorType::InvalidToken))
     }
 
     // Create claims from encoded string
-    fn from_string(encoded_str: &str) -> Result<Self, AuthError>
+    fn from_string(encoded_str: &str) -> Result<Self, AppError>
     where
         Self: Sized,
         Self: for<'de> Deserialize<'de>,
     {
-        // Get environment variables
-        let aud = env::var("JWT_AUDIENCE").expect("Missing JWT_AUDIENCE environment variable");
-        let iss = env::var("JWT_ISSUER").expect("Missing JWT_ISSUER environment variable");
-
-        // Build validation
-        let mut validation = Validation::new(Algorithm::HS256);
-        validation.leeway = 5;
-        validation.set_audience(&[aud]);
-        validation.set_issuer(&[iss]);
-
-        // Decode token
-        match decode::<Self>(encoded_str, &KEYS.decoding, &validation) {
-            Ok(token_data) => Ok(token_data.claims),
-            Err(_) => Err(AuthError::from_type(AuthErrorType::InvalidToken)),
+        // Decode token, rejecting tokens of another type signed with the same key
+        match decode::<Self>(encoded_str, &keys()?.decoding, &validation()?) {
+            Ok(token_data) if token_data.claims.typ() == Self::TOKEN_TYPE => Ok(token_data.claims),
+            _ => Err(AppError::Auth(AuthErrorType::InvalidToken)),
         }
     }
 
     // Create default claims
-    fn default() -> Self;
+    fn default() -> Result<Self, AppError>
+    where
+        Self: Sized;
 
     // Generate token from claims
-    fn generate_token(&self) -> Result<AuthToken, AuthError>
+    fn generate_token(&self) -> Result<AuthToken, AppError>
     where
         Self: Serialize,
     {
-        match encode(&Header::default(), &self, &KEYS.encoding) {
-            Ok(encoded_string) => Ok(AuthToken::new(encoded_string)),
+        match encode(&Header::default(), &self, &keys()?.encoding) {
+            Ok(encoded_string) => {
+                counter!("auth_tokens_issued_total", "token_type" => Self::TOKEN_TYPE).increment(1);
+                Ok(AuthToken::new(encoded_string))
+            }
             Err(error) => {
-                println!("Error generating token: {:?}", error);
-                Err(AuthError::from_type(AuthErrorType::TokenGeneration))
+                tracing::error!(?error, "Error generating token");
+                Err(AppError::Auth(AuthErrorType::TokenGeneration))
             }
         }
     }
 }
 
 // Build claims from authorization header
-async fn from_request_parts<T>(parts: &mut Parts) -> Result<T, AuthError>
+async fn from_request_parts<T>(parts: &mut Parts) -> Result<T, AppError>
 where
-    T: for<'de> Deserialize<'de>,
+    T: JWTClaims + for<'de> Deserialize<'de>,
 {
-    // Get environment variables
-    let aud = env::var("JWT_AUDIENCE").expect("Missing JWT_AUDIENCE environment variable");
-    let iss = env::var("JWT_ISSUER").expect("Missing JWT_ISSUER environment variable");
-
     // Extract authorization header
     let TypedHeader(Authorization(bearer)) = parts
         .extract::<TypedHeader<Authorization<Bearer>>>()
         .await
-        .map_err(|_| AuthError::from_type(AuthErrorType::InvalidToken))?;
+        .map_err(|_| AppError::Auth(AuthErrorType::InvalidToken))?;
 
-    // Build validation
-    let mut validation = Validation::new(Algorithm::HS256);
-    validation.leeway = 5;
-    validation.set_audience(&[aud]);
-    validation.set_issuer(&[iss]);
+    T::from_string(bearer.token())
+}
 
-    // Decode token
-    let token_data = decode::<T>(bearer.token(), &KEYS.decoding, &validation)
-        .map_err(|_| AuthError::from_type(AuthErrorType::InvalidToken))?;
-    Ok(token_data.claims)
+// Get user for token subject, rejecting deleted and disabled accounts
+async fn token_subject(db: &Db, uuid: String) -> Result<User, AppError> {
+    let user =
+        get_db_user_by_uuid(&mut *db.reader().await?, uuid).await.map_err(|error| match error {
+            AppError::NotFound(_) => AppError::Auth(AuthErrorType::InvalidToken),
+            error => error,
+        })?;
+    if user.is_disabled() {
+        return Err(AppError::Auth(AuthErrorType::AccountDisabled));
+    }
+    Ok(user)
+}
+
+// Roles granted by user's current admin flag
+fn user_roles(user: &User) -> Vec<String> {
+    match user.is_admin {
+        true => vec!["admin".to_string(), "user".to_string()],
+        false => vec!["user".to_string()],
+    }
 }
 
 // Authentication claims
@@ -150,60 +180,116 @@ pub struct AuthClaims {
     pub exp: u64,
     pub role: Vec<String>,
     pub iat: usize,
+    pub typ: String,
+    // Session the token was exchanged from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
 }
 
 impl JWTClaims for AuthClaims {
+    const TOKEN_TYPE: &'static str = "access";
+
+    fn typ(&self) -> &str {
+        &self.typ
+    }
+
     // Create new claims from UUID
-    async fn new(uuid: String) -> Result<Self, AuthError> {
+    async fn new(db: &Db, uuid: String) -> Result<Self, AppError> {
         // Get environment variables
-        let aud = env::var("JWT_AUDIENCE").expect("Missing JWT_AUDIENCE environment variable");
-        let iss = env::var("JWT_ISSUER").expect("Missing JWT_ISSUER environment variable");
+        let aud = env_var("JWT_AUDIENCE")?;
+        let iss = env_var("JWT_ISSUER")?;
 
         // Build claims from database user
-        match get_db_user_by_uuid(uuid).await {
-            Ok(user) => Ok(Self {
-                iss,
-                sub: user.uuid,
-                aud,
-                exp: get_current_timestamp() + *TOKEN_LIFETIME,
-                role: if user.is_admin {
-                    vec!["admin".to_string(), "user".to_string()]
-                } else {
-                    vec!["user".to_string()]
-                },
-                iat: get_current_timestamp() as usize,
-            }),
-            Err(_) => Err(AuthError::from_type(AuthErrorType::TokenGeneration)),
-        }
+        let lifetime = token_lifetime()?;
+        let user = token_subject(db, uuid).await?;
+        let role = user_roles(&user);
+        Ok(Self {
+            iss,
+            sub: user.uuid,
+            aud,
+            exp: get_current_timestamp() + lifetime,
+            role,
+            iat: get_current_timestamp() as usize,
+            typ: Self::TOKEN_TYPE.to_string(),
+            sid: None,
+        })
     }
 
     // Create default claims
-    fn default() -> Self {
+    fn default() -> Result<Self, AppError> {
         // Get environment variables
-        let aud = env::var("JWT_AUDIENCE").expect("Missing JWT_AUDIENCE environment variable");
-        let iss = env::var("JWT_ISSUER").expect("Missing JWT_ISSUER environment variable");
+        let aud = env_var("JWT_AUDIENCE")?;
+        let iss = env_var("JWT_ISSUER")?;
 
         // Build claims
-        Self {
+        Ok(Self {
             iss,
             sub: String::new(),
             aud,
-            exp: get_current_timestamp() + *TOKEN_LIFETIME,
+            exp: get_current_timestamp() + token_lifetime()?,
             role: vec!["user".to_string()],
             iat: get_current_timestamp() as usize,
-        }
+            typ: Self::TOKEN_TYPE.to_string(),
+            sid: None,
+        })
     }
 }
 
 #[async_trait]
 impl<S> FromRequestParts<S> for AuthClaims
 where
-    S: Sync,
+    Databases: FromRef<S>,
+    S: Send + Sync,
 {
-    type Rejection = AuthError;
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let mut claims = from_request_parts::<AuthClaims>(parts).await?;
+        Span::current().record("user.sub", claims.sub.as_str());
+
+        // Reject tokens of users deleted or disabled since the token was issued, and replace the
+        // token's roles with the current ones so demoted admins lose access immediately
+        let db = parts.extract_with_state::<Db, S>(state).await?;
+        let user = token_subject(&db, claims.sub.clone()).await?;
+        claims.role = user_roles(&user);
+
+        // Reject tokens of sessions revoked since the token was issued
+        if let Some(sid) = &claims.sid {
+            if !session_strategy::is_db_session_active(&mut *db.reader().await?, sid, &claims.sub)
+                .await?
+            {
+                return Err(AppError::Auth(AuthErrorType::InvalidToken));
+            }
+        }
+        Ok(claims)
+    }
+}
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        from_request_parts::<AuthClaims>(parts).await
+impl AuthClaims {
+    // Whether claims grant role
+    pub fn has_role(&self, role: &str) -> bool {
+        self.role.iter().any(|claim| claim == role)
+    }
+}
+
+// Authentication claims of an admin user
+#[derive(Debug)]
+pub struct AdminClaims(pub AuthClaims);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AdminClaims
+where
+    Databases: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let claims = AuthClaims::from_request_parts(parts, state).await?;
+        if !claims.has_role("admin") {
+            return Err(AppError::Auth(AuthErrorType::Forbidden));
+        }
+        Ok(Self(claims))
     }
 }
 
@@ -215,39 +301,67 @@ pub struct AuthRequestClaims {
     pub aud: String,
     pub exp: u64,
     pub iat: usize,
+    pub typ: String,
+    // Session the token belongs to, revoked on logout
+    pub sid: String,
 }
 
-impl JWTClaims for AuthRequestClaims {
-    // Create new claims from UUID
-    async fn new(uuid: String) -> Result<Self, AuthError> {
+impl AuthRequestClaims {
+    // Create claims for session, expiring with it
+    pub fn for_session(session: &Session) -> Result<Self, AppError> {
         // Get environment variables
-        let aud = env::var("JWT_AUDIENCE").expect("Missing JWT_AUDIENCE environment variable");
-        let iss = env::var("JWT_ISSUER").expect("Missing JWT_ISSUER environment variable");
+        let aud = env_var("JWT_AUDIENCE")?;
+        let iss = env_var("JWT_ISSUER")?;
 
         // Build claims
         Ok(Self {
             iss,
-            sub: uuid,
+            sub: session.user_uuid.clone(),
             aud,
-            exp: get_current_timestamp() + *TOKEN_LIFETIME,
+            exp: session.expires_at as u64,
             iat: get_current_timestamp() as usize,
+            typ: Self::TOKEN_TYPE.to_string(),
+            sid: session.uuid.clone(),
         })
     }
+}
+
+impl JWTClaims for AuthRequestClaims {
+    const TOKEN_TYPE: &'static str = "request";
+
+    fn typ(&self) -> &str {
+        &self.typ
+    }
+
+    // Create new claims from UUID in a new session without client details
+    async fn new(db: &Db, uuid: String) -> Result<Self, AppError> {
+        let expires_at = (get_current_timestamp() + request_token_lifetime()?) as i64;
+        let session = session_strategy::insert_db_session(
+            &mut *db.writer().await?,
+            &AuditContext::default(),
+            &uuid,
+            expires_at,
+        )
+        .await?;
+        Self::for_session(&session)
+    }
 
     // Create default claims
-    fn default() -> Self {
+    fn default() -> Result<Self, AppError> {
         // Get environment variables
-        let aud = env::var("JWT_AUDIENCE").expect("Missing JWT_AUDIENCE environment variable");
-        let iss = env::var("JWT_ISSUER").expect("Missing JWT_ISSUER environment variable");
+        let aud = env_var("JWT_AUDIENCE")?;
+        let iss = env_var("JWT_ISSUER")?;
 
         // Build claims
-        Self {
+        Ok(Self {
             iss,
             sub: String::new(),
             aud,
-            exp: get_current_timestamp() + *TOKEN_LIFETIME,
+            exp: get_current_timestamp() + request_token_lifetime()?,
             iat: get_current_timestamp() as usize,
-        }
+            typ: Self::TOKEN_TYPE.to_string(),
+            sid: String::new(),
+        })
     }
 }
 
@@ -256,32 +370,74 @@ impl<S> FromRequestParts<S> for AuthRequestClaims
 where
     S: Sync,
 {
-    type Rejection = AuthError;
+    type Rejection = AppError;
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        from_request_parts::<AuthRequestClaims>(parts).await
+        let claims = from_request_parts::<AuthRequestClaims>(parts).await?;
+        Span::current().record("user.sub", claims.sub.as_str());
+        Ok(claims)
     }
 }
 
-#[derive(Debug)]
-pub struct AuthError(crate::types::auth::AuthError);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Signing key, audience, issuer and lifetimes the claims below are built and checked against,
+    // set to the same values by each test
+    fn jwt_env() {
+        std::env::set_var("JWT_SECRET", "test-secret");
+        std::env::set_var("JWT_AUDIENCE", "rustenv-test");
+        std::env::set_var("JWT_ISSUER", "rustenv-test");
+        std::env::set_var("AUTH_TOKEN_EXPIRY", "300");
+        std::env::set_var("AUTH_REQUEST_TOKEN_EXPIRY", "3600");
+    }
+
+    fn request_claims() -> AuthRequestClaims {
+        AuthRequestClaims {
+            sub: "user".to_string(),
+            sid: "session".to_string(),
+            ..JWTClaims::default().unwrap()
+        }
+    }
 
-impl AuthError {
-    pub fn from_type(error_type: AuthErrorType) -> Self {
-        Self { 0: crate::types::auth::AuthError::from_type(error_type) }
+    fn access_claims() -> AuthClaims {
+        AuthClaims {
+            sub: "user".to_string(),
+            sid: Some("session".to_string()),
+            ..JWTClaims::default().unwrap()
+        }
     }
 
-    pub fn status(&self) -> StatusCode {
-        self.0.status.to_owned()
+    #[test]
+    fn tokens_decode_as_their_own_type() {
+        jwt_env();
+        let request = request_claims().generate_token().unwrap();
+        let access = access_claims().generate_token().unwrap();
+
+        assert_eq!(AuthRequestClaims::from_string(&request.access_token).unwrap().sid, "session");
+        assert_eq!(AuthClaims::from_string(&access.access_token).unwrap().sub, "user");
     }
 
-    pub fn body(&self) -> AuthErrorBody {
-        self.0.body.to_owned()
+    #[test]
+    fn access_token_is_not_a_request_token() {
+        jwt_env();
+        let access = access_claims().generate_token().unwrap();
+
+        assert!(matches!(
+            AuthRequestClaims::from_string(&access.access_token),
+            Err(AppError::Auth(AuthErrorType::InvalidToken))
+        ));
     }
-}
 
-impl IntoResponse for AuthError {
-    fn into_response(self) -> Response<Body> {
-        (self.status(), Json(json!(self.body()))).into_response()
+    #[test]
+    fn request_token_is_not_an_access_token() {
+        jwt_env();
+        let request = request_claims().generate_token().unwrap();
+
+        assert!(matches!(
+            AuthClaims::from_string(&request.access_token),
+            Err(AppError::Auth(AuthErrorType::InvalidToken))
+        ));
     }
 }
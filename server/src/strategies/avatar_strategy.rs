@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+use bytes::Bytes;
+use image::imageops::FilterType;
+use image::{ImageFormat, ImageReader, Limits};
+use jsonwebtoken::get_current_timestamp;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::config::env_parse_opt;
+use crate::error::AppError;
+use crate::storage::{self, BlobStore};
+use crate::types::media::{AVATAR_SIZES, AvatarFormat, AvatarUrls};
+
+// Upload size limit when AVATAR_MAX_BYTES is unset
+const DEFAULT_MAX_UPLOAD_BYTES: usize = 5 * 1024 * 1024;
+
+// Largest source image edge accepted, guarding against decompression bombs
+const MAX_SOURCE_DIMENSION: u32 = 4096;
+
+// Decoder allocation limit
+const MAX_DECODE_ALLOC: u64 = 128 * 1024 * 1024;
+
+// Avatar upload size limit from AVATAR_MAX_BYTES
+pub fn max_upload_bytes() -> usize {
+    match env_parse_opt::<usize>("AVATAR_MAX_BYTES") {
+        Ok(bytes) => bytes.unwrap_or(DEFAULT_MAX_UPLOAD_BYTES),
+        Err(error) => {
+            tracing::error!(%error, "Invalid avatar size limit, using default");
+            DEFAULT_MAX_UPLOAD_BYTES
+        }
+    }
+}
+
+// Avatar output format from AVATAR_FORMAT (webp or png)
+pub fn avatar_format() -> Result<AvatarFormat, AppError> {
+    Ok(env_parse_opt::<AvatarFormat>("AVATAR_FORMAT")?.unwrap_or_default())
+}
+
+// Key of avatar thumbnail at size, stored next to the avatar key's stem
+fn sized_key(avatar_key: &str, size: u32) -> String {
+    match avatar_key.rsplit_once('.') {
+        Some((stem, extension)) => format!("{}/{}.{}", stem, size, extension),
+        None => format!("{}/{}", avatar_key, size),
+    }
+}
+
+// Decode uploaded image and re-encode it as square thumbnails, one per avatar size
+pub fn resize_avatar(data: &[u8], format: AvatarFormat) -> Result<Vec<(u32, Vec<u8>)>, AppError> {
+    let invalid = || {
+        AppError::Rejected(
+            http::StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Avatar must be a JPEG, PNG or WebP image".to_string(),
+        )
+    };
+
+    // Sniff the actual format rather than trusting the declared content type
+    let source_format = image::guess_format(data).map_err(|_| invalid())?;
+    if !matches!(source_format, ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP) {
+        return Err(invalid());
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_SOURCE_DIMENSION);
+    limits.max_image_height = Some(MAX_SOURCE_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_ALLOC);
+    let mut reader = ImageReader::with_format(Cursor::new(data), source_format);
+    reader.limits(limits);
+    let image = reader.decode().map_err(|error| {
+        AppError::Rejected(
+            http::StatusCode::UNPROCESSABLE_ENTITY,
+            format!("Avatar image could not be decoded: {}", error),
+        )
+    })?;
+
+    AVATAR_SIZES
+        .iter()
+        .map(|&size| {
+            // WebP encoding supports 8-bit RGBA only
+            let thumbnail = image.resize_to_fill(size, size, FilterType::Lanczos3).into_rgba8();
+            let mut encoded = Cursor::new(Vec::new());
+            thumbnail
+                .write_to(&mut encoded, format.image_format())
+                .map_err(|error| AppError::Internal(format!("Error encoding avatar: {}", error)))?;
+            Ok((size, encoded.into_inner()))
+        })
+        .collect()
+}
+
+// Resize and store uploaded avatar for user, returning its avatar key
+#[instrument(skip_all, fields(user.uuid = %uuid), err(level = "debug"))]
+pub async fn store_avatar(
+    store: &dyn BlobStore,
+    uuid: &str,
+    data: Bytes,
+) -> Result<String, AppError> {
+    // Decoding and resizing is CPU bound
+    let format = avatar_format()?;
+    let thumbnails = tokio::task::spawn_blocking(move || resize_avatar(&data, format))
+        .await
+        .map_err(|error| AppError::Internal(format!("Avatar processing failed: {}", error)))??;
+
+    // New key per upload so cached URLs of the previous avatar never show the new one
+    let avatar_key = format!("avatars/{}/{}.{}", uuid, Uuid::new_v4(), format.extension());
+    for (size, thumbnail) in thumbnails {
+        let key = sized_key(&avatar_key, size);
+        if let Err(error) = store.put(&key, Bytes::from(thumbnail), format.content_type()).await {
+            delete_avatar(store, &avatar_key).await;
+            return Err(error);
+        }
+    }
+    Ok(avatar_key)
+}
+
+// Delete stored avatar thumbnails, logging failures
+pub async fn delete_avatar(store: &dyn BlobStore, avatar_key: &str) {
+    for size in AVATAR_SIZES {
+        let key = sized_key(avatar_key, size);
+        if let Err(error) = store.delete(&key).await {
+            tracing::warn!(%error, key, "Error deleting avatar");
+        }
+    }
+}
+
+// Signed URLs for each size of a stored avatar
+pub fn avatar_urls(store: &dyn BlobStore, avatar_key: &str) -> Result<AvatarUrls, AppError> {
+    let expires_in = storage::url_expiry();
+    let urls = AVATAR_SIZES
+        .iter()
+        .map(|&size| Ok((size, store.signed_url(&sized_key(avatar_key, size), expires_in)?)))
+        .collect::<Result<BTreeMap<_, _>, AppError>>()?;
+    Ok(AvatarUrls { urls, expires_at: (get_current_timestamp() + expires_in.as_secs()) as i64 })
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+    use image::{ImageBuffer, Rgba};
+
+    use super::*;
+
+    // PNG image of size filled with one colour
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = ImageBuffer::from_pixel(width, height, Rgba([200_u8, 80, 40, 255]));
+        let mut encoded = Cursor::new(Vec::new());
+        image.write_to(&mut encoded, ImageFormat::Png).unwrap();
+        encoded.into_inner()
+    }
+
+    #[test]
+    fn resize_avatar_makes_each_size() {
+        let thumbnails = resize_avatar(&png(300, 200), AvatarFormat::Png).unwrap();
+        let sizes: Vec<u32> = thumbnails.iter().map(|(size, _)| *size).collect();
+        assert_eq!(sizes, AVATAR_SIZES);
+
+        for (size, data) in thumbnails {
+            let thumbnail = image::load_from_memory(&data).unwrap();
+            assert_eq!((thumbnail.width(), thumbnail.height()), (size, size));
+        }
+    }
+
+    #[test]
+    fn resize_avatar_rejects_unsupported_format() {
+        let gif = b"GIF89a\x01\x00\x01\x00\x00\x00\x00;";
+        assert!(matches!(
+            resize_avatar(gif, AvatarFormat::Webp),
+            Err(AppError::Rejected(StatusCode::UNSUPPORTED_MEDIA_TYPE, _))
+        ));
+        assert!(matches!(
+            resize_avatar(b"not an image", AvatarFormat::Webp),
+            Err(AppError::Rejected(StatusCode::UNSUPPORTED_MEDIA_TYPE, _))
+        ));
+    }
+
+    #[test]
+    fn resize_avatar_rejects_oversized_dimensions() {
+        for (width, height) in [(MAX_SOURCE_DIMENSION + 1, 1), (1, MAX_SOURCE_DIMENSION + 1)] {
+            assert!(matches!(
+                resize_avatar(&png(width, height), AvatarFormat::Webp),
+                Err(AppError::Rejected(StatusCode::UNPROCESSABLE_ENTITY, _))
+            ));
+        }
+    }
+
+    #[test]
+    fn sized_key_keeps_extension() {
+        assert_eq!(sized_key("avatars/u/id.webp", 64), "avatars/u/id/64.webp");
+        assert_eq!(sized_key("avatars/u/id", 64), "avatars/u/id/64");
+    }
+}
@@ -1,2 +1,6 @@
+pub mod audit_strategy;
 pub mod auth_strategy;
+pub mod avatar_strategy;
+pub mod session_strategy;
+pub mod two_factor_strategy;
 pub mod user_strategy;
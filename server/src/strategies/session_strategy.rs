@@ -0,0 +1,231 @@
+use jsonwebtoken::get_current_timestamp;
+use sqlx::any::Any;
+use sqlx::{AnyConnection, Executor};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::query::SqlBuilder;
+use crate::strategies::audit_strategy::{self, AuditContext};
+use crate::types::audit::{AuditEventType, AuditOutcome};
+use crate::types::auth::AuthErrorType;
+use crate::types::session::Session;
+
+// Insert session for user, recording the client address and user agent from the audit context
+#[instrument(skip_all, fields(db.operation = "INSERT", db.table = "sessions", user.uuid = %user_uuid), err(level = "debug"))]
+pub async fn insert_db_session<'e, E>(
+    executor: E,
+    audit: &AuditContext,
+    user_uuid: &str,
+    expires_at: i64,
+) -> Result<Session, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    let now = get_current_timestamp() as i64;
+    sqlx::query_as::<_, Session>(
+        r#"
+            INSERT INTO "sessions" (uuid, user_uuid, ip, user_agent, created_at, last_used_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $5, $6)
+            RETURNING *
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(user_uuid.to_string())
+    .bind(audit.ip.clone())
+    .bind(audit.user_agent.clone())
+    .bind(now)
+    .bind(expires_at)
+    .fetch_one(executor)
+    .await
+    .map_err(AppError::from)
+}
+
+// Record use of active session, rejecting revoked and expired sessions
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "sessions", session.uuid = %uuid), err(level = "debug"))]
+pub async fn touch_db_session<'e, E>(
+    executor: E,
+    uuid: &str,
+    user_uuid: &str,
+) -> Result<(), AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    let now = get_current_timestamp() as i64;
+    let result = sqlx::query(
+        r#"
+            UPDATE "sessions"
+            SET last_used_at = $3
+            WHERE uuid = $1 AND user_uuid = $2 AND revoked_at IS NULL AND expires_at > $3
+        "#,
+    )
+    .bind(uuid.to_string())
+    .bind(user_uuid.to_string())
+    .bind(now)
+    .execute(executor)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::Auth(AuthErrorType::InvalidToken));
+    }
+    Ok(())
+}
+
+// Whether session of user is neither revoked nor expired
+#[instrument(skip_all, fields(db.operation = "SELECT", db.table = "sessions", session.uuid = %uuid), err(level = "debug"))]
+pub async fn is_db_session_active<'e, E>(
+    executor: E,
+    uuid: &str,
+    user_uuid: &str,
+) -> Result<bool, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    let count: i64 = sqlx::query_scalar(
+        r#"
+            SELECT COUNT(*) FROM "sessions"
+            WHERE uuid = $1 AND user_uuid = $2 AND revoked_at IS NULL AND expires_at > $3
+        "#,
+    )
+    .bind(uuid.to_string())
+    .bind(user_uuid.to_string())
+    .bind(get_current_timestamp() as i64)
+    .fetch_one(executor)
+    .await?;
+    Ok(count > 0)
+}
+
+// Get active sessions of user, most recently used first
+#[instrument(skip_all, fields(db.operation = "SELECT", db.table = "sessions", user.uuid = %user_uuid), err(level = "debug"))]
+pub async fn get_db_user_sessions<'e, E>(
+    executor: E,
+    user_uuid: &str,
+) -> Result<Vec<Session>, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    sqlx::query_as::<_, Session>(
+        r#"
+            SELECT * FROM "sessions"
+            WHERE user_uuid = $1 AND revoked_at IS NULL AND expires_at > $2
+            ORDER BY last_used_at DESC, id DESC
+        "#,
+    )
+    .bind(user_uuid.to_string())
+    .bind(get_current_timestamp() as i64)
+    .fetch_all(executor)
+    .await
+    .map_err(AppError::from)
+}
+
+// Get every stored session of user, including ended sessions not yet purged, oldest first
+#[instrument(skip_all, fields(db.operation = "SELECT", db.table = "sessions", user.uuid = %user_uuid), err(level = "debug"))]
+pub async fn get_db_stored_user_sessions<'e, E>(
+    executor: E,
+    user_uuid: &str,
+) -> Result<Vec<Session>, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    sqlx::query_as::<_, Session>(
+        r#"
+            SELECT * FROM "sessions"
+            WHERE user_uuid = $1
+            ORDER BY id
+        "#,
+    )
+    .bind(user_uuid.to_string())
+    .fetch_all(executor)
+    .await
+    .map_err(AppError::from)
+}
+
+// Revoke active session of user, recording it as event type (logout or session revocation)
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "sessions", session.uuid = %uuid), err(level = "debug"))]
+pub async fn revoke_db_session(
+    connection: &mut AnyConnection,
+    audit: &AuditContext,
+    event_type: AuditEventType,
+    user_uuid: &str,
+    uuid: &str,
+) -> Result<(), AppError> {
+    let now = get_current_timestamp() as i64;
+    let result = sqlx::query(
+        r#"
+            UPDATE "sessions"
+            SET revoked_at = $3
+            WHERE uuid = $1 AND user_uuid = $2 AND revoked_at IS NULL AND expires_at > $3
+        "#,
+    )
+    .bind(uuid.to_string())
+    .bind(user_uuid.to_string())
+    .bind(now)
+    .execute(&mut *connection)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Session not found".to_string()));
+    }
+
+    // Record revocation on the same connection
+    let event = audit.event(event_type, AuditOutcome::Success).target(uuid);
+    audit_strategy::insert_audit_event(connection, &event).await
+}
+
+// Revoke all active sessions of user except the one given, returning the number revoked
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "sessions", user.uuid = %user_uuid), err(level = "debug"))]
+pub async fn revoke_db_user_sessions<'e, E>(
+    executor: E,
+    user_uuid: &str,
+    except: Option<&str>,
+) -> Result<u64, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    let mut builder = SqlBuilder::new(r#"UPDATE "sessions" SET revoked_at = "#);
+    builder
+        .push_bind(get_current_timestamp() as i64)
+        .push(" WHERE user_uuid = ")
+        .push_bind(user_uuid.to_string())
+        .push(" AND revoked_at IS NULL");
+    if let Some(except) = except {
+        builder.push(" AND uuid <> ").push_bind(except.to_string());
+    }
+    let (sql, arguments) = builder.build()?;
+    let result = sqlx::query_with(&sql, arguments).execute(executor).await?;
+    Ok(result.rows_affected())
+}
+
+// Delete sessions that expired or were revoked before cutoff, returning the number deleted
+#[instrument(skip_all, fields(db.operation = "DELETE", db.table = "sessions"), err(level = "debug"))]
+pub async fn purge_db_sessions_before<'e, E>(executor: E, cutoff: i64) -> Result<u64, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    let result = sqlx::query(
+        r#"
+            DELETE FROM "sessions"
+            WHERE expires_at < $1 OR revoked_at < $1
+        "#,
+    )
+    .bind(cutoff)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+// Delete all sessions of user, returning the number deleted
+#[instrument(skip_all, fields(db.operation = "DELETE", db.table = "sessions", user.uuid = %user_uuid), err(level = "debug"))]
+pub async fn delete_db_user_sessions<'e, E>(executor: E, user_uuid: &str) -> Result<u64, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    let result = sqlx::query(
+        r#"
+            DELETE FROM "sessions"
+            WHERE user_uuid = $1
+        "#,
+    )
+    .bind(user_uuid.to_string())
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected())
+}
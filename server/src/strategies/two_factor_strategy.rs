@@ -0,0 +1,173 @@
+use jsonwebtoken::get_current_timestamp;
+use sqlx::any::Any;
+use sqlx::{AnyConnection, Executor};
+use totp_rs::{Algorithm, Secret, TOTP};
+use tracing::instrument;
+
+use crate::config::{env_parse_opt, env_var};
+use crate::error::AppError;
+use crate::strategies::audit_strategy::{self, AuditContext};
+use crate::types::audit::{AuditEventType, AuditOutcome};
+use crate::types::two_factor::TwoFactorSetup;
+use crate::types::user::User;
+
+// Authenticator code length
+const DIGITS: usize = 6;
+
+// Seconds each code is valid for
+const STEP: u64 = 30;
+
+// Steps either side of the current one accepted, allowing for clock drift
+const SKEW: u8 = 1;
+
+// Issuer shown in authenticator apps, TOTP_ISSUER or the JWT issuer
+fn issuer() -> Result<String, AppError> {
+    match env_parse_opt::<String>("TOTP_ISSUER")? {
+        Some(issuer) => Ok(issuer),
+        None => env_var("JWT_ISSUER"),
+    }
+}
+
+// TOTP generator for secret bytes, labelled with account name and checking single steps
+fn totp(secret: Vec<u8>, account_name: &str) -> Result<TOTP, AppError> {
+    TOTP::new(Algorithm::SHA1, DIGITS, 0, STEP, secret, Some(issuer()?), account_name.to_string())
+        .map_err(|error| AppError::Internal(format!("Error creating TOTP: {}", error)))
+}
+
+// Generate new authenticator secret for account name
+pub fn generate_setup(account_name: &str) -> Result<TwoFactorSetup, AppError> {
+    let secret = Secret::generate_secret()
+        .to_bytes()
+        .map_err(|error| AppError::Internal(format!("Error generating TOTP secret: {}", error)))?;
+    let totp = totp(secret, account_name)?;
+    Ok(TwoFactorSetup { secret: totp.get_secret_base32(), otpauth_url: totp.get_url() })
+}
+
+// Time step code belongs to if it is current for base32 secret and newer than the last accepted step
+pub fn verify_code(
+    secret: &str,
+    account_name: &str,
+    code: &str,
+    last_step: Option<i64>,
+) -> Result<Option<i64>, AppError> {
+    let secret = Secret::Encoded(secret.to_string())
+        .to_bytes()
+        .map_err(|error| AppError::Internal(format!("Invalid stored TOTP secret: {}", error)))?;
+    let totp = totp(secret, account_name)?;
+
+    // Try the newest steps first, so a code valid for several steps claims the latest
+    let current = (get_current_timestamp() / STEP) as i64;
+    let step = (current - i64::from(SKEW)..=current + i64::from(SKEW))
+        .rev()
+        .filter(|step| last_step.is_none_or(|last_step| *step > last_step))
+        .find(|step| totp.check(code, *step as u64 * STEP));
+    Ok(step)
+}
+
+// Accept code of user for secret at most once, recording its time step so it cannot be replayed
+pub async fn accept_code<'e, E>(
+    executor: E,
+    user: &User,
+    secret: &str,
+    code: &str,
+) -> Result<bool, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    match verify_code(secret, &user.username, code, user.totp_last_step)? {
+        Some(step) => set_db_user_totp_step(executor, &user.uuid, step).await,
+        None => Ok(false),
+    }
+}
+
+// Record accepted time step of database user by UUID, returning false when it was already used
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "users", user.uuid = %uuid), err(level = "debug"))]
+async fn set_db_user_totp_step<'e, E>(executor: E, uuid: &str, step: i64) -> Result<bool, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    let result = sqlx::query(
+        r#"
+            UPDATE "users"
+            SET totp_last_step = $2
+            WHERE uuid = $1 AND (totp_last_step IS NULL OR totp_last_step < $2)
+        "#,
+    )
+    .bind(uuid.to_string())
+    .bind(step)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+// Store pending authenticator secret of database user by UUID
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "users", user.uuid = %uuid), err(level = "debug"))]
+pub async fn set_db_user_totp_secret<'e, E>(
+    executor: E,
+    uuid: String,
+    secret: String,
+) -> Result<(), AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    let result = sqlx::query(
+        r#"
+            UPDATE "users"
+            SET totp_secret = $2, updated_at = $3
+            WHERE uuid = $1 AND deleted_at IS NULL AND totp_enabled_at IS NULL
+        "#,
+    )
+    .bind(uuid)
+    .bind(secret)
+    .bind(get_current_timestamp() as i64)
+    .execute(executor)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("User not found".to_string()));
+    }
+    Ok(())
+}
+
+// Enable two-factor authentication with the pending secret, or disable it and drop the secret
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "users", user.uuid = %uuid), err(level = "debug"))]
+pub async fn set_db_user_two_factor(
+    connection: &mut AnyConnection,
+    audit: &AuditContext,
+    uuid: String,
+    enabled: bool,
+) -> Result<User, AppError> {
+    let now = get_current_timestamp() as i64;
+    let sql = match enabled {
+        true => {
+            r#"
+                UPDATE "users"
+                SET totp_enabled_at = $2, updated_at = $2
+                WHERE uuid = $1 AND deleted_at IS NULL AND totp_secret IS NOT NULL
+                RETURNING *
+            "#
+        }
+        false => {
+            r#"
+                UPDATE "users"
+                SET totp_secret = NULL, totp_enabled_at = NULL, updated_at = $2
+                WHERE uuid = $1 AND deleted_at IS NULL
+                RETURNING *
+            "#
+        }
+    };
+    let user = sqlx::query_as::<_, User>(sql)
+        .bind(uuid.to_owned())
+        .bind(now)
+        .fetch_optional(&mut *connection)
+        .await?
+        .ok_or(AppError::NotFound("User not found".to_string()))?;
+
+    // Record state change on the same connection
+    let event_type = match enabled {
+        true => AuditEventType::TwoFactorEnable,
+        false => AuditEventType::TwoFactorDisable,
+    };
+    let event = audit.event(event_type, AuditOutcome::Success).target(uuid);
+    audit_strategy::insert_audit_event(connection, &event).await?;
+    Ok(user)
+}
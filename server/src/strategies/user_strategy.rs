@@ -1,65 +1,251 @@
-use std::env;
-
 use bcrypt::{DEFAULT_COST, hash_with_salt};
-use sqlx::any::{AnyQueryResult, AnyRow};
+use http::StatusCode;
+use jsonwebtoken::get_current_timestamp;
+use sqlx::any::{Any, AnyQueryResult, AnyRow};
+use sqlx::{AnyConnection, Connection, Executor, FromRow, Row};
+use tracing::instrument;
 use uuid::Uuid;
 
-use crate::pool::get_pool;
-use crate::types::user::{User, UserRegister};
+use crate::config::env_var;
+use crate::error::AppError;
+use crate::query::SqlBuilder;
+use crate::strategies::audit_strategy::{self, AuditContext};
+use crate::strategies::session_strategy;
+use crate::types::audit::{AuditEventType, AuditOutcome};
+use crate::types::pagination::{DEFAULT_PAGE_LIMIT, SortOrder, order_sql};
+use crate::types::user::{
+    CursorValue, USER_INFORMATION_COLUMNS, User, UserCursor, UserInformation, UserListQuery,
+    UserProfileUpdate, UserRegister, UserSort, sort_column,
+};
 
-// Get all database users
-pub async fn get_db_users() -> Result<Vec<User>, sqlx::Error> {
-    sqlx::query_as::<_, User>(
-        r#"
-            SELECT * FROM \"users\"
-        "#,
-    )
-    .fetch_all(&get_pool())
-    .await
+// Hash password with PASSWORD_SALT
+fn hash_password(password: String) -> Result<String, AppError> {
+    // Get 16-byte salt
+    let salt: [u8; 16] = env_var("PASSWORD_SALT")?
+        .as_bytes()
+        .get(0..16)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(AppError::Internal("PASSWORD_SALT must be at least 16 bytes".to_string()))?;
+
+    // Hash password
+    match hash_with_salt(password, DEFAULT_COST, salt) {
+        Ok(hashed_password) => Ok(hashed_password.to_string()),
+        Err(error) => Err(AppError::Internal(format!("Error hashing password: {}", error))),
+    }
+}
+
+// Page of user information with cursors for the neighbouring pages
+pub struct UserPage {
+    pub users: Vec<UserInformation>,
+    pub total: i64,
+    pub next_cursor: Option<UserCursor>,
+    pub prev_cursor: Option<UserCursor>,
+}
+
+// Get page of database users matching list query, projecting user information columns
+#[instrument(skip_all, fields(db.operation = "SELECT", db.table = "users"), err(level = "debug"))]
+pub async fn get_db_user_page(
+    connection: &mut AnyConnection,
+    query: &UserListQuery,
+) -> Result<UserPage, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+    let sort = query.sort.unwrap_or_default();
+    let order = query.order.unwrap_or_default();
+    let column = sort_column(sort);
+
+    // Decode cursor, which must match the requested sort
+    let cursor = match &query.cursor {
+        Some(encoded) => Some(
+            UserCursor::decode(encoded)
+                .filter(|cursor| cursor.sort == sort && cursor.order == order)
+                .ok_or(AppError::Rejected(
+                    StatusCode::BAD_REQUEST,
+                    "Invalid pagination cursor".to_string(),
+                ))?,
+        ),
+        None => None,
+    };
+    let backward = cursor.as_ref().is_some_and(|cursor| cursor.backward);
+
+    // Count all matching users
+    let mut count = SqlBuilder::new(r#"SELECT COUNT(*) FROM "users" WHERE deleted_at IS NULL"#);
+    push_user_filters(&mut count, query);
+    let (sql, arguments) = count.build()?;
+    let total: i64 = sqlx::query_scalar_with(&sql, arguments).fetch_one(&mut *connection).await?;
+
+    // Walk backwards in reverse order for previous pages
+    let direction = if backward { order.reverse() } else { order };
+    let mut page = SqlBuilder::new(format!(
+        r#"SELECT id, {} FROM "users" WHERE deleted_at IS NULL"#,
+        USER_INFORMATION_COLUMNS
+    ));
+    push_user_filters(&mut page, query);
+    if let Some(cursor) = &cursor {
+        let comparison = match direction {
+            SortOrder::Asc => ">",
+            SortOrder::Desc => "<",
+        };
+        page.push(format!(" AND ({} {} ", column, comparison));
+        push_cursor_value(&mut page, &cursor.value);
+        page.push(format!(" OR ({} = ", column));
+        push_cursor_value(&mut page, &cursor.value);
+        page.push(format!(" AND id {} ", comparison)).push_bind(cursor.id).push("))");
+    }
+
+    // Fetch one extra row to detect a further page
+    page.push(format!(
+        " ORDER BY {} {}, id {} LIMIT ",
+        column,
+        order_sql(direction),
+        order_sql(direction)
+    ))
+    .push_bind(i64::from(limit) + 1);
+    if let Some(offset) = query.offset {
+        page.push(" OFFSET ").push_bind(offset as i64);
+    }
+    let (sql, arguments) = page.build()?;
+    let mut rows = sqlx::query_with(&sql, arguments).fetch_all(connection).await?;
+
+    let has_more = rows.len() > limit as usize;
+    rows.truncate(limit as usize);
+    if backward {
+        rows.reverse();
+    }
+
+    // Build cursors from the first and last rows
+    let cursor_at = |row: &AnyRow, backward: bool| -> Result<UserCursor, AppError> {
+        let value = match sort {
+            UserSort::CreatedAt => CursorValue::Int(row.try_get("created_at")?),
+            _ => CursorValue::Text(row.try_get(column)?),
+        };
+        Ok(UserCursor { sort, order, value, id: row.try_get("id")?, backward })
+    };
+    let next_cursor = match rows.last() {
+        Some(row) if backward || has_more => Some(cursor_at(row, false)?),
+        _ => None,
+    };
+    let prev_cursor = match rows.first() {
+        Some(row) if (backward && has_more) || (!backward && cursor.is_some()) => {
+            Some(cursor_at(row, true)?)
+        }
+        _ => None,
+    };
+
+    let users = rows.iter().map(UserInformation::from_row).collect::<Result<Vec<_>, _>>()?;
+    Ok(UserPage { users, total, next_cursor, prev_cursor })
 }
 
-// Get database user by identifier (username or email)
-pub async fn get_db_user_by_identifier(identifier: String) -> Result<User, sqlx::Error> {
+// Append user listing filters to query
+fn push_user_filters(builder: &mut SqlBuilder, query: &UserListQuery) {
+    if let Some(search) = &query.search {
+        let pattern = contains_pattern(search);
+        builder
+            .push(" AND (LOWER(username) LIKE ")
+            .push_bind(pattern.clone())
+            .push(r" ESCAPE '\' OR LOWER(email) LIKE ")
+            .push_bind(pattern.clone())
+            .push(r" ESCAPE '\' OR LOWER(display_name) LIKE ")
+            .push_bind(pattern)
+            .push(r" ESCAPE '\')");
+    }
+    if let Some(username) = &query.username {
+        builder
+            .push(" AND LOWER(username) LIKE ")
+            .push_bind(contains_pattern(username))
+            .push(r" ESCAPE '\'");
+    }
+    if let Some(email) = &query.email {
+        builder
+            .push(" AND LOWER(email) LIKE ")
+            .push_bind(contains_pattern(email))
+            .push(r" ESCAPE '\'");
+    }
+    if let Some(is_admin) = query.is_admin {
+        builder.push(" AND is_admin = ").push_bind(is_admin);
+    }
+    if let Some(created_after) = query.created_after {
+        builder.push(" AND created_at >= ").push_bind(created_after);
+    }
+    if let Some(created_before) = query.created_before {
+        builder.push(" AND created_at < ").push_bind(created_before);
+    }
+}
+
+// Bind cursor sort key value
+fn push_cursor_value(builder: &mut SqlBuilder, value: &CursorValue) {
+    match value {
+        CursorValue::Int(value) => builder.push_bind(*value),
+        CursorValue::Text(value) => builder.push_bind(value.to_owned()),
+    };
+}
+
+// Case-insensitive substring LIKE pattern with wildcards escaped
+fn contains_pattern(value: &str) -> String {
+    let escaped =
+        value.to_lowercase().replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+// Get database user by identifier (username or email), including disabled but not deleted users
+#[instrument(skip_all, fields(db.operation = "SELECT", db.table = "users"), err(level = "debug"))]
+pub async fn get_db_user_by_identifier<'e, E>(
+    executor: E,
+    identifier: String,
+) -> Result<User, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
     sqlx::query_as::<_, User>(
         r#"
-            SELECT * FROM \"users\"
-            WHERE username = $1 OR email = $1
+            SELECT * FROM "users"
+            WHERE (username = $1 OR email = $1) AND deleted_at IS NULL
         "#,
     )
     .bind(identifier)
-    .fetch_one(&get_pool())
+    .fetch_one(executor)
     .await
+    .map_err(AppError::from)
 }
 
-// Get database user by UUID
-pub async fn get_db_user_by_uuid(uuid: String) -> Result<User, sqlx::Error> {
+// Get database user by UUID, including disabled but not deleted users
+#[instrument(skip_all, fields(db.operation = "SELECT", db.table = "users", user.uuid = %uuid), err(level = "debug"))]
+pub async fn get_db_user_by_uuid<'e, E>(executor: E, uuid: String) -> Result<User, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
     sqlx::query_as::<_, User>(
         r#"
-            SELECT * FROM \"users\"
-            WHERE uuid = $1
+            SELECT * FROM "users"
+            WHERE uuid = $1 AND deleted_at IS NULL
         "#,
     )
     .bind(uuid)
-    .fetch_one(&get_pool())
+    .fetch_one(executor)
     .await
+    .map_err(AppError::from)
 }
 
 // Insert database user to database
-pub async fn insert_db_user(user_register: UserRegister) -> Result<User, sqlx::Error> {
+#[instrument(skip_all, fields(db.operation = "INSERT", db.table = "users"), err(level = "debug"))]
+pub async fn insert_db_user<'e, E>(
+    executor: E,
+    user_register: UserRegister,
+) -> Result<User, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
     // Create uuid
     let id = Uuid::new_v4();
 
-    // Generate salt and hash password
-    let mut salt: [u8; 16] = [0; 16];
-    salt.copy_from_slice(&env::var("PASSWORD_SALT").unwrap().as_bytes()[0..16]);
-    let hashed_password =
-        hash_with_salt(user_register.password, DEFAULT_COST, salt).unwrap().to_string();
+    // Hash password
+    let hashed_password = hash_password(user_register.password)?;
 
     // Query database
+    let now = get_current_timestamp() as i64;
     sqlx::query_as::<_, User>(
         r#"
-            INSERT INTO \"users\" (uuid, username, password, email, is_admin)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO "users" (uuid, username, password, email, is_admin, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $6)
             RETURNING *
         "#,
     )
@@ -68,46 +254,277 @@ pub async fn insert_db_user(user_register: UserRegister) -> Result<User, sqlx::E
     .bind(hashed_password)
     .bind(user_register.email)
     .bind(false)
-    .fetch_one(&get_pool())
+    .bind(now)
+    .fetch_one(executor)
     .await
+    .map_err(AppError::from)
 }
 
-// Update database user in database
-pub async fn update_db_user(user: User) -> Result<AnyRow, sqlx::Error> {
-    // Generate salt and hash password
-    let mut salt: [u8; 16] = [0; 16];
-    salt.copy_from_slice(&env::var("PASSWORD_SALT").unwrap().as_bytes()[0..16]);
-    let hashed_password = hash_with_salt(user.password, DEFAULT_COST, salt).unwrap().to_string();
+// Update database user password by UUID
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "users", user.uuid = %uuid), err(level = "debug"))]
+pub async fn update_db_user_password(
+    connection: &mut AnyConnection,
+    audit: &AuditContext,
+    uuid: String,
+    password: String,
+) -> Result<(), AppError> {
+    // Hash password
+    let hashed_password = hash_password(password)?;
 
     // Query database
-    sqlx::query(
+    let result = sqlx::query(
         r#"
-            UPDATE \"users\"
-            SET uuid = $2, username = $3, password = $4, email = $5, is_admin = $6
-            WHERE uuid = $1
-            RETURNING *
+            UPDATE "users"
+            SET password = $2, updated_at = $3
+            WHERE uuid = $1 AND deleted_at IS NULL
         "#,
     )
-    .bind(user.id)
-    .bind(user.uuid)
-    .bind(user.username)
+    .bind(uuid.to_owned())
     .bind(hashed_password)
-    .bind(user.email)
-    .bind(user.is_admin)
-    .fetch_one(&get_pool())
-    .await
+    .bind(get_current_timestamp() as i64)
+    .execute(&mut *connection)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("User not found".to_string()));
+    }
+
+    // Record password change on the same connection
+    let event = audit.event(AuditEventType::PasswordChange, AuditOutcome::Success).target(uuid);
+    audit_strategy::insert_audit_event(connection, &event).await
 }
 
-// Delete database user from database by UUID
-pub async fn delete_db_user_by_uuid(uuid: String) -> Result<AnyQueryResult, sqlx::Error> {
-    sqlx::query(
+// Update database user email by UUID
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "users", user.uuid = %uuid), err(level = "debug"))]
+pub async fn update_db_user_email(
+    connection: &mut AnyConnection,
+    audit: &AuditContext,
+    uuid: String,
+    email: String,
+) -> Result<User, AppError> {
+    let user = sqlx::query_as::<_, User>(
+        r#"
+            UPDATE "users"
+            SET email = $2, updated_at = $3
+            WHERE uuid = $1 AND deleted_at IS NULL
+            RETURNING *
+        "#,
+    )
+    .bind(uuid.to_owned())
+    .bind(email)
+    .bind(get_current_timestamp() as i64)
+    .fetch_optional(&mut *connection)
+    .await?
+    .ok_or(AppError::NotFound("User not found".to_string()))?;
+
+    // Record email change on the same connection
+    let event = audit.event(AuditEventType::EmailChange, AuditOutcome::Success).target(uuid);
+    audit_strategy::insert_audit_event(connection, &event).await?;
+    Ok(user)
+}
+
+// Record successful login time for database user by UUID
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "users", user.uuid = %uuid), err(level = "debug"))]
+pub async fn touch_db_user_last_login<'e, E>(executor: E, uuid: String) -> Result<User, AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    sqlx::query_as::<_, User>(
         r#"
-            DELETE FROM \"users\"
-            WHERE uuid = $1
+            UPDATE "users"
+            SET last_login_at = $2
+            WHERE uuid = $1 AND deleted_at IS NULL
             RETURNING *
         "#,
     )
     .bind(uuid)
-    .execute(&get_pool())
+    .bind(get_current_timestamp() as i64)
+    .fetch_one(executor)
     .await
+    .map_err(AppError::from)
+}
+
+// Apply partial profile update to database user by UUID, leaving absent fields unchanged
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "users", user.uuid = %uuid), err(level = "debug"))]
+pub async fn update_db_user_profile(
+    connection: &mut AnyConnection,
+    uuid: String,
+    update: UserProfileUpdate,
+) -> Result<User, AppError> {
+    // Nothing to change
+    if update.is_empty() {
+        return get_db_user_by_uuid(connection, uuid).await;
+    }
+
+    let mut builder = SqlBuilder::new(r#"UPDATE "users" SET updated_at = "#);
+    builder.push_bind(get_current_timestamp() as i64);
+    let fields = [
+        ("display_name", update.display_name),
+        ("avatar_url", update.avatar_url),
+        ("locale", update.locale),
+        ("timezone", update.timezone),
+    ];
+    for (column, value) in fields {
+        if let Some(value) = value {
+            builder.push(format!(", {} = ", column)).push_bind(value);
+        }
+    }
+    builder.push(" WHERE uuid = ").push_bind(uuid).push(" AND deleted_at IS NULL RETURNING *");
+
+    let (sql, arguments) = builder.build()?;
+    sqlx::query_as_with::<_, User, _>(&sql, arguments)
+        .fetch_one(connection)
+        .await
+        .map_err(AppError::from)
+}
+
+// Set or clear uploaded avatar key of database user by UUID
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "users", user.uuid = %uuid), err(level = "debug"))]
+pub async fn set_db_user_avatar_key<'e, E>(
+    executor: E,
+    uuid: String,
+    avatar_key: Option<String>,
+) -> Result<(), AppError>
+where
+    E: Executor<'e, Database = Any>,
+{
+    let result = sqlx::query(
+        r#"
+            UPDATE "users"
+            SET avatar_key = $2, updated_at = $3
+            WHERE uuid = $1 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(uuid)
+    .bind(avatar_key)
+    .bind(get_current_timestamp() as i64)
+    .execute(executor)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("User not found".to_string()));
+    }
+    Ok(())
+}
+
+// Soft delete database user by UUID, keeping the row until the retention period has passed
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "users", user.uuid = %uuid), err(level = "debug"))]
+pub async fn delete_db_user_by_uuid(
+    connection: &mut AnyConnection,
+    audit: &AuditContext,
+    uuid: String,
+) -> Result<AnyQueryResult, AppError> {
+    let result = sqlx::query(
+        r#"
+            UPDATE "users"
+            SET deleted_at = $2, updated_at = $2
+            WHERE uuid = $1 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(uuid.to_owned())
+    .bind(get_current_timestamp() as i64)
+    .execute(&mut *connection)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("User not found".to_string()));
+    }
+
+    // Record deletion on the same connection
+    let event = audit.event(AuditEventType::UserDelete, AuditOutcome::Success).target(uuid);
+    audit_strategy::insert_audit_event(connection, &event).await?;
+    Ok(result)
+}
+
+// Disable or re-enable database user by UUID
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "users", user.uuid = %uuid), err(level = "debug"))]
+pub async fn set_db_user_disabled(
+    connection: &mut AnyConnection,
+    audit: &AuditContext,
+    uuid: String,
+    disabled: bool,
+) -> Result<(), AppError> {
+    let now = get_current_timestamp() as i64;
+    let result = sqlx::query(
+        r#"
+            UPDATE "users"
+            SET disabled_at = $2, updated_at = $3
+            WHERE uuid = $1 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(uuid.to_owned())
+    .bind(disabled.then_some(now))
+    .bind(now)
+    .execute(&mut *connection)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("User not found".to_string()));
+    }
+
+    // Record state change on the same connection
+    let event_type = match disabled {
+        true => AuditEventType::UserDisable,
+        false => AuditEventType::UserEnable,
+    };
+    let event = audit.event(event_type, AuditOutcome::Success).target(uuid);
+    audit_strategy::insert_audit_event(connection, &event).await
+}
+
+// Grant or revoke admin role of database user by UUID
+#[instrument(skip_all, fields(db.operation = "UPDATE", db.table = "users", user.uuid = %uuid), err(level = "debug"))]
+pub async fn set_db_user_admin(
+    connection: &mut AnyConnection,
+    audit: &AuditContext,
+    uuid: String,
+    is_admin: bool,
+) -> Result<User, AppError> {
+    let user = sqlx::query_as::<_, User>(
+        r#"
+            UPDATE "users"
+            SET is_admin = $2, updated_at = $3
+            WHERE uuid = $1 AND deleted_at IS NULL
+            RETURNING *
+        "#,
+    )
+    .bind(uuid.to_owned())
+    .bind(is_admin)
+    .bind(get_current_timestamp() as i64)
+    .fetch_optional(&mut *connection)
+    .await?
+    .ok_or(AppError::NotFound("User not found".to_string()))?;
+
+    // Record role change on the same connection
+    let role = if is_admin { "admin" } else { "user" };
+    let event =
+        audit.event(AuditEventType::RoleChange, AuditOutcome::Success).target(uuid).detail(role);
+    audit_strategy::insert_audit_event(connection, &event).await?;
+    Ok(user)
+}
+
+// Permanently delete users soft deleted before cutoff along with their sessions and the client
+// details of their audit events, returning their UUIDs and avatar keys
+#[instrument(skip_all, fields(db.operation = "DELETE", db.table = "users"), err(level = "debug"))]
+pub async fn purge_db_users_deleted_before(
+    connection: &mut AnyConnection,
+    cutoff: i64,
+) -> Result<Vec<(String, Option<String>)>, AppError> {
+    // Delete and record in one transaction, so no user is purged without an audit trail
+    let mut transaction = connection.begin().await?;
+    let purged: Vec<(String, Option<String>)> = sqlx::query_as(
+        r#"
+            DELETE FROM "users"
+            WHERE deleted_at IS NOT NULL AND deleted_at < $1
+            RETURNING uuid, avatar_key
+        "#,
+    )
+    .bind(cutoff)
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    let audit = AuditContext::default();
+    for (uuid, _) in &purged {
+        session_strategy::delete_db_user_sessions(&mut *transaction, uuid).await?;
+        audit_strategy::anonymize_db_audit_events_by_actor(&mut *transaction, uuid).await?;
+        let event = audit.event(AuditEventType::UserPurge, AuditOutcome::Success).target(uuid);
+        audit_strategy::insert_audit_event(&mut *transaction, &event).await?;
+    }
+    transaction.commit().await?;
+    Ok(purged)
 }
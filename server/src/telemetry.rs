@@ -0,0 +1,102 @@
+use std::env;
+
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer, fmt};
+
+// Default log filter when RUST_LOG is not set
+const DEFAULT_FILTER: &str = "info,rustenv_server=debug,tower_http=debug";
+
+// Keeps telemetry exporters alive and flushes them when dropped
+pub struct TelemetryGuard {
+    #[cfg(feature = "otel")]
+    provider: Option<opentelemetry_sdk::trace::TracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otel")]
+        if let Some(provider) = self.provider.take() {
+            if let Err(error) = provider.shutdown() {
+                eprintln!("Error shutting down tracer provider: {:?}", error);
+            }
+        }
+    }
+}
+
+// Install global tracing subscriber
+//
+// LOG_FORMAT selects `json` or human-readable output and RUST_LOG overrides the log filter.
+// With the `otel` feature, spans are also exported over OTLP when
+// OTEL_EXPORTER_OTLP_ENDPOINT is set.
+pub fn init_telemetry(service_name: &'static str) -> TelemetryGuard {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let fmt_layer = match env::var("LOG_FORMAT").as_deref() {
+        Ok("json") => fmt::layer().json().with_current_span(true).with_span_list(false).boxed(),
+        _ => fmt::layer().boxed(),
+    };
+    let endpoint = env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok().filter(|url| !url.is_empty());
+
+    #[cfg(feature = "otel")]
+    {
+        let provider =
+            endpoint.and_then(|endpoint| match otel::tracer_provider(service_name, &endpoint) {
+                Ok(provider) => Some(provider),
+                Err(error) => {
+                    eprintln!("Error creating OTLP exporter for {}: {:?}", endpoint, error);
+                    None
+                }
+            });
+        let otel_layer = provider.as_ref().map(otel::layer);
+
+        tracing_subscriber::registry().with(filter).with(fmt_layer).with(otel_layer).init();
+        TelemetryGuard { provider }
+    }
+
+    #[cfg(not(feature = "otel"))]
+    {
+        tracing_subscriber::registry().with(filter).with(fmt_layer).init();
+        if endpoint.is_some() {
+            tracing::warn!(
+                service_name,
+                "OTEL_EXPORTER_OTLP_ENDPOINT is set but the otel feature is not enabled"
+            );
+        }
+        TelemetryGuard {}
+    }
+}
+
+#[cfg(feature = "otel")]
+mod otel {
+    use opentelemetry::KeyValue;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::trace::TracerProvider;
+    use opentelemetry_sdk::{Resource, runtime};
+    use tracing::Subscriber;
+    use tracing_subscriber::Layer;
+    use tracing_subscriber::registry::LookupSpan;
+
+    // Build tracer provider exporting spans to an OTLP collector
+    pub fn tracer_provider(
+        service_name: &'static str,
+        endpoint: &str,
+    ) -> Result<TracerProvider, opentelemetry::trace::TraceError> {
+        let exporter = SpanExporter::builder().with_tonic().with_endpoint(endpoint).build()?;
+        let provider = TracerProvider::builder()
+            .with_batch_exporter(exporter, runtime::Tokio)
+            .with_resource(Resource::new(vec![KeyValue::new("service.name", service_name)]))
+            .build();
+        opentelemetry::global::set_tracer_provider(provider.clone());
+        Ok(provider)
+    }
+
+    // Build tracing layer forwarding spans to tracer provider
+    pub fn layer<S>(provider: &TracerProvider) -> impl Layer<S>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        tracing_opentelemetry::layer().with_tracer(provider.tracer("rustenv_server"))
+    }
+}
@@ -0,0 +1,109 @@
+pub use rustenv_shared::audit::{
+    AuditEvent, AuditEventType, AuditOutcome, AuditQuery, ExportFormat,
+};
+
+// Audit event to record
+#[derive(Clone, Debug)]
+pub struct NewAuditEvent {
+    pub event_type: AuditEventType,
+    pub outcome: AuditOutcome,
+    pub actor_uuid: Option<String>,
+    pub target: Option<String>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub detail: Option<String>,
+}
+
+impl NewAuditEvent {
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+// CSV header row for audit export
+pub const AUDIT_CSV_HEADER: &str =
+    "id,event_type,outcome,actor_uuid,target,ip,user_agent,detail,created_at\n";
+
+// Format event as CSV row
+pub fn audit_csv_row(event: &AuditEvent) -> String {
+    let fields = [
+        event.id.to_string(),
+        csv_field(&event.event_type),
+        csv_field(&event.outcome),
+        csv_field(event.actor_uuid.as_deref().unwrap_or_default()),
+        csv_field(event.target.as_deref().unwrap_or_default()),
+        csv_field(event.ip.as_deref().unwrap_or_default()),
+        csv_field(event.user_agent.as_deref().unwrap_or_default()),
+        csv_field(event.detail.as_deref().unwrap_or_default()),
+        event.created_at.to_string(),
+    ];
+    format!("{}\n", fields.join(","))
+}
+
+// Quote CSV field when it contains separators, quotes or line breaks, and defuse spreadsheet formulas
+fn csv_field(value: &str) -> String {
+    let value = match value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        true => format!("'{}", value),
+        false => value.to_string(),
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_field_quotes_separators() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field(""), "");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(csv_field("carriage\r\nreturn"), "\"carriage\r\nreturn\"");
+    }
+
+    #[test]
+    fn csv_field_defuses_formulas() {
+        assert_eq!(csv_field("=1+1"), "'=1+1");
+        assert_eq!(csv_field("+31 6 1234"), "'+31 6 1234");
+        assert_eq!(csv_field("-2"), "'-2");
+        assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(csv_field("\t=1+1"), "'\t=1+1");
+        assert_eq!(csv_field("\r=1+1"), "\"'\r=1+1\"");
+        // Formulas are defused before quoting
+        assert_eq!(csv_field("=HYPERLINK(\"x\",\"y\")"), "\"'=HYPERLINK(\"\"x\"\",\"\"y\"\")\"");
+        // Only a leading character starts a formula
+        assert_eq!(csv_field("a=b"), "a=b");
+    }
+
+    #[test]
+    fn audit_csv_row_escapes_each_field() {
+        let event = AuditEvent {
+            id: 7,
+            event_type: "login".to_string(),
+            outcome: "failure".to_string(),
+            actor_uuid: None,
+            target: Some("=cmd|' /C calc'!A0".to_string()),
+            ip: Some("203.0.113.7".to_string()),
+            user_agent: Some("Mozilla/5.0 (X11, Linux)".to_string()),
+            detail: Some("wrong \"password\"\nagain".to_string()),
+            created_at: 1_700_000_000,
+        };
+        assert_eq!(
+            audit_csv_row(&event),
+            "7,login,failure,,'=cmd|' /C calc'!A0,203.0.113.7,\"Mozilla/5.0 (X11, Linux)\",\
+             \"wrong \"\"password\"\"\nagain\",1700000000\n"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::str::FromStr;
+
+use image::ImageFormat;
+pub use rustenv_shared::media::{AVATAR_CONTENT_TYPES, AVATAR_SIZES, AvatarUrls};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+// Format avatars are re-encoded to
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AvatarFormat {
+    #[default]
+    Webp,
+    Png,
+}
+
+impl AvatarFormat {
+    pub fn image_format(&self) -> ImageFormat {
+        match self {
+            AvatarFormat::Webp => ImageFormat::WebP,
+            AvatarFormat::Png => ImageFormat::Png,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            AvatarFormat::Webp => "image/webp",
+            AvatarFormat::Png => "image/png",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AvatarFormat::Webp => "webp",
+            AvatarFormat::Png => "png",
+        }
+    }
+}
+
+impl FromStr for AvatarFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "webp" => Ok(AvatarFormat::Webp),
+            "png" => Ok(AvatarFormat::Png),
+            value => Err(format!("unsupported avatar format {}", value)),
+        }
+    }
+}
+
+// Multipart avatar upload body
+#[derive(ToSchema)]
+pub struct AvatarUpload {
+    // JPEG, PNG or WebP image
+    #[schema(value_type = String, format = Binary)]
+    pub avatar: Vec<u8>,
+}
+
+// Signature query of a signed media URL
+#[derive(Clone, Debug, Deserialize, IntoParams, Serialize)]
+#[into_params(parameter_in = Query)]
+pub struct MediaSignature {
+    pub expires: u64,
+    pub signature: String,
+}
@@ -1,2 +1,7 @@
-pub mod auth;
+pub mod audit;
+pub mod media;
+pub mod pagination;
+pub mod session;
 pub mod user;
+
+pub use rustenv_shared::{auth, error, health, two_factor, validation};
@@ -0,0 +1,52 @@
+pub use rustenv_shared::pagination::{DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT, Page, SortOrder};
+use serde::Serialize;
+
+use crate::error::AppError;
+
+// SQL keyword for sort direction
+pub fn order_sql(order: SortOrder) -> &'static str {
+    match order {
+        SortOrder::Asc => "ASC",
+        SortOrder::Desc => "DESC",
+    }
+}
+
+// Offsets of the next and previous pages around the page at offset
+pub fn neighbour_offsets(offset: u64, limit: u32, total: i64) -> (Option<u64>, Option<u64>) {
+    let next = offset + u64::from(limit);
+    let next = (next < total.max(0) as u64).then_some(next);
+    let prev = (offset > 0).then(|| offset.saturating_sub(u64::from(limit)));
+    (next, prev)
+}
+
+// Build link to path with query string
+pub fn page_link<Q>(path: &str, query: &Q) -> Result<String, AppError>
+where
+    Q: Serialize,
+{
+    let query = serde_urlencoded::to_string(query)
+        .map_err(|error| AppError::Internal(format!("Error encoding page link: {}", error)))?;
+    Ok(format!("{}?{}", path, query))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbour_offsets_at_ends() {
+        assert_eq!(neighbour_offsets(0, 10, 25), (Some(10), None));
+        assert_eq!(neighbour_offsets(10, 10, 25), (Some(20), Some(0)));
+        assert_eq!(neighbour_offsets(20, 10, 25), (None, Some(10)));
+        assert_eq!(neighbour_offsets(0, 10, 10), (None, None));
+        assert_eq!(neighbour_offsets(0, 10, 0), (None, None));
+    }
+
+    #[test]
+    fn neighbour_offsets_off_grid() {
+        // Previous page starts at zero rather than going negative
+        assert_eq!(neighbour_offsets(5, 10, 25), (Some(15), Some(0)));
+        // Offsets past the end still link back
+        assert_eq!(neighbour_offsets(40, 10, 25), (None, Some(30)));
+    }
+}
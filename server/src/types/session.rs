@@ -0,0 +1,61 @@
+pub use rustenv_shared::session::{SessionExport, SessionInformation};
+use sqlx::any::AnyRow;
+use sqlx::{FromRow, Row};
+
+#[derive(Clone, Debug)]
+pub struct Session {
+    pub id: i64,
+    pub uuid: String,
+    pub user_uuid: String,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: i64,
+    pub last_used_at: i64,
+    pub expires_at: i64,
+    pub revoked_at: Option<i64>,
+}
+
+impl Session {
+    // Session information, marked current when it is the requesting session
+    pub fn information(self, current: Option<&str>) -> SessionInformation {
+        SessionInformation {
+            current: current == Some(self.uuid.as_str()),
+            uuid: self.uuid,
+            ip: self.ip,
+            user_agent: self.user_agent,
+            created_at: self.created_at,
+            last_used_at: self.last_used_at,
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+impl From<Session> for SessionExport {
+    fn from(session: Session) -> Self {
+        Self {
+            uuid: session.uuid,
+            ip: session.ip,
+            user_agent: session.user_agent,
+            created_at: session.created_at,
+            last_used_at: session.last_used_at,
+            expires_at: session.expires_at,
+            revoked_at: session.revoked_at,
+        }
+    }
+}
+
+impl<'r> FromRow<'r, AnyRow> for Session {
+    fn from_row(row: &'r AnyRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            uuid: row.try_get("uuid")?,
+            user_uuid: row.try_get("user_uuid")?,
+            ip: row.try_get("ip")?,
+            user_agent: row.try_get("user_agent")?,
+            created_at: row.try_get("created_at")?,
+            last_used_at: row.try_get("last_used_at")?,
+            expires_at: row.try_get("expires_at")?,
+            revoked_at: row.try_get("revoked_at")?,
+        })
+    }
+}
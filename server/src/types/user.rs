@@ -1,9 +1,14 @@
-use std::fmt;
-
+use base64::prelude::*;
+pub use rustenv_shared::user::{
+    UserDataExport, UserEmailChange, UserInformation, UserListQuery, UserLogin, UserPasswordChange,
+    UserProfileUpdate, UserRegister, UserRoleUpdate, UserSort, try_get_bool,
+};
 use serde::{Deserialize, Serialize};
 use sqlx::any::AnyRow;
 use sqlx::{FromRow, Row};
 
+use super::pagination::SortOrder;
+
 #[derive(Clone, Debug, Serialize)]
 pub struct User {
     pub id: i32,
@@ -12,6 +17,29 @@ pub struct User {
     pub password: String,
     pub email: String,
     pub is_admin: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub last_login_at: Option<i64>,
+    pub deleted_at: Option<i64>,
+    pub disabled_at: Option<i64>,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub avatar_key: Option<String>,
+    pub locale: Option<String>,
+    pub timezone: Option<String>,
+    pub totp_secret: Option<String>,
+    pub totp_enabled_at: Option<i64>,
+    pub totp_last_step: Option<i64>,
+}
+
+impl User {
+    pub fn is_disabled(&self) -> bool {
+        self.disabled_at.is_some()
+    }
+
+    pub fn has_two_factor(&self) -> bool {
+        self.totp_enabled_at.is_some()
+    }
 }
 
 impl<'r> FromRow<'r, AnyRow> for User {
@@ -21,100 +49,103 @@ impl<'r> FromRow<'r, AnyRow> for User {
         let username: String = row.try_get("username")?;
         let password: String = row.try_get("password")?;
         let email: String = row.try_get("email")?;
-        let is_admin: bool = row.try_get("is_admin")?;
+        let is_admin: bool = try_get_bool(row, "is_admin")?;
+        let created_at: i64 = row.try_get("created_at")?;
+        let updated_at: i64 = row.try_get("updated_at")?;
+        let last_login_at: Option<i64> = row.try_get("last_login_at")?;
+        let deleted_at: Option<i64> = row.try_get("deleted_at")?;
+        let disabled_at: Option<i64> = row.try_get("disabled_at")?;
+        let display_name: Option<String> = row.try_get("display_name")?;
+        let avatar_url: Option<String> = row.try_get("avatar_url")?;
+        let avatar_key: Option<String> = row.try_get("avatar_key")?;
+        let locale: Option<String> = row.try_get("locale")?;
+        let timezone: Option<String> = row.try_get("timezone")?;
+        let totp_secret: Option<String> = row.try_get("totp_secret")?;
+        let totp_enabled_at: Option<i64> = row.try_get("totp_enabled_at")?;
+        let totp_last_step: Option<i64> = row.try_get("totp_last_step")?;
 
-        Ok(Self { id, uuid, username, password, email, is_admin })
+        Ok(Self {
+            id,
+            uuid,
+            username,
+            password,
+            email,
+            is_admin,
+            created_at,
+            updated_at,
+            last_login_at,
+            deleted_at,
+            disabled_at,
+            display_name,
+            avatar_url,
+            avatar_key,
+            locale,
+            timezone,
+            totp_secret,
+            totp_enabled_at,
+            totp_last_step,
+        })
     }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
-pub struct UserRegister {
-    pub username: String,
-    pub password: String,
-    pub email: String,
-}
+// Columns selected for user information projections
+pub const USER_INFORMATION_COLUMNS: &str = "uuid, username, email, is_admin, display_name, \
+    avatar_url, locale, timezone, created_at, updated_at, last_login_at, disabled_at, totp_enabled_at";
 
-impl UserRegister {
-    pub fn set(&self, key: &str, value: String) -> Result<Self, String> {
-        let mut clone = self.clone();
-        match key {
-            "username" => clone.username = value,
-            "password" => clone.password = value,
-            "email" => clone.email = value,
-            _ => return Err(format!("Invalid key: {}", key)),
-        }
-        Ok(clone)
-    }
-}
-
-impl fmt::Display for UserRegister {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "UserRegister {{ username: {}, password: {}, email: {} }}",
-            self.username, self.password, self.email
-        )
-    }
-}
-
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
-pub struct UserLogin {
-    pub username: String,
-    pub password: String,
-}
-
-impl UserLogin {
-    pub fn set(&self, key: &str, value: String) -> Result<Self, String> {
-        let mut clone = self.clone();
-        match key {
-            "username" => clone.username = value,
-            "password" => clone.password = value,
-            _ => return Err(format!("Invalid key: {}", key)),
+impl From<User> for UserInformation {
+    fn from(user: User) -> Self {
+        Self {
+            uuid: user.uuid,
+            username: user.username,
+            email: user.email,
+            is_admin: user.is_admin,
+            display_name: user.display_name,
+            avatar_url: user.avatar_url,
+            locale: user.locale,
+            timezone: user.timezone,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            last_login_at: user.last_login_at,
+            disabled_at: user.disabled_at,
+            two_factor_enabled_at: user.totp_enabled_at,
         }
-        Ok(clone)
     }
 }
 
-impl fmt::Display for UserLogin {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "UserLogin {{ username: {}, password: {} }}", self.username, self.password)
+// Column user listings are sorted by
+pub fn sort_column(sort: UserSort) -> &'static str {
+    match sort {
+        UserSort::Username => "username",
+        UserSort::Email => "email",
+        UserSort::CreatedAt => "created_at",
     }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, FromRow, PartialEq, Serialize)]
-pub struct UserInformation {
-    pub uuid: String,
-    pub username: String,
-    pub email: String,
-    pub is_admin: bool,
+// Sort key value stored in a user listing cursor
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum CursorValue {
+    Int(i64),
+    Text(String),
 }
 
-impl fmt::Display for UserInformation {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "UserInformation {{ uuid: {}, username: {}, email: {}, is_admin: {} }}",
-            self.uuid, self.username, self.email, self.is_admin
-        )
-    }
+// Keyset position in a user listing, encoded as opaque base64url JSON
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct UserCursor {
+    pub sort: UserSort,
+    pub order: SortOrder,
+    pub value: CursorValue,
+    pub id: i32,
+    pub backward: bool,
 }
 
-impl UserInformation {
-    pub fn from_user(user: User) -> Self {
-        Self {
-            uuid: user.uuid,
-            username: user.username,
-            email: user.email,
-            is_admin: user.is_admin,
-        }
+impl UserCursor {
+    pub fn encode(&self) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
     }
 
-    pub fn default() -> Self {
-        Self {
-            uuid: String::new(),
-            username: String::new(),
-            email: String::new(),
-            is_admin: false,
-        }
+    pub fn decode(encoded: &str) -> Option<Self> {
+        let decoded = BASE64_URL_SAFE_NO_PAD.decode(encoded).ok()?;
+        serde_json::from_slice(&decoded).ok()
     }
 }
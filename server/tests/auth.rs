@@ -0,0 +1,128 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use axum::body::Body;
+use common::{TestDatabase, register, send, send_request};
+use http::{Method, Request, StatusCode};
+use rustenv_server::strategies::two_factor_strategy;
+use serde_json::json;
+use totp_rs::{Algorithm, Secret, TOTP};
+
+// Failed registrations must not wait on the write lock of their own rolled back insert
+#[tokio::test]
+async fn duplicate_registration_records_failure() {
+    let test = TestDatabase::new().await;
+    let app = test.app();
+    let payload = json!({
+        "username": "duplicate",
+        "email": "duplicate@example.com",
+        "password": "correct horse battery staple",
+    });
+
+    let (status, _, body) =
+        send(&app, Method::POST, "/auth/register", Some(payload.clone()), None).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+
+    let start = Instant::now();
+    let (status, _, _) = send(&app, Method::POST, "/auth/register", Some(payload), None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(start.elapsed() < Duration::from_secs(2), "Registration waited for a database lock");
+
+    let failures = test
+        .scalar(
+            "SELECT COUNT(*) FROM audit_events \
+             WHERE event_type = 'register' AND outcome = 'failure' AND target = 'duplicate'",
+        )
+        .await;
+    assert_eq!(failures, 1);
+}
+
+// Invalid registrations return a problem body listing the errors of each field
+#[tokio::test]
+async fn invalid_registration_lists_field_errors() {
+    let test = TestDatabase::new().await;
+    let payload = json!({
+        "username": "no spaces!",
+        "email": "not-an-email",
+        "password": "password123",
+    });
+
+    let (status, headers, body) =
+        send(&test.app(), Method::POST, "/auth/register", Some(payload), None).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(headers["Content-Type"], "application/problem+json");
+    assert_eq!(body["status"], 422);
+    assert_eq!(body["error_type"], "InvalidInput");
+    assert_eq!(body["fields"]["username"][0]["code"], "username_charset");
+    assert_eq!(body["fields"]["email"][0]["code"], "email");
+    assert_eq!(body["fields"]["password"][0]["code"], "password_common");
+    assert_eq!(body["fields"]["password"][0]["message"], "Password is too common");
+}
+
+// Audit events keep the address the trusted proxy saw, not entries the client forged before it
+#[tokio::test]
+async fn audit_ignores_spoofed_forwarded_for() {
+    let test = TestDatabase::new().await;
+    std::env::set_var("TRUST_PROXY_HEADERS", "true");
+    let payload = json!({ "username": "nobody", "password": "wrong password" });
+    let request = Request::post("/auth/login")
+        .header("Content-Type", "application/json")
+        .header("X-Forwarded-For", "6.6.6.6, 10.0.0.1, 198.51.100.9")
+        .body(Body::from(payload.to_string()))
+        .unwrap();
+
+    let (status, _, _) = send_request(&test.app(), request).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let spoofed = test.scalar("SELECT COUNT(*) FROM audit_events WHERE ip <> '198.51.100.9'").await;
+    let recorded = test.scalar("SELECT COUNT(*) FROM audit_events WHERE ip = '198.51.100.9'").await;
+    assert_eq!((spoofed, recorded), (0, 1));
+}
+
+// Only callers knowing the password learn that an account is disabled
+#[tokio::test]
+async fn disabled_account_needs_password() {
+    let test = TestDatabase::new().await;
+    let app = test.app();
+    register(&app, "disabled").await;
+    test.execute("UPDATE users SET disabled_at = 1 WHERE username = 'disabled'").await;
+
+    let payload = json!({ "username": "disabled", "password": "wrong password" });
+    let (status, _, body) = send(&app, Method::POST, "/auth/login", Some(payload), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error_type"], "WrongCredentials");
+
+    let payload = json!({ "username": "disabled", "password": "correct horse battery staple" });
+    let (status, _, body) = send(&app, Method::POST, "/auth/login", Some(payload), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error_type"], "AccountDisabled");
+}
+
+// Each authenticator code logs in once, so a captured code cannot be replayed while still current
+#[tokio::test]
+async fn two_factor_code_is_accepted_once() {
+    let test = TestDatabase::new().await;
+    let app = test.app();
+    register(&app, "guarded").await;
+    let secret = two_factor_strategy::generate_setup("guarded").unwrap().secret;
+    test.execute(&format!(
+        "UPDATE users SET totp_secret = '{}', totp_enabled_at = 1 WHERE username = 'guarded'",
+        secret
+    ))
+    .await;
+
+    let secret = Secret::Encoded(secret).to_bytes().unwrap();
+    let totp = TOTP::new(Algorithm::SHA1, 6, 0, 30, secret, None, "guarded".to_string()).unwrap();
+    let payload = json!({
+        "username": "guarded",
+        "password": "correct horse battery staple",
+        "two_factor_code": totp.generate_current().unwrap(),
+    });
+
+    let (status, _, body) =
+        send(&app, Method::POST, "/auth/login", Some(payload.clone()), None).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let (status, _, body) = send(&app, Method::POST, "/auth/login", Some(payload), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error_type"], "InvalidTwoFactorCode");
+}
@@ -0,0 +1,153 @@
+// Helpers shared by the integration tests, each test binary using only some of them
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::middleware::from_fn;
+use http::{HeaderMap, Method, Request, StatusCode};
+use rustenv_server::controllers::{
+    account_controller, audit_controller, auth_controller, user_controller,
+};
+use rustenv_server::middleware::{request_context, transaction};
+use rustenv_server::pool::{Database, Databases, Driver, PoolConfig, PoolRole};
+use rustenv_server::state::AppState;
+use rustenv_server::storage::local::LocalBlobStore;
+use serde_json::Value;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+// Token and password settings shared by every test, so tests running in parallel agree on them
+pub fn set_env() {
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("JWT_AUDIENCE", "rustenv-test");
+    std::env::set_var("JWT_ISSUER", "rustenv-test");
+    std::env::set_var("AUTH_TOKEN_EXPIRY", "300");
+    std::env::set_var("AUTH_REQUEST_TOKEN_EXPIRY", "3600");
+    std::env::set_var("PASSWORD_SALT", "testpasswordsalt");
+}
+
+// Migrated SQLite database in a temporary file, removed on drop
+pub struct TestDatabase {
+    pub databases: Databases,
+    path: PathBuf,
+}
+
+impl TestDatabase {
+    pub async fn new() -> Self {
+        set_env();
+        let path = std::env::temp_dir().join(format!("rustenv-test-{}.db", Uuid::new_v4()));
+        let url = format!("sqlite://{}?mode=rwc", path.display());
+        let primary =
+            Database::new(PoolRole::Primary, url, PoolConfig::defaults(Driver::Sqlite)).unwrap();
+        primary.connect().await.unwrap();
+        Self { databases: Databases::new(primary, None), path }
+    }
+
+    // Router with the API routes and request middleware of the server
+    pub fn app(&self) -> Router {
+        let media = LocalBlobStore::new(self.path.with_extension("media"), "/media", b"test");
+        Router::new()
+            .nest("/audit", audit_controller::routes())
+            .nest("/auth", auth_controller::routes())
+            .nest("/users", user_controller::routes().merge(account_controller::routes()))
+            .with_state(AppState { db: self.databases.clone(), media: Arc::new(media) })
+            .layer(from_fn(transaction::transaction_scope))
+            .layer(from_fn(request_context::scope_request_context))
+    }
+
+    // Run statement against the primary
+    pub async fn execute(&self, statement: &str) {
+        let mut connection = self.databases.primary.acquire().await.unwrap();
+        sqlx::query(statement).execute(&mut *connection).await.unwrap();
+    }
+
+    // Run scalar query against the primary
+    pub async fn scalar(&self, query: &str) -> i64 {
+        let mut connection = self.databases.primary.acquire().await.unwrap();
+        sqlx::query_scalar::<_, i64>(query).fetch_one(&mut *connection).await.unwrap()
+    }
+}
+
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", self.path.display(), suffix));
+        }
+        let _ = std::fs::remove_dir_all(self.path.with_extension("media"));
+    }
+}
+
+// Send request with optional JSON body and bearer token, returning status, headers and JSON body
+pub async fn send(
+    app: &Router,
+    method: Method,
+    uri: &str,
+    body: Option<Value>,
+    token: Option<&str>,
+) -> (StatusCode, HeaderMap, Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let request = match body {
+        Some(body) => request
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+        None => request.body(Body::empty()).unwrap(),
+    };
+    send_request(app, request).await
+}
+
+// Send prepared request, returning status, headers and JSON body
+pub async fn send_request(app: &Router, request: Request<Body>) -> (StatusCode, HeaderMap, Value) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let (parts, body) = response.into_parts();
+    let bytes = to_bytes(body, usize::MAX).await.unwrap();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (parts.status, parts.headers, json)
+}
+
+// Registered user's UUID and access token
+pub struct TestUser {
+    pub uuid: String,
+    pub token: String,
+}
+
+// Register user and exchange its request token for an access token
+pub async fn register(app: &Router, username: &str) -> TestUser {
+    let (uuid, request_token) = register_only(app, username).await;
+    TestUser { uuid, token: exchange(app, &request_token).await }
+}
+
+// Register user and make it an admin before exchanging its request token
+pub async fn register_admin(test: &TestDatabase, app: &Router, username: &str) -> TestUser {
+    let (uuid, request_token) = register_only(app, username).await;
+    test.execute(&format!("UPDATE users SET is_admin = 1 WHERE uuid = '{}'", uuid)).await;
+    TestUser { uuid, token: exchange(app, &request_token).await }
+}
+
+// Register user, returning its UUID and request token
+async fn register_only(app: &Router, username: &str) -> (String, String) {
+    let payload = serde_json::json!({
+        "username": username,
+        "email": format!("{}@example.com", username),
+        "password": "correct horse battery staple",
+    });
+    let (status, headers, body) =
+        send(app, Method::POST, "/auth/register", Some(payload), None).await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    let request_token = headers["Authorization"].to_str().unwrap().to_string();
+    (body["uuid"].as_str().unwrap().to_string(), request_token)
+}
+
+// Exchange request token for an access token
+async fn exchange(app: &Router, request_token: &str) -> String {
+    let (status, _, token) =
+        send(app, Method::POST, "/auth/token", None, Some(request_token)).await;
+    assert_eq!(status, StatusCode::OK, "{}", token);
+    token["access_token"].as_str().unwrap().to_string()
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::Router;
+use common::{send, set_env};
+use http::{Method, StatusCode};
+use rustenv_server::controllers::health_controller;
+use rustenv_server::pool::{Database, Databases, Driver, PoolConfig, PoolRole};
+use rustenv_server::state::AppState;
+use rustenv_server::storage::local::LocalBlobStore;
+
+// Failed readiness checks report the dependency as unavailable without the error detail
+#[tokio::test]
+async fn readyz_hides_error_details() {
+    set_env();
+    let url = "sqlite:///nonexistent/secret-location.db".to_string();
+    let primary =
+        Database::new(PoolRole::Primary, url, PoolConfig::defaults(Driver::Sqlite)).unwrap();
+    let media = LocalBlobStore::new(std::env::temp_dir(), "/media", b"test");
+    let app = Router::new()
+        .merge(health_controller::routes())
+        .with_state(AppState { db: Databases::new(primary, None), media: Arc::new(media) });
+
+    let (status, _, body) = send(&app, Method::GET, "/readyz", None, None).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "down");
+    for check in ["database", "migrations"] {
+        assert_eq!(body["checks"][check]["status"], "down", "{}", body);
+        assert_eq!(body["checks"][check]["detail"], "unavailable", "{}", body);
+    }
+    assert!(!body.to_string().contains("pool"), "{}", body);
+}
@@ -0,0 +1,27 @@
+use std::path::Path;
+
+use rustenv_server::openapi::ApiDoc;
+use utoipa::OpenApi;
+
+// Checked-in OpenAPI document, relative to the crate root
+const SPEC_PATH: &str = "openapi.json";
+
+// Fail when the checked-in spec no longer matches the routes, rewriting it with UPDATE_OPENAPI=1
+#[test]
+fn openapi_spec_is_up_to_date() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SPEC_PATH);
+    let generated =
+        ApiDoc::openapi().to_pretty_json().expect("Error serializing OpenAPI spec") + "\n";
+
+    if std::env::var("UPDATE_OPENAPI").is_ok_and(|value| value == "1") {
+        std::fs::write(&path, &generated).expect("Error writing OpenAPI spec");
+        return;
+    }
+
+    let checked_in = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        checked_in == generated,
+        "{} is out of date, regenerate it with `UPDATE_OPENAPI=1 cargo test -p rustenv_server --test openapi`",
+        SPEC_PATH
+    );
+}
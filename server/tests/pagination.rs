@@ -0,0 +1,121 @@
+mod common;
+
+use common::{TestDatabase, TestUser, register_admin, send};
+use http::{Method, StatusCode};
+use serde_json::Value;
+
+// Admin and seeded users, ten of them created a second apart in reverse username order
+async fn seeded() -> (TestDatabase, axum::Router, TestUser) {
+    let test = TestDatabase::new().await;
+    let app = test.app();
+    let admin = register_admin(&test, &app, "admin").await;
+    for index in 0..10 {
+        test.execute(&format!(
+            "INSERT INTO users (uuid, username, password, email, created_at) \
+             VALUES ('uuid-{index}', 'user{index}', 'x', 'user{index}@example.com', {})",
+            1_000 - index
+        ))
+        .await;
+    }
+    (test, app, admin)
+}
+
+// Usernames of the page at uri
+async fn page(app: &axum::Router, admin: &TestUser, uri: &str) -> (Vec<String>, Value) {
+    let (status, _, body) = send(app, Method::GET, uri, None, Some(&admin.token)).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let usernames = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|user| user["username"].as_str().unwrap().to_string())
+        .collect();
+    (usernames, body)
+}
+
+#[tokio::test]
+async fn cursor_pages_forward_and_back() {
+    let (_test, app, admin) = seeded().await;
+
+    // Walk forward from the first page until there is no next cursor
+    let mut forward = Vec::new();
+    let (mut usernames, mut body) = page(&app, &admin, "/users?limit=4&sort=username").await;
+    assert_eq!(body["total"], 11);
+    assert!(body["prev"].is_null());
+    loop {
+        forward.push(usernames);
+        let Some(next) = body["next"].as_str().map(str::to_string) else { break };
+        assert!(body["offset"].is_null());
+        (usernames, body) = page(&app, &admin, &next).await;
+        assert!(body["prev"].is_string());
+    }
+    let expected = [
+        vec!["admin", "user0", "user1", "user2"],
+        vec!["user3", "user4", "user5", "user6"],
+        vec!["user7", "user8", "user9"],
+    ];
+    assert_eq!(forward, expected);
+
+    // Walk back from the last page, getting the same pages in reverse
+    let mut backward = vec![forward.pop().unwrap()];
+    while let Some(prev) = body["prev"].as_str().map(str::to_string) {
+        (usernames, body) = page(&app, &admin, &prev).await;
+        assert!(body["next"].is_string());
+        backward.push(usernames);
+    }
+    backward.reverse();
+    assert_eq!(backward, expected);
+}
+
+#[tokio::test]
+async fn cursor_pages_descending_by_creation() {
+    let (_test, app, admin) = seeded().await;
+
+    let (usernames, body) = page(&app, &admin, "/users?limit=5&order=desc").await;
+    assert_eq!(usernames, ["admin", "user0", "user1", "user2", "user3"]);
+    let (usernames, body) = page(&app, &admin, body["next"].as_str().unwrap()).await;
+    assert_eq!(usernames, ["user4", "user5", "user6", "user7", "user8"]);
+    let (usernames, last) = page(&app, &admin, body["next"].as_str().unwrap()).await;
+    assert_eq!(usernames, ["user9"]);
+    assert!(last["next"].is_null());
+    let (usernames, _) = page(&app, &admin, last["prev"].as_str().unwrap()).await;
+    assert_eq!(usernames, ["user4", "user5", "user6", "user7", "user8"]);
+}
+
+// Cursors only continue the sort and order they were issued for
+#[tokio::test]
+async fn cursor_rejects_other_sort() {
+    let (_test, app, admin) = seeded().await;
+    let (_, body) = page(&app, &admin, "/users?limit=4&sort=username").await;
+    let next = body["next"].as_str().unwrap();
+    let cursor = next.split("cursor=").nth(1).unwrap().split('&').next().unwrap();
+
+    for query in ["sort=email", "sort=username&order=desc", "sort=created_at"] {
+        let uri = format!("/users?limit=4&{}&cursor={}", query, cursor);
+        let (status, _, _) = send(&app, Method::GET, &uri, None, Some(&admin.token)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", query);
+    }
+    let (status, _, _) =
+        send(&app, Method::GET, "/users?cursor=garbage", None, Some(&admin.token)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn offset_links_at_both_ends() {
+    let (_test, app, admin) = seeded().await;
+
+    let (usernames, body) = page(&app, &admin, "/users?limit=4&offset=0&sort=username").await;
+    assert_eq!(usernames, ["admin", "user0", "user1", "user2"]);
+    assert_eq!(body["offset"], 0);
+    assert!(body["prev"].is_null());
+    let next = body["next"].as_str().unwrap();
+    assert!(next.contains("offset=4"), "{}", next);
+
+    let (usernames, body) = page(&app, &admin, "/users?limit=4&offset=8&sort=username").await;
+    assert_eq!(usernames, ["user7", "user8", "user9"]);
+    assert!(body["next"].is_null());
+    let prev = body["prev"].as_str().unwrap();
+    assert!(prev.contains("offset=4"), "{}", prev);
+    let (usernames, _) = page(&app, &admin, prev).await;
+    assert_eq!(usernames, ["user3", "user4", "user5", "user6"]);
+}
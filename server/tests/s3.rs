@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::Router;
+use axum::extract::{Path, State};
+use axum::routing::get;
+use bytes::Bytes;
+use http::header::CONTENT_TYPE;
+use http::{HeaderMap, StatusCode};
+use rustenv_server::error::AppError;
+use rustenv_server::storage::BlobStore;
+use rustenv_server::storage::s3::S3BlobStore;
+use rusty_s3::{Bucket, Credentials, UrlStyle};
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+// Objects of the stand-in bucket by key, with their content types
+type Objects = Arc<Mutex<HashMap<String, (Bytes, String)>>>;
+
+// Minimal path-style S3 stand-in serving one bucket, ignoring request signatures
+async fn stand_in() -> S3BlobStore {
+    async fn put(
+        State(objects): State<Objects>,
+        Path((_, key)): Path<(String, String)>,
+        headers: HeaderMap,
+        data: Bytes,
+    ) -> StatusCode {
+        let content_type = headers[CONTENT_TYPE].to_str().unwrap().to_string();
+        objects.lock().unwrap().insert(key, (data, content_type));
+        StatusCode::OK
+    }
+    async fn get_object(
+        State(objects): State<Objects>,
+        Path((_, key)): Path<(String, String)>,
+    ) -> Result<([(http::HeaderName, String); 1], Bytes), StatusCode> {
+        let objects = objects.lock().unwrap();
+        let (data, content_type) = objects.get(&key).ok_or(StatusCode::NOT_FOUND)?;
+        Ok(([(CONTENT_TYPE, content_type.clone())], data.clone()))
+    }
+    async fn delete(
+        State(objects): State<Objects>,
+        Path((_, key)): Path<(String, String)>,
+    ) -> StatusCode {
+        match objects.lock().unwrap().remove(&key) {
+            Some(_) => StatusCode::NO_CONTENT,
+            None => StatusCode::NOT_FOUND,
+        }
+    }
+
+    let app = Router::new()
+        .route("/:bucket/*key", get(get_object).put(put).delete(delete))
+        .with_state(Objects::default());
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let bucket = Bucket::new(endpoint, UrlStyle::Path, "rustenv", "us-east-1").unwrap();
+    S3BlobStore::new(bucket, Credentials::new("access", "secret"))
+}
+
+// Store round trip, checked against the in-process stand-in on every run
+#[tokio::test]
+async fn stand_in_round_trip() {
+    round_trip(&stand_in().await).await;
+}
+
+// Round trip through a real bucket, such as a local MinIO stand-in:
+//
+//     docker run -p 9000:9000 minio/minio server /data
+//     S3_ENDPOINT=http://localhost:9000 S3_BUCKET=rustenv S3_ACCESS_KEY=minioadmin \
+//     S3_SECRET_KEY=minioadmin S3_PATH_STYLE=true cargo test -p rustenv_server --test s3 -- --ignored
+#[tokio::test]
+#[ignore = "needs an S3-compatible bucket configured through S3_* variables"]
+async fn bucket_round_trip() {
+    round_trip(&S3BlobStore::from_env().expect("S3_* variables must configure a bucket")).await;
+}
+
+// Put, get, fetch through a presigned URL and delete an object
+async fn round_trip(store: &S3BlobStore) {
+    let key = format!("tests/{}/64.png", Uuid::new_v4());
+
+    store.put(&key, Bytes::from_static(b"image"), "image/png").await.unwrap();
+    let blob = store.get(&key).await.unwrap();
+    assert_eq!(blob.data, Bytes::from_static(b"image"));
+    assert_eq!(blob.content_type, "image/png");
+
+    // Presigned URLs serve the object without credentials
+    let url = store.signed_url(&key, Duration::from_secs(60)).unwrap();
+    let body = reqwest::get(url).await.unwrap().error_for_status().unwrap().bytes().await;
+    assert_eq!(body.unwrap(), Bytes::from_static(b"image"));
+
+    store.delete(&key).await.unwrap();
+    assert!(matches!(store.get(&key).await, Err(AppError::NotFound(_))));
+    // Deleting again succeeds
+    store.delete(&key).await.unwrap();
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use common::{TestDatabase, register, register_admin, send};
+use http::{Method, StatusCode};
+use rustenv_server::error::AppError;
+use rustenv_server::strategies::audit_strategy::{AuditContext, insert_audit_event};
+use rustenv_server::strategies::user_strategy::{
+    get_db_user_by_uuid, purge_db_users_deleted_before, update_db_user_password,
+};
+use rustenv_server::types::audit::{AuditEventType, AuditOutcome};
+
+// Other people's actor and client details stay out of a user's export
+#[tokio::test]
+async fn export_removes_details_of_events_by_others() {
+    let test = TestDatabase::new().await;
+    let app = test.app();
+    let user = register(&app, "exported").await;
+
+    let stranger = AuditContext {
+        actor_uuid: None,
+        ip: Some("203.0.113.7".to_string()),
+        user_agent: Some("stranger".to_string()),
+    };
+    let admin = stranger.with_actor("admin-uuid");
+    let own =
+        AuditContext { ip: Some("198.51.100.1".to_string()), ..stranger.with_actor(&user.uuid) };
+    let mut connection = test.databases.primary.acquire().await.unwrap();
+    for event in [
+        stranger.event(AuditEventType::Login, AuditOutcome::Failure).target("exported"),
+        admin.event(AuditEventType::UserDisable, AuditOutcome::Success).target(&user.uuid),
+        own.event(AuditEventType::PasswordChange, AuditOutcome::Success).target(&user.uuid),
+    ] {
+        insert_audit_event(&mut *connection, &event).await.unwrap();
+    }
+    drop(connection);
+
+    let (status, _, export) =
+        send(&app, Method::GET, "/users/me/export", None, Some(&user.token)).await;
+    assert_eq!(status, StatusCode::OK, "{}", export);
+    let events = export["audit_events"].as_array().unwrap();
+    for event in events {
+        match event["actor_uuid"].as_str() {
+            Some(actor) => assert_eq!(actor, user.uuid),
+            None => assert!(event["ip"].is_null() && event["user_agent"].is_null(), "{}", event),
+        }
+    }
+    assert!(events.iter().any(|event| event["ip"] == "198.51.100.1"));
+    assert!(events.iter().any(|event| event["event_type"] == "user_disable"));
+    assert!(events.iter().any(|event| event["target"] == "exported"));
+}
+
+// Sessions are stored about the user, so they are part of the export
+#[tokio::test]
+async fn export_includes_sessions() {
+    let test = TestDatabase::new().await;
+    let app = test.app();
+    let user = register(&app, "sessions").await;
+
+    let (status, _, export) =
+        send(&app, Method::GET, "/users/me/export", None, Some(&user.token)).await;
+    assert_eq!(status, StatusCode::OK, "{}", export);
+    let sessions = export["sessions"].as_array().unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert!(sessions[0]["revoked_at"].is_null());
+}
+
+// Purged users are recorded in the audit log and leave no sessions or client details behind
+#[tokio::test]
+async fn purge_records_deleted_users() {
+    let test = TestDatabase::new().await;
+    let app = test.app();
+    let user = register(&app, "purged").await;
+    let other = register(&app, "other").await;
+    test.execute("UPDATE users SET deleted_at = 1 WHERE username = 'purged'").await;
+
+    let client = AuditContext {
+        actor_uuid: None,
+        ip: Some("198.51.100.1".to_string()),
+        user_agent: Some("browser".to_string()),
+    };
+    let mut connection = test.databases.primary.acquire().await.unwrap();
+    for actor in [&user.uuid, &other.uuid] {
+        let event = client.with_actor(actor).event(AuditEventType::Login, AuditOutcome::Success);
+        insert_audit_event(&mut *connection, &event).await.unwrap();
+    }
+    let purged = purge_db_users_deleted_before(&mut connection, 2).await.unwrap();
+    drop(connection);
+
+    assert_eq!(purged, vec![(user.uuid.clone(), None)]);
+    assert_eq!(test.scalar("SELECT COUNT(*) FROM users").await, 1);
+    assert_eq!(
+        test.scalar("SELECT COUNT(*) FROM audit_events WHERE event_type = 'user_purge'").await,
+        1
+    );
+
+    // Sessions go and the purged user's events keep no client details, while the other user's stay
+    let sessions =
+        |uuid: &str| format!("SELECT COUNT(*) FROM sessions WHERE user_uuid = '{}'", uuid);
+    assert_eq!(test.scalar(&sessions(&user.uuid)).await, 0);
+    assert_eq!(test.scalar(&sessions(&other.uuid)).await, 1);
+    let with_details = |uuid: &str| {
+        format!(
+            "SELECT COUNT(*) FROM audit_events \
+             WHERE actor_uuid = '{}' AND (ip IS NOT NULL OR user_agent IS NOT NULL)",
+            uuid
+        )
+    };
+    assert_eq!(test.scalar(&with_details(&user.uuid)).await, 0);
+    assert_eq!(test.scalar(&with_details(&other.uuid)).await, 1);
+}
+
+// Users stay when their purge cannot be recorded
+#[tokio::test]
+async fn purge_keeps_users_when_audit_fails() {
+    let test = TestDatabase::new().await;
+    let app = test.app();
+    register(&app, "kept").await;
+    test.execute("UPDATE users SET deleted_at = 1 WHERE username = 'kept'").await;
+    test.execute("DROP TABLE audit_events").await;
+
+    let mut connection = test.databases.primary.acquire().await.unwrap();
+    assert!(purge_db_users_deleted_before(&mut connection, 2).await.is_err());
+    drop(connection);
+
+    assert_eq!(test.scalar("SELECT COUNT(*) FROM users").await, 1);
+}
+
+// Password changes leave soft deleted users untouched
+#[tokio::test]
+async fn password_update_skips_deleted_users() {
+    let test = TestDatabase::new().await;
+    let app = test.app();
+    let user = register(&app, "deleted").await;
+    let mut connection = test.databases.primary.acquire().await.unwrap();
+    let hash = get_db_user_by_uuid(&mut *connection, user.uuid.clone()).await.unwrap().password;
+    drop(connection);
+    test.execute("UPDATE users SET deleted_at = 1 WHERE username = 'deleted'").await;
+
+    let mut connection = test.databases.primary.acquire().await.unwrap();
+    let password = "another long password".to_string();
+    let result =
+        update_db_user_password(&mut connection, &AuditContext::default(), user.uuid, password)
+            .await;
+    drop(connection);
+
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+    let unchanged = format!("SELECT COUNT(*) FROM users WHERE password = '{}'", hash);
+    assert_eq!(test.scalar(&unchanged).await, 1);
+}
+
+// Admin routes check the current admin flag rather than the role in the token
+#[tokio::test]
+async fn demoted_admin_loses_access() {
+    let test = TestDatabase::new().await;
+    let app = test.app();
+    let admin = register_admin(&test, &app, "admin").await;
+    let demoted = register_admin(&test, &app, "demoted").await;
+
+    let (status, _, _) = send(&app, Method::GET, "/users", None, Some(&demoted.token)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let uri = format!("/users/{}/role", demoted.uuid);
+    let payload = serde_json::json!({ "is_admin": false });
+    let (status, _, body) = send(&app, Method::PUT, &uri, Some(payload), Some(&admin.token)).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (status, _, _) = send(&app, Method::GET, "/users", None, Some(&demoted.token)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _, _) = send(&app, Method::GET, "/users/me", None, Some(&demoted.token)).await;
+    assert_eq!(status, StatusCode::OK);
+}
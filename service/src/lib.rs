@@ -0,0 +1,46 @@
+use std::time::{Duration, Instant};
+
+use axum::extract::{MatchedPath, Request};
+use axum::middleware::Next;
+use axum::response::Response;
+use metrics::{counter, histogram};
+
+// Default time allowed for in-flight requests to finish after shutdown
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Histogram buckets for durations in seconds
+pub const SECONDS_BUCKETS: &[f64] =
+    &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+// Drain timeout from SHUTDOWN_DRAIN_TIMEOUT (seconds)
+pub fn drain_timeout() -> Duration {
+    match std::env::var("SHUTDOWN_DRAIN_TIMEOUT") {
+        Ok(value) => match value.parse::<u64>() {
+            Ok(seconds) => Duration::from_secs(seconds),
+            Err(error) => {
+                tracing::warn!(%error, "Invalid SHUTDOWN_DRAIN_TIMEOUT, using default");
+                DEFAULT_DRAIN_TIMEOUT
+            }
+        },
+        Err(_) => DEFAULT_DRAIN_TIMEOUT,
+    }
+}
+
+// Record request count and latency by route and status
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    let start = Instant::now();
+    let method = request.method().to_string();
+    let route = match request.extensions().get::<MatchedPath>() {
+        Some(path) => path.as_str().to_string(),
+        None => "unmatched".to_string(),
+    };
+
+    let response = next.run(request).await;
+
+    let labels =
+        [("method", method), ("route", route), ("status", response.status().as_u16().to_string())];
+    counter!("http_requests_total", &labels).increment(1);
+    histogram!("http_request_duration_seconds", &labels).record(start.elapsed().as_secs_f64());
+
+    response
+}
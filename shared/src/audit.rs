@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "sqlx")]
+use sqlx::any::AnyRow;
+#[cfg(feature = "sqlx")]
+use sqlx::{FromRow, Row};
+use validator::Validate;
+
+use crate::pagination::MAX_PAGE_LIMIT;
+
+// Security-relevant event kinds
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEventType {
+    Register,
+    Login,
+    Logout,
+    TokenExchange,
+    SessionRevoke,
+    PasswordChange,
+    EmailChange,
+    TwoFactorEnable,
+    TwoFactorDisable,
+    UserDelete,
+    UserPurge,
+    UserDisable,
+    UserEnable,
+    RoleChange,
+    DataExport,
+}
+
+impl AuditEventType {
+    pub const ALL: [AuditEventType; 15] = [
+        AuditEventType::Register,
+        AuditEventType::Login,
+        AuditEventType::Logout,
+        AuditEventType::TokenExchange,
+        AuditEventType::SessionRevoke,
+        AuditEventType::PasswordChange,
+        AuditEventType::EmailChange,
+        AuditEventType::TwoFactorEnable,
+        AuditEventType::TwoFactorDisable,
+        AuditEventType::UserDelete,
+        AuditEventType::UserPurge,
+        AuditEventType::UserDisable,
+        AuditEventType::UserEnable,
+        AuditEventType::RoleChange,
+        AuditEventType::DataExport,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditEventType::Register => "register",
+            AuditEventType::Login => "login",
+            AuditEventType::Logout => "logout",
+            AuditEventType::TokenExchange => "token_exchange",
+            AuditEventType::SessionRevoke => "session_revoke",
+            AuditEventType::PasswordChange => "password_change",
+            AuditEventType::EmailChange => "email_change",
+            AuditEventType::TwoFactorEnable => "two_factor_enable",
+            AuditEventType::TwoFactorDisable => "two_factor_disable",
+            AuditEventType::UserDelete => "user_delete",
+            AuditEventType::UserPurge => "user_purge",
+            AuditEventType::UserDisable => "user_disable",
+            AuditEventType::UserEnable => "user_enable",
+            AuditEventType::RoleChange => "role_change",
+            AuditEventType::DataExport => "data_export",
+        }
+    }
+}
+
+// Whether the audited action succeeded
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+}
+
+impl AuditOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditOutcome::Success => "success",
+            AuditOutcome::Failure => "failure",
+        }
+    }
+}
+
+// Recorded audit event
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AuditEvent {
+    pub id: i64,
+    pub event_type: String,
+    pub outcome: String,
+    pub actor_uuid: Option<String>,
+    pub target: Option<String>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub detail: Option<String>,
+    pub created_at: i64,
+}
+
+#[cfg(feature = "sqlx")]
+impl<'r> FromRow<'r, AnyRow> for AuditEvent {
+    fn from_row(row: &'r AnyRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            event_type: row.try_get("event_type")?,
+            outcome: row.try_get("outcome")?,
+            actor_uuid: row.try_get("actor_uuid")?,
+            target: row.try_get("target")?,
+            ip: row.try_get("ip")?,
+            user_agent: row.try_get("user_agent")?,
+            detail: row.try_get("detail")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+// Audit log export file format
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Jsonl,
+}
+
+impl ExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, Validate)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+pub struct AuditQuery {
+    #[validate(range(
+        min = 1,
+        max = "MAX_PAGE_LIMIT",
+        message = "Limit must be between 1 and 100"
+    ))]
+    pub limit: Option<u32>,
+    pub offset: Option<u64>,
+    pub event_type: Option<AuditEventType>,
+    pub outcome: Option<AuditOutcome>,
+    pub actor_uuid: Option<String>,
+    pub target: Option<String>,
+    pub created_after: Option<i64>,
+    pub created_before: Option<i64>,
+    pub format: Option<ExportFormat>,
+}
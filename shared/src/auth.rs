@@ -0,0 +1,128 @@
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AuthToken {
+    pub access_token: String,
+    pub token_type: String,
+}
+
+impl AuthToken {
+    pub fn new(access_token: String) -> Self {
+        Self { access_token, token_type: "Bearer".to_string() }
+    }
+
+    pub fn from_string(string: String) -> Self {
+        Self { access_token: string, token_type: "Bearer".to_string() }
+    }
+
+    pub fn to_string(self: AuthToken) -> String {
+        self.access_token
+    }
+}
+
+impl Default for AuthToken {
+    fn default() -> Self {
+        Self { access_token: String::new(), token_type: "Bearer".to_string() }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum AuthErrorType {
+    AccountDisabled,
+    BadRequest,
+    Conflict,
+    Forbidden,
+    InvalidInput,
+    InvalidToken,
+    InvalidTwoFactorCode,
+    NotFound,
+    ServerError,
+    ServiceUnavailable,
+    TokenGeneration,
+    TwoFactorRequired,
+    UserExists,
+    UserNotExists,
+    WrongCredentials,
+}
+
+impl AuthErrorType {
+    // HTTP status for error type
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AuthErrorType::AccountDisabled => StatusCode::FORBIDDEN,
+            AuthErrorType::BadRequest => StatusCode::BAD_REQUEST,
+            AuthErrorType::Conflict => StatusCode::CONFLICT,
+            AuthErrorType::Forbidden => StatusCode::FORBIDDEN,
+            AuthErrorType::InvalidInput => StatusCode::UNPROCESSABLE_ENTITY,
+            AuthErrorType::InvalidToken => StatusCode::UNAUTHORIZED,
+            AuthErrorType::InvalidTwoFactorCode => StatusCode::UNAUTHORIZED,
+            AuthErrorType::NotFound => StatusCode::NOT_FOUND,
+            AuthErrorType::ServerError => StatusCode::INTERNAL_SERVER_ERROR,
+            AuthErrorType::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+            AuthErrorType::TokenGeneration => StatusCode::INTERNAL_SERVER_ERROR,
+            AuthErrorType::TwoFactorRequired => StatusCode::UNAUTHORIZED,
+            AuthErrorType::UserExists => StatusCode::CONFLICT,
+            AuthErrorType::UserNotExists => StatusCode::NOT_FOUND,
+            AuthErrorType::WrongCredentials => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    // Human-readable summary for error type
+    pub fn message(&self) -> &'static str {
+        match self {
+            AuthErrorType::AccountDisabled => "Account is disabled",
+            AuthErrorType::BadRequest => "Bad request",
+            AuthErrorType::Conflict => "Resource already exists",
+            AuthErrorType::Forbidden => "Insufficient permissions",
+            AuthErrorType::InvalidInput => "Invalid input",
+            AuthErrorType::InvalidToken => "Invalid token",
+            AuthErrorType::InvalidTwoFactorCode => "Invalid two-factor code",
+            AuthErrorType::NotFound => "Resource not found",
+            AuthErrorType::ServerError => "Server error",
+            AuthErrorType::ServiceUnavailable => "Service unavailable",
+            AuthErrorType::TokenGeneration => "Error generating token",
+            AuthErrorType::TwoFactorRequired => "Two-factor code required",
+            AuthErrorType::UserExists => "User already exists",
+            AuthErrorType::UserNotExists => "User does not exist",
+            AuthErrorType::WrongCredentials => "Incorrect password",
+        }
+    }
+
+    // Stable machine-readable identifier for error type
+    pub fn slug(&self) -> &'static str {
+        match self {
+            AuthErrorType::AccountDisabled => "account-disabled",
+            AuthErrorType::BadRequest => "bad-request",
+            AuthErrorType::Conflict => "conflict",
+            AuthErrorType::Forbidden => "forbidden",
+            AuthErrorType::InvalidInput => "invalid-input",
+            AuthErrorType::InvalidToken => "invalid-token",
+            AuthErrorType::InvalidTwoFactorCode => "invalid-two-factor-code",
+            AuthErrorType::NotFound => "not-found",
+            AuthErrorType::ServerError => "server-error",
+            AuthErrorType::ServiceUnavailable => "service-unavailable",
+            AuthErrorType::TokenGeneration => "token-generation",
+            AuthErrorType::TwoFactorRequired => "two-factor-required",
+            AuthErrorType::UserExists => "user-exists",
+            AuthErrorType::UserNotExists => "user-not-exists",
+            AuthErrorType::WrongCredentials => "wrong-credentials",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AuthErrorBody {
+    pub error_type: AuthErrorType,
+    pub error_message: String,
+}
+
+impl AuthErrorBody {
+    pub fn from_type(error_type: AuthErrorType) -> Self {
+        let error_message = error_type.message().to_string();
+        Self { error_type, error_message }
+    }
+}
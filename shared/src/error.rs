@@ -0,0 +1,52 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::auth::{AuthErrorBody, AuthErrorType};
+use crate::validation::FieldError;
+
+// Media type for problem details responses
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+// Prefix for problem type URIs
+pub const PROBLEM_TYPE_PREFIX: &str = "urn:rustenv:problem:";
+
+// RFC 7807 problem details body
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(flatten)]
+    pub body: AuthErrorBody,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, Vec<FieldError>>,
+}
+
+impl ProblemDetails {
+    pub fn from_type(error_type: AuthErrorType, detail: Option<String>) -> Self {
+        let body = AuthErrorBody {
+            error_message: detail.clone().unwrap_or_else(|| error_type.message().to_string()),
+            error_type,
+        };
+
+        Self {
+            problem_type: format!("{}{}", PROBLEM_TYPE_PREFIX, body.error_type.slug()),
+            title: body.error_type.message().to_string(),
+            status: body.error_type.status().as_u16(),
+            detail,
+            instance: None,
+            request_id: None,
+            body,
+            fields: BTreeMap::new(),
+        }
+    }
+}
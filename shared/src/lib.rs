@@ -0,0 +1,10 @@
+pub mod audit;
+pub mod auth;
+pub mod error;
+pub mod health;
+pub mod media;
+pub mod pagination;
+pub mod session;
+pub mod two_factor;
+pub mod user;
+pub mod validation;
@@ -0,0 +1,17 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+// Square thumbnail edge lengths avatars are stored at
+pub const AVATAR_SIZES: [u32; 3] = [64, 128, 256];
+
+// Content types accepted for avatar uploads
+pub const AVATAR_CONTENT_TYPES: [&str; 3] = ["image/jpeg", "image/png", "image/webp"];
+
+// Signed URLs for each avatar size
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AvatarUrls {
+    pub urls: BTreeMap<u32, String>,
+    pub expires_at: i64,
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+// Page size when none is requested
+pub const DEFAULT_PAGE_LIMIT: u32 = 20;
+
+// Largest page size a client may request
+pub const MAX_PAGE_LIMIT: u32 = 100;
+
+// Page of results with links to neighbouring pages
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    pub next: Option<String>,
+    pub prev: Option<String>,
+}
+
+// Sort direction
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    // Opposite sort direction
+    pub fn reverse(self) -> Self {
+        match self {
+            SortOrder::Asc => SortOrder::Desc,
+            SortOrder::Desc => SortOrder::Asc,
+        }
+    }
+}
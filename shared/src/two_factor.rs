@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::validation::TWO_FACTOR_CODE_REGEX;
+
+// Pending authenticator secret, enabled once a code from it is confirmed
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TwoFactorSetup {
+    // Base32 secret for manual entry
+    pub secret: String,
+    // otpauth:// URL for QR codes
+    pub otpauth_url: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TwoFactorCode {
+    #[validate(regex(path = *TWO_FACTOR_CODE_REGEX, message = "Code must be 6 digits"))]
+    pub code: String,
+}
+
+impl TwoFactorCode {
+    pub fn set(&self, key: &str, value: String) -> Result<Self, String> {
+        let mut clone = self.clone();
+        match key {
+            "code" => clone.code = value,
+            _ => return Err(format!("Invalid key: {}", key)),
+        }
+        Ok(clone)
+    }
+}
@@ -0,0 +1,321 @@
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize};
+#[cfg(feature = "sqlx")]
+use sqlx::any::AnyRow;
+#[cfg(feature = "sqlx")]
+use sqlx::{FromRow, Row};
+use validator::{Validate, ValidationError};
+
+use crate::audit::AuditEvent;
+use crate::pagination::{MAX_PAGE_LIMIT, SortOrder};
+use crate::session::SessionExport;
+use crate::validation::{
+    TWO_FACTOR_CODE_REGEX, USERNAME_REGEX, validate_avatar_url, validate_display_name,
+    validate_locale, validate_password, validate_timezone,
+};
+
+// Get boolean column, accepting integers from drivers without a boolean type (SQLite)
+#[cfg(feature = "sqlx")]
+pub fn try_get_bool(row: &AnyRow, column: &str) -> Result<bool, sqlx::Error> {
+    match row.try_get::<bool, _>(column) {
+        Ok(value) => Ok(value),
+        Err(_) => Ok(row.try_get::<i64, _>(column)? != 0),
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UserRegister {
+    #[validate(
+        length(min = 3, max = 32, message = "Username must be between 3 and 32 characters"),
+        regex(
+            path = *USERNAME_REGEX,
+            code = "username_charset",
+            message = "Username may only contain letters, numbers, '.', '_' and '-'"
+        )
+    )]
+    pub username: String,
+    #[validate(custom(function = "validate_password"))]
+    pub password: String,
+    #[validate(
+        length(max = 254, message = "Email must be at most 254 characters"),
+        email(message = "Email is not a valid email address")
+    )]
+    pub email: String,
+}
+
+impl UserRegister {
+    pub fn set(&self, key: &str, value: String) -> Result<Self, String> {
+        let mut clone = self.clone();
+        match key {
+            "username" => clone.username = value,
+            "password" => clone.password = value,
+            "email" => clone.email = value,
+            _ => return Err(format!("Invalid key: {}", key)),
+        }
+        Ok(clone)
+    }
+}
+
+impl fmt::Display for UserRegister {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "UserRegister {{ username: {}, password: {}, email: {} }}",
+            self.username, self.password, self.email
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UserLogin {
+    #[validate(length(
+        min = 1,
+        max = 254,
+        code = "login_required",
+        message = "Username or email is required"
+    ))]
+    pub username: String,
+    #[validate(length(min = 1, code = "password_required", message = "Password is required"))]
+    pub password: String,
+    // Current authenticator code, required for accounts with two-factor authentication
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(regex(path = *TWO_FACTOR_CODE_REGEX, message = "Code must be 6 digits"))]
+    pub two_factor_code: Option<String>,
+}
+
+impl UserLogin {
+    pub fn set(&self, key: &str, value: String) -> Result<Self, String> {
+        let mut clone = self.clone();
+        match key {
+            "username" => clone.username = value,
+            "password" => clone.password = value,
+            "two_factor_code" => {
+                clone.two_factor_code = Some(value).filter(|code| !code.is_empty())
+            }
+            _ => return Err(format!("Invalid key: {}", key)),
+        }
+        Ok(clone)
+    }
+}
+
+impl fmt::Display for UserLogin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "UserLogin {{ username: {}, password: {} }}", self.username, self.password)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UserInformation {
+    pub uuid: String,
+    pub username: String,
+    pub email: String,
+    pub is_admin: bool,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub locale: Option<String>,
+    pub timezone: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub last_login_at: Option<i64>,
+    pub disabled_at: Option<i64>,
+    pub two_factor_enabled_at: Option<i64>,
+}
+
+impl UserInformation {
+    pub fn is_disabled(&self) -> bool {
+        self.disabled_at.is_some()
+    }
+
+    pub fn has_two_factor(&self) -> bool {
+        self.two_factor_enabled_at.is_some()
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl<'r> FromRow<'r, AnyRow> for UserInformation {
+    fn from_row(row: &'r AnyRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            uuid: row.try_get("uuid")?,
+            username: row.try_get("username")?,
+            email: row.try_get("email")?,
+            is_admin: try_get_bool(row, "is_admin")?,
+            display_name: row.try_get("display_name")?,
+            avatar_url: row.try_get("avatar_url")?,
+            locale: row.try_get("locale")?,
+            timezone: row.try_get("timezone")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            last_login_at: row.try_get("last_login_at")?,
+            disabled_at: row.try_get("disabled_at")?,
+            two_factor_enabled_at: row.try_get("totp_enabled_at")?,
+        })
+    }
+}
+
+impl fmt::Display for UserInformation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "UserInformation {{ uuid: {}, username: {}, email: {}, is_admin: {} }}",
+            self.uuid, self.username, self.email, self.is_admin
+        )
+    }
+}
+
+// Partial profile update, where an absent field is left unchanged and null clears it
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UserProfileUpdate {
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    #[validate(custom(function = "validate_display_name"))]
+    pub display_name: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    #[validate(custom(function = "validate_avatar_url"))]
+    pub avatar_url: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    #[validate(custom(function = "validate_timezone"))]
+    pub timezone: Option<Option<String>>,
+}
+
+impl UserProfileUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.display_name.is_none()
+            && self.avatar_url.is_none()
+            && self.locale.is_none()
+            && self.timezone.is_none()
+    }
+}
+
+// Deserialize a present field, keeping null distinct from absent
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+// Own password change, confirmed with the current password
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UserPasswordChange {
+    #[validate(length(
+        min = 1,
+        code = "password_required",
+        message = "Current password is required"
+    ))]
+    pub current_password: String,
+    #[validate(custom(function = "validate_password"))]
+    pub new_password: String,
+}
+
+impl UserPasswordChange {
+    pub fn set(&self, key: &str, value: String) -> Result<Self, String> {
+        let mut clone = self.clone();
+        match key {
+            "current_password" => clone.current_password = value,
+            "new_password" => clone.new_password = value,
+            _ => return Err(format!("Invalid key: {}", key)),
+        }
+        Ok(clone)
+    }
+}
+
+// Own email change, confirmed with the password
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UserEmailChange {
+    #[validate(
+        length(max = 254, message = "Email must be at most 254 characters"),
+        email(message = "Email is not a valid email address")
+    )]
+    pub email: String,
+    #[validate(length(min = 1, code = "password_required", message = "Password is required"))]
+    pub password: String,
+}
+
+impl UserEmailChange {
+    pub fn set(&self, key: &str, value: String) -> Result<Self, String> {
+        let mut clone = self.clone();
+        match key {
+            "email" => clone.email = value,
+            "password" => clone.password = value,
+            _ => return Err(format!("Invalid key: {}", key)),
+        }
+        Ok(clone)
+    }
+}
+
+// Column to sort user listings by
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum UserSort {
+    Username,
+    Email,
+    #[default]
+    CreatedAt,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, Validate)]
+#[cfg_attr(
+    feature = "openapi",
+    derive(utoipa::IntoParams),
+    into_params(parameter_in = Query)
+)]
+#[validate(schema(function = "validate_user_list_query", skip_on_field_errors = false))]
+pub struct UserListQuery {
+    #[validate(range(
+        min = 1,
+        max = "MAX_PAGE_LIMIT",
+        message = "Limit must be between 1 and 100"
+    ))]
+    pub limit: Option<u32>,
+    pub offset: Option<u64>,
+    pub cursor: Option<String>,
+    // Matches username, email or display name
+    #[validate(length(max = 254, message = "Search must be at most 254 characters"))]
+    pub search: Option<String>,
+    #[validate(length(max = 254, message = "Username filter must be at most 254 characters"))]
+    pub username: Option<String>,
+    #[validate(length(max = 254, message = "Email filter must be at most 254 characters"))]
+    pub email: Option<String>,
+    pub is_admin: Option<bool>,
+    pub created_after: Option<i64>,
+    pub created_before: Option<i64>,
+    pub sort: Option<UserSort>,
+    pub order: Option<SortOrder>,
+}
+
+// Reject mixing offset and cursor pagination
+fn validate_user_list_query(query: &UserListQuery) -> Result<(), ValidationError> {
+    if query.offset.is_some() && query.cursor.is_some() {
+        return Err(ValidationError::new("pagination_mode")
+            .with_message("Use either offset or cursor pagination, not both".into()));
+    }
+    Ok(())
+}
+
+// Admin role change for a user
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UserRoleUpdate {
+    pub is_admin: bool,
+}
+
+// Archive of everything stored about a user
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UserDataExport {
+    pub exported_at: i64,
+    pub profile: UserInformation,
+    pub sessions: Vec<SessionExport>,
+    pub audit_events: Vec<AuditEvent>,
+}
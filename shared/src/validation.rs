@@ -0,0 +1,212 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use validator::{ValidateUrl, ValidationError, ValidationErrors};
+
+// Minimum password length
+pub const PASSWORD_MIN_LENGTH: usize = 8;
+
+// Maximum password length in bytes (bcrypt ignores anything past 72 bytes)
+pub const PASSWORD_MAX_BYTES: usize = 72;
+
+// Allowed username characters
+pub static USERNAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[A-Za-z0-9_.-]+$").expect("Invalid username regex"));
+
+// Six digit authenticator code
+pub static TWO_FACTOR_CODE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[0-9]{6}$").expect("Invalid two-factor code regex"));
+
+// Bundled list of common and breached passwords
+static COMMON_PASSWORDS: Lazy<HashSet<String>> = Lazy::new(|| {
+    include_str!("../assets/common_passwords.txt")
+        .lines()
+        .map(|line| line.trim().to_lowercase())
+        .filter(|line| !line.is_empty())
+        .collect()
+});
+
+// Validate password against password policy
+pub fn validate_password(password: &str) -> Result<(), ValidationError> {
+    if password.chars().count() < PASSWORD_MIN_LENGTH {
+        return Err(ValidationError::new("password_length").with_message(Cow::from(format!(
+            "Password must be at least {} characters",
+            PASSWORD_MIN_LENGTH
+        ))));
+    }
+    if password.len() > PASSWORD_MAX_BYTES {
+        return Err(ValidationError::new("password_length").with_message(Cow::from(format!(
+            "Password must be at most {} bytes",
+            PASSWORD_MAX_BYTES
+        ))));
+    }
+    if COMMON_PASSWORDS.contains(&password.to_lowercase()) {
+        return Err(ValidationError::new("password_common")
+            .with_message(Cow::from("Password is too common")));
+    }
+    Ok(())
+}
+
+// Maximum display name length
+pub const DISPLAY_NAME_MAX_LENGTH: usize = 64;
+
+// Maximum avatar URL length
+pub const AVATAR_URL_MAX_LENGTH: usize = 2048;
+
+// BCP 47 language tag such as "en" or "pt-BR"
+static LOCALE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[A-Za-z]{2,3}(-[A-Za-z0-9]{2,8})*$").expect("Invalid locale regex"));
+
+// IANA time zone name such as "UTC" or "America/New_York"
+static TIMEZONE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[A-Za-z][A-Za-z0-9_+-]*(/[A-Za-z0-9_+-]+){0,2}$").expect("Invalid timezone regex")
+});
+
+// Validate display name is non-blank and not too long
+pub fn validate_display_name(display_name: &str) -> Result<(), ValidationError> {
+    let length = display_name.chars().count();
+    if display_name.trim().is_empty() || length > DISPLAY_NAME_MAX_LENGTH {
+        return Err(ValidationError::new("display_name_length").with_message(Cow::from(format!(
+            "Display name must be between 1 and {} characters",
+            DISPLAY_NAME_MAX_LENGTH
+        ))));
+    }
+    Ok(())
+}
+
+// Validate avatar URL is an absolute HTTPS URL
+pub fn validate_avatar_url(avatar_url: &str) -> Result<(), ValidationError> {
+    if avatar_url.len() > AVATAR_URL_MAX_LENGTH
+        || !avatar_url.starts_with("https://")
+        || !avatar_url.validate_url()
+    {
+        return Err(ValidationError::new("avatar_url")
+            .with_message(Cow::from("Avatar URL must be an HTTPS URL")));
+    }
+    Ok(())
+}
+
+// Validate locale is a BCP 47 language tag
+pub fn validate_locale(locale: &str) -> Result<(), ValidationError> {
+    if locale.len() > 35 || !LOCALE_REGEX.is_match(locale) {
+        return Err(ValidationError::new("locale")
+            .with_message(Cow::from("Locale must be a language tag such as \"en-US\"")));
+    }
+    Ok(())
+}
+
+// Validate timezone looks like an IANA time zone name
+pub fn validate_timezone(timezone: &str) -> Result<(), ValidationError> {
+    if timezone.len() > 64 || !TIMEZONE_REGEX.is_match(timezone) {
+        return Err(ValidationError::new("timezone").with_message(Cow::from(
+            "Timezone must be an IANA time zone name such as \"Europe/Berlin\"",
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FieldError {
+    pub code: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn from_error(error: &ValidationError) -> Self {
+        let message = match &error.message {
+            Some(message) => message.to_string(),
+            None => format!("Invalid value ({})", error.code),
+        };
+
+        Self { code: error.code.to_string(), message }
+    }
+}
+
+// Collect field errors keyed by field name
+pub fn field_errors(errors: &ValidationErrors) -> BTreeMap<String, Vec<FieldError>> {
+    errors
+        .field_errors()
+        .into_iter()
+        .map(|(field, errors)| {
+            (field.to_string(), errors.iter().map(FieldError::from_error).collect())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use validator::Validate;
+
+    use super::*;
+    use crate::user::UserRegister;
+
+    // Registration payload passing every rule
+    fn register() -> UserRegister {
+        UserRegister {
+            username: "valid_user-1.0".to_string(),
+            password: "correct horse battery staple".to_string(),
+            email: "valid@example.com".to_string(),
+        }
+    }
+
+    // Codes of the errors reported for field
+    fn codes(errors: &ValidationErrors, field: &str) -> Vec<String> {
+        field_errors(errors)
+            .remove(field)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|error| error.code)
+            .collect()
+    }
+
+    #[test]
+    fn password_minimum_length() {
+        assert_eq!(validate_password("short12").unwrap_err().code, "password_length");
+        assert!(validate_password("tr0ub4dor").is_ok());
+        // Length counts characters, not bytes
+        assert_eq!(validate_password("ääääääa").unwrap_err().code, "password_length");
+    }
+
+    #[test]
+    fn password_bcrypt_cap() {
+        assert!(validate_password(&"x".repeat(PASSWORD_MAX_BYTES)).is_ok());
+        assert_eq!(
+            validate_password(&"x".repeat(PASSWORD_MAX_BYTES + 1)).unwrap_err().code,
+            "password_length"
+        );
+        // Multibyte characters count against the cap by their encoded size
+        assert_eq!(validate_password(&"ä".repeat(37)).unwrap_err().code, "password_length");
+    }
+
+    #[test]
+    fn password_common_list() {
+        assert_eq!(validate_password("password123").unwrap_err().code, "password_common");
+        assert_eq!(validate_password("PassWord123").unwrap_err().code, "password_common");
+    }
+
+    #[test]
+    fn username_charset() {
+        let user = UserRegister { username: "no spaces!".to_string(), ..register() };
+        assert_eq!(codes(&user.validate().unwrap_err(), "username"), ["username_charset"]);
+        let user = UserRegister { username: "ab".to_string(), ..register() };
+        assert_eq!(codes(&user.validate().unwrap_err(), "username"), ["length"]);
+        assert!(register().validate().is_ok());
+    }
+
+    #[test]
+    fn register_reports_each_field() {
+        let user = UserRegister {
+            username: "x".repeat(33),
+            password: "password".to_string(),
+            email: "not-an-email".to_string(),
+        };
+        let errors = user.validate().unwrap_err();
+        assert_eq!(codes(&errors, "username"), ["length"]);
+        assert_eq!(codes(&errors, "password"), ["password_common"]);
+        assert_eq!(codes(&errors, "email"), ["email"]);
+    }
+}
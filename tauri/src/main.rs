@@ -1,14 +1,55 @@
 #![cfg_attr(all(not(debug_assertions), target_os = "windows"), windows_subsystem = "windows")]
 
+mod settings;
+
+use std::env;
+
 use rustenv_backend::app;
+use tauri::{RunEvent, WindowEvent};
+use tracing_subscriber::EnvFilter;
 
 fn main() {
+    init_tracing();
+
     let port = portpicker::pick_unused_port().expect("failed to pick port");
-    tauri::async_runtime::spawn(app(port));
+    let (shutdown, backend) = app(port);
+    let mut backend = Some(tauri::async_runtime::spawn(backend));
 
+    let window_shutdown = shutdown.clone();
     tauri::Builder::default()
         .manage(port)
-        .invoke_handler(tauri::generate_handler![])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(tauri::generate_handler![
+            settings::load_settings,
+            settings::update_settings
+        ])
+        .on_window_event(move |window, event| {
+            // Stop backend when the main window closes
+            if window.label() == "main" && matches!(event, WindowEvent::Destroyed) {
+                window_shutdown.shutdown();
+            }
+        })
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(move |_app, event| {
+            // Wait for backend to drain before the process exits
+            if let RunEvent::Exit = event {
+                shutdown.shutdown();
+                if let Some(backend) = backend.take() {
+                    if let Err(error) = tauri::async_runtime::block_on(backend) {
+                        tracing::error!(%error, "Backend task failed");
+                    }
+                }
+            }
+        });
+}
+
+// Install tracing subscriber for backend logs
+fn init_tracing() {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new("info,rustenv_backend=debug,tower_http=debug"));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    match env::var("LOG_FORMAT").as_deref() {
+        Ok("json") => builder.json().init(),
+        _ => builder.init(),
+    }
 }
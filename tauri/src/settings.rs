@@ -0,0 +1,66 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+// Settings file name in the app config directory
+const SETTINGS_FILE: &str = "settings.json";
+
+// Desktop preferences set from the app, which treats values as opaque
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Settings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+impl Settings {
+    // Overwrite fields set in update
+    fn merge(&mut self, update: Settings) {
+        if update.theme.is_some() {
+            self.theme = update.theme;
+        }
+        if update.locale.is_some() {
+            self.locale = update.locale;
+        }
+    }
+}
+
+fn settings_path(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_config_dir()
+        .map(|dir| dir.join(SETTINGS_FILE))
+        .map_err(|error| error.to_string())
+}
+
+// Read settings file, falling back to defaults when missing or unreadable
+fn read(app: &AppHandle) -> Settings {
+    settings_path(app)
+        .ok()
+        .and_then(|path| fs::read(path).ok())
+        .and_then(|data| serde_json::from_slice(&data).ok())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn load_settings(app: AppHandle) -> Settings {
+    read(&app)
+}
+
+// Save fields set in settings, returning the merged settings
+#[tauri::command]
+pub fn update_settings(app: AppHandle, settings: Settings) -> Result<Settings, String> {
+    let path = settings_path(&app)?;
+    let mut merged = read(&app);
+    merged.merge(settings);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+    }
+    let data = serde_json::to_vec_pretty(&merged).map_err(|error| error.to_string())?;
+    fs::write(&path, data).map_err(|error| error.to_string())?;
+    tracing::debug!(path = %path.display(), "Saved settings");
+    Ok(merged)
+}